@@ -0,0 +1,32 @@
+use tauri::State;
+
+use crate::db::backup::{
+    create_backup as create_backup_in_db, list_backups as list_backups_in_db,
+    restore_backup as restore_backup_in_db,
+};
+use crate::db::queries::settings::fetch_locale;
+use crate::error::AppError;
+use crate::i18n::set_current_locale;
+use crate::models::{BackupInfo, BackupReason};
+use crate::state::AppState;
+
+#[tauri::command]
+pub fn create_backup(state: State<AppState>) -> Result<BackupInfo, AppError> {
+    let conn = state.db.get()?;
+    create_backup_in_db(&conn, BackupReason::Manual).map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn list_backups() -> Result<Vec<BackupInfo>, AppError> {
+    list_backups_in_db().map_err(AppError::from)
+}
+
+/// Restores `file_name` from the backup directory and returns the backup
+/// that was taken of the database it replaced.
+#[tauri::command]
+pub fn restore_backup(file_name: String, state: State<AppState>) -> Result<BackupInfo, AppError> {
+    let mut conn = state.db.get()?;
+    let safety_copy = restore_backup_in_db(&mut conn, &state.vault, &file_name)?;
+    set_current_locale(fetch_locale(&conn)?);
+    Ok(safety_copy)
+}
@@ -1,70 +1,287 @@
 use chrono::Utc;
 use tauri::State;
 
+use crate::db::pool::ConnectionPool;
 use crate::db::queries::bots::delete_bot_account as delete_bot_account_in_db;
-use crate::db::queries::bots::fetch_all_bots;
 use crate::db::queries::bots::get_bot_config as get_bot_config_in_db;
-use crate::db::queries::bots::insert_bot_account;
 use crate::db::queries::bots::update_bot_account as update_bot_account_in_db;
 use crate::db::queries::bots::update_bot_config as update_bot_config_in_db;
-use crate::models::{BotAccount, BotConfig};
+use crate::db::queries::bots::{
+    fetch_all_bots, find_bot_account_by_id, insert_bot_account, update_bot_credential_health,
+    update_bot_profile,
+};
+use crate::db::queries::logs::insert_execution_log;
+use crate::error::AppError;
+use crate::i18n::Message;
+use crate::models::{
+    ApiTier, BotAccount, BotAccountSummary, BotAccountUpdate, BotConfig, CredentialHealth,
+    CredentialVerification, ExecutionLog, ProfileRefreshFailure, ProfileRefreshReport,
+};
+use crate::services::twitter::{rate_limits, TwitterApi, TwitterErrorCode};
 use crate::state::AppState;
+use crate::utils::vault::CredentialVault;
+
+const STATUS_ACTIVE: &str = "active";
+const STATUS_INVALID_CREDENTIALS: &str = "invalid_credentials";
 
 #[tauri::command]
-pub fn get_bot_accounts(state: State<AppState>) -> Result<Vec<BotAccount>, String> {
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    fetch_all_bots(&conn).map_err(|e| e.to_string())
+pub fn get_bot_accounts(state: State<AppState>) -> Result<Vec<BotAccountSummary>, AppError> {
+    let conn = state.db.get()?;
+    let accounts = fetch_all_bots(&conn, &state.vault)?;
+    Ok(accounts.iter().map(BotAccountSummary::from).collect())
 }
 
 #[tauri::command]
-pub fn add_bot_account(account: BotAccount, state: State<AppState>) -> Result<i64, String> {
+pub fn add_bot_account(account: BotAccount, state: State<AppState>) -> Result<i64, AppError> {
     if account.account_name.trim().is_empty() {
-        return Err("Account name is required".to_string());
+        return Err(AppError::Validation(Message::new(
+            "bot.account_name_required",
+        )));
     }
+    validate_api_type(&account.api_type)?;
     if account.api_key.trim().is_empty() {
-        return Err("API key is required".to_string());
+        return Err(AppError::Validation(Message::new("bot.api_key_required")));
     }
     if account.api_key_secret.trim().is_empty() {
-        return Err("API key secret is required".to_string());
+        return Err(AppError::Validation(Message::new(
+            "bot.api_key_secret_required",
+        )));
     }
     if account.access_token.trim().is_empty() {
-        return Err("Access token is required".to_string());
+        return Err(AppError::Validation(Message::new(
+            "bot.access_token_required",
+        )));
     }
     if account.access_token_secret.trim().is_empty() {
-        return Err("Access token secret is required".to_string());
+        return Err(AppError::Validation(Message::new(
+            "bot.access_token_secret_required",
+        )));
     }
 
     let now = Utc::now().to_rfc3339();
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    insert_bot_account(&conn, &account, &now).map_err(|e| e.to_string())
+    let conn = state.db.get()?;
+    insert_bot_account(&conn, &state.vault, &account, &now).map_err(AppError::from)
 }
 
 #[tauri::command]
-pub fn update_bot_account(account: BotAccount, state: State<AppState>) -> Result<(), String> {
-    if account.id.is_none() {
-        return Err("Account ID is required".to_string());
+pub fn update_bot_account(
+    account: BotAccountUpdate,
+    state: State<AppState>,
+) -> Result<(), AppError> {
+    if account.account_name.trim().is_empty() {
+        return Err(AppError::Validation(Message::new(
+            "bot.account_name_required",
+        )));
     }
+    validate_api_type(&account.api_type)?;
     let now = Utc::now().to_rfc3339();
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    update_bot_account_in_db(&conn, &account, &now).map_err(|e| e.to_string())
+    let conn = state.db.get()?;
+    update_bot_account_in_db(&conn, &state.vault, &account, &now).map_err(AppError::from)
+}
+
+fn validate_api_type(api_type: &str) -> Result<(), AppError> {
+    match ApiTier::from_api_type(api_type) {
+        Some(_) => Ok(()),
+        None => Err(AppError::Validation(
+            Message::new("bot.unknown_api_type").with("api_type", api_type),
+        )),
+    }
+}
+
+#[tauri::command]
+pub fn delete_bot_account(id: i64, state: State<AppState>) -> Result<(), AppError> {
+    let conn = state.db.get()?;
+    delete_bot_account_in_db(&conn, id).map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn get_bot_config(account_id: i64, state: State<AppState>) -> Result<BotConfig, AppError> {
+    let conn = state.db.get()?;
+    get_bot_config_in_db(&conn, account_id).map_err(AppError::from)
 }
 
 #[tauri::command]
-pub fn delete_bot_account(id: i64, state: State<AppState>) -> Result<(), String> {
+pub fn update_bot_config(config: BotConfig, state: State<AppState>) -> Result<(), AppError> {
     let now = Utc::now().to_rfc3339();
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    delete_bot_account_in_db(&conn, id, &now).map_err(|e| e.to_string())
+    let conn = state.db.get()?;
+    update_bot_config_in_db(&conn, &config, &now).map_err(AppError::from)
 }
 
 #[tauri::command]
-pub fn get_bot_config(account_id: i64, state: State<AppState>) -> Result<BotConfig, String> {
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    get_bot_config_in_db(&conn, account_id).map_err(|e| e.to_string())
+pub async fn verify_bot_credentials(
+    account_id: i64,
+    state: State<'_, AppState>,
+) -> Result<CredentialVerification, AppError> {
+    verify_credentials(&state.db, &state.vault, state.twitter.as_ref(), account_id).await
 }
 
+/// Re-verifies every account in turn, updating the stored profiles. One
+/// account failing does not stop the others.
 #[tauri::command]
-pub fn update_bot_config(config: BotConfig, state: State<AppState>) -> Result<(), String> {
+pub async fn refresh_bot_profiles(
+    state: State<'_, AppState>,
+) -> Result<ProfileRefreshReport, AppError> {
+    let accounts = {
+        let conn = state.db.get()?;
+        fetch_all_bots(&conn, &state.vault)?
+    };
+
+    let mut report = ProfileRefreshReport {
+        verified: Vec::new(),
+        failed: Vec::new(),
+    };
+    for account in accounts {
+        let account_id = account.id.unwrap_or_default();
+        match verify_credentials(&state.db, &state.vault, state.twitter.as_ref(), account_id).await
+        {
+            Ok(verification) => report.verified.push(verification),
+            Err(error) => report.failed.push(ProfileRefreshFailure {
+                account_id,
+                account_name: account.account_name,
+                message: error.message().to_string(),
+            }),
+        }
+    }
+
+    println!(
+        "Refreshed bot profiles: {} verified, {} failed",
+        report.verified.len(),
+        report.failed.len()
+    );
+    Ok(report)
+}
+
+/// Looks the account's credentials up with `GET /2/users/me` and stores the
+/// profile they belong to. A 401 sets `status` to `invalid_credentials`,
+/// which a later successful check turns back into `active`; a paused account
+/// stays paused. Other failures only mark the check as failed and are
+/// returned as `Err`. Every check is logged as a `read` and recorded in
+/// `credential_health` and `last_verified_at`.
+pub async fn verify_credentials(
+    db: &ConnectionPool,
+    vault: &CredentialVault,
+    api: &dyn TwitterApi,
+    account_id: i64,
+) -> Result<CredentialVerification, AppError> {
+    let account = {
+        let conn = db.get()?;
+        find_bot_account_by_id(&conn, vault, account_id)?
+    };
+
+    let result = rate_limits::get_me(db, api, &account).await;
+
     let now = Utc::now().to_rfc3339();
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    update_bot_config_in_db(&conn, &config, &now).map_err(|e| e.to_string())
+    let mut log = ExecutionLog {
+        id: None,
+        account_id,
+        log_type: "read".to_string(),
+        message: String::new(),
+        message_id: None,
+        message_params: Default::default(),
+        error_code: None,
+        tweet_id: None,
+        tweet_content: None,
+        status: "success".to_string(),
+        created_at: now.clone(),
+    };
+    let conn = db.get()?;
+
+    match result {
+        Ok(user) => {
+            let name_mismatch = !same_handle(&account.account_name, &user.username);
+            let message = if name_mismatch {
+                Message::new("bot.credentials_name_mismatch")
+                    .with("account_name", &account.account_name)
+                    .with("username", &user.username)
+            } else {
+                Message::new("bot.credentials_verified").with("username", &user.username)
+            };
+
+            let status = if account.status == STATUS_INVALID_CREDENTIALS {
+                STATUS_ACTIVE
+            } else {
+                account.status.as_str()
+            };
+            update_bot_profile(
+                &conn,
+                account_id,
+                &user.id,
+                &user.username,
+                &user.name,
+                status,
+                &now,
+            )?;
+            log.set_message(message.clone());
+            insert_execution_log(&conn, &log, &now)?;
+
+            Ok(CredentialVerification {
+                account_id,
+                status: status.to_string(),
+                credential_health: CredentialHealth::Valid,
+                twitter_user_id: Some(user.id),
+                twitter_username: Some(user.username),
+                twitter_display_name: Some(user.name),
+                name_mismatch,
+                message: message.to_string(),
+            })
+        }
+        Err(error) => {
+            let invalid = matches!(
+                error,
+                AppError::TwitterApi {
+                    code: TwitterErrorCode::Unauthorized,
+                    ..
+                }
+            );
+            let message_id = if invalid {
+                "bot.credentials_invalid"
+            } else {
+                "bot.credentials_check_failed"
+            };
+            let message = Message::new(message_id).with_message("error", error.message());
+
+            log.status = "error".to_string();
+            log.error_code = Some(error.log_code().to_string());
+            log.set_message(message.clone());
+            insert_execution_log(&conn, &log, &now)?;
+
+            if !invalid {
+                update_bot_credential_health(
+                    &conn,
+                    account_id,
+                    CredentialHealth::CheckFailed,
+                    &account.status,
+                    &now,
+                )?;
+                return Err(error);
+            }
+
+            update_bot_credential_health(
+                &conn,
+                account_id,
+                CredentialHealth::Invalid,
+                STATUS_INVALID_CREDENTIALS,
+                &now,
+            )?;
+
+            Ok(CredentialVerification {
+                account_id,
+                status: STATUS_INVALID_CREDENTIALS.to_string(),
+                credential_health: CredentialHealth::Invalid,
+                twitter_user_id: account.twitter_user_id,
+                twitter_username: account.twitter_username,
+                twitter_display_name: account.twitter_display_name,
+                name_mismatch: false,
+                message: message.to_string(),
+            })
+        }
+    }
+}
+
+/// Handles compare case-insensitively, with or without a leading `@`.
+fn same_handle(account_name: &str, username: &str) -> bool {
+    account_name
+        .trim()
+        .trim_start_matches('@')
+        .eq_ignore_ascii_case(username)
 }
@@ -1,11 +1,50 @@
+use chrono::Utc;
 use tauri::State;
 
 use crate::db::queries::bots::fetch_dashboard_stats;
-use crate::models::DashboardStats;
+use crate::db::queries::quota::{fetch_quota_usage, find_quota_usage};
+use crate::db::queries::rate_limits::fetch_account_rate_limits;
+use crate::error::AppError;
+use crate::models::{AccountRateLimit, DashboardStats, QuotaUsage};
+use crate::services::quota::quota_month;
 use crate::state::AppState;
 
 #[tauri::command]
-pub fn get_dashboard_stats(state: State<AppState>) -> Result<DashboardStats, String> {
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    fetch_dashboard_stats(&conn).map_err(|e| e.to_string())
+pub fn get_dashboard_stats(state: State<AppState>) -> Result<DashboardStats, AppError> {
+    let conn = state.db.get()?;
+    fetch_dashboard_stats(&conn).map_err(AppError::from)
+}
+
+/// Remaining quota per account and endpoint. A window whose reset time has
+/// passed is reported as full again.
+#[tauri::command]
+pub fn get_rate_limits(state: State<AppState>) -> Result<Vec<AccountRateLimit>, AppError> {
+    let conn = state.db.get()?;
+    let now = Utc::now().timestamp();
+
+    let mut rate_limits = fetch_account_rate_limits(&conn)?;
+    for rate_limit in &mut rate_limits {
+        if rate_limit.reset_at.is_some_and(|reset_at| reset_at <= now) {
+            rate_limit.remaining = rate_limit.limit;
+            rate_limit.reset_at = None;
+        }
+    }
+
+    Ok(rate_limits)
+}
+
+/// This month's posts and reads against each account's tier budget, or just
+/// `account_id`'s when given.
+#[tauri::command]
+pub fn get_quota_usage(
+    account_id: Option<i64>,
+    state: State<AppState>,
+) -> Result<Vec<QuotaUsage>, AppError> {
+    let conn = state.db.get()?;
+    let month = quota_month(Utc::now());
+
+    match account_id {
+        Some(id) => Ok(vec![find_quota_usage(&conn, id, &month)?]),
+        None => Ok(fetch_quota_usage(&conn, &month)?),
+    }
 }
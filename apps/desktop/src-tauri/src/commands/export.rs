@@ -1,18 +1,67 @@
 use tauri::State;
 
+use crate::db::backup::create_backup;
+use crate::error::AppError;
+use crate::models::{
+    BackupReason, DataExportOptions, DataExportReport, DataImportOptions, GithubExportOptions,
+    GithubExportReport, ImportReport, StateImportReport,
+};
 use crate::services::{
     export_data as export_data_service, export_github_config as export_github_config_service,
+    import_data as import_data_service, import_system_state as import_system_state_service,
 };
 use crate::state::AppState;
 
 #[tauri::command]
-pub fn export_data(path: String, state: State<AppState>) -> Result<(), String> {
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    export_data_service(&conn, &path)
+pub fn export_data(
+    path: String,
+    options: Option<DataExportOptions>,
+    state: State<AppState>,
+) -> Result<DataExportReport, AppError> {
+    let options = options.unwrap_or_default();
+    let conn = state.db.get()?;
+    export_data_service(&conn, &state.vault, &path, &options)
+}
+
+#[tauri::command]
+pub fn export_github_config(
+    path: String,
+    options: Option<GithubExportOptions>,
+    state: State<AppState>,
+) -> Result<GithubExportReport, AppError> {
+    let options = options.unwrap_or_default();
+    let conn = state.db.get()?;
+    export_github_config_service(&conn, &state.vault, &path, &options)
 }
 
+/// Previews (or with `apply`, writes) the progress recorded in the Actions
+/// `system-state.json` at `path`.
 #[tauri::command]
-pub fn export_github_config(path: String, state: State<AppState>) -> Result<(), String> {
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    export_github_config_service(&conn, &path)
+pub fn import_system_state(
+    path: String,
+    apply: bool,
+    state: State<AppState>,
+) -> Result<StateImportReport, AppError> {
+    let conn = state.db.get()?;
+    import_system_state_service(&conn, &path, apply)
+}
+
+/// Restores a data export or Actions config from any version of the app.
+/// A snapshot of the database is taken first unless it is a dry run.
+#[tauri::command]
+pub fn import_data(
+    path: String,
+    options: Option<DataImportOptions>,
+    state: State<AppState>,
+) -> Result<ImportReport, AppError> {
+    let options = options.unwrap_or_default();
+    let conn = state.db.get()?;
+    let safety_backup = match options.dry_run {
+        true => None,
+        false => Some(create_backup(&conn, BackupReason::PreRestore)?),
+    };
+
+    let mut report = import_data_service(&conn, &state.vault, &path, &options)?;
+    report.safety_backup = safety_backup.map(|backup| backup.file_name);
+    Ok(report)
 }
@@ -2,6 +2,8 @@
 use tauri::State;
 
 use crate::db::queries::logs::{fetch_execution_logs, insert_execution_log};
+use crate::error::AppError;
+use crate::i18n::current_locale;
 use crate::models::ExecutionLog;
 use crate::state::AppState;
 
@@ -10,15 +12,21 @@ pub fn get_execution_logs(
     account_id: Option<i64>,
     limit: Option<i32>,
     state: State<AppState>,
-) -> Result<Vec<ExecutionLog>, String> {
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
+) -> Result<Vec<ExecutionLog>, AppError> {
+    let conn = state.db.get()?;
     let limit = limit.unwrap_or(100);
-    fetch_execution_logs(&conn, account_id, limit).map_err(|e| e.to_string())
+    let locale = current_locale();
+
+    let mut logs = fetch_execution_logs(&conn, account_id, limit)?;
+    for log in &mut logs {
+        log.localize(locale);
+    }
+    Ok(logs)
 }
 
 #[tauri::command]
-pub fn add_execution_log(log: ExecutionLog, state: State<AppState>) -> Result<i64, String> {
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
+pub fn add_execution_log(log: ExecutionLog, state: State<AppState>) -> Result<i64, AppError> {
+    let conn = state.db.get()?;
     let now = Utc::now().to_rfc3339();
-    insert_execution_log(&conn, &log, &now).map_err(|e| e.to_string())
+    insert_execution_log(&conn, &log, &now).map_err(AppError::from)
 }
@@ -1,8 +1,10 @@
-﻿pub mod bots;
+﻿pub mod backups;
+pub mod bots;
 pub mod dashboard;
 pub mod export;
 pub mod logs;
 pub mod replies;
 pub mod schedules;
+pub mod settings;
 pub mod tests;
 pub mod users;
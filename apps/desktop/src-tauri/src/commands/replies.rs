@@ -1,14 +1,14 @@
 use chrono::Utc;
 use tauri::State;
 
-use crate::db::cleanup::cleanup_orphaned_reply_settings;
 use crate::db::queries::bots::bot_exists;
 use crate::db::queries::replies::{
-    count_active_reply_settings, delete_reply_setting,
-    fetch_reply_settings as fetch_reply_settings_query,
+    delete_reply_setting, fetch_reply_settings as fetch_reply_settings_query,
     save_reply_settings as save_reply_settings_query,
     update_last_checked_tweet as update_last_checked_tweet_query,
 };
+use crate::error::AppError;
+use crate::i18n::Message;
 use crate::models::ReplySettings;
 use crate::state::AppState;
 
@@ -18,41 +18,45 @@ pub fn save_reply_settings(
     target_bot_ids: Vec<i64>,
     reply_content: String,
     state: State<AppState>,
-) -> Result<i64, String> {
+) -> Result<i64, AppError> {
     if reply_content.trim().is_empty() {
-        return Err("返信内容が空です".to_string());
+        return Err(AppError::Validation(Message::new("reply.content_empty")));
     }
     if target_bot_ids.is_empty() {
-        return Err("監視対象Botが選択されていません".to_string());
+        return Err(AppError::Validation(Message::new("reply.no_targets")));
     }
 
     let now = Utc::now().to_rfc3339();
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
+    let conn = state.db.get()?;
 
-    if !bot_exists(&conn, reply_bot_id).map_err(|e| e.to_string())? {
-        return Err(format!("返信Bot ID {} が存在しません", reply_bot_id));
+    if !bot_exists(&conn, reply_bot_id)? {
+        return Err(AppError::NotFound(
+            Message::new("reply.reply_bot_not_found").with("id", reply_bot_id),
+        ));
     }
 
     for target_id in &target_bot_ids {
-        if !bot_exists(&conn, *target_id).map_err(|e| e.to_string())? {
-            return Err(format!("監視対象Bot ID {} が存在しません", target_id));
+        if !bot_exists(&conn, *target_id)? {
+            return Err(AppError::NotFound(
+                Message::new("reply.target_bot_not_found").with("id", target_id),
+            ));
         }
     }
 
     save_reply_settings_query(&conn, reply_bot_id, &target_bot_ids, &reply_content, &now)
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
-pub fn get_reply_settings(state: State<AppState>) -> Result<Vec<ReplySettings>, String> {
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    fetch_reply_settings_query(&conn).map_err(|e| e.to_string())
+pub fn get_reply_settings(state: State<AppState>) -> Result<Vec<ReplySettings>, AppError> {
+    let conn = state.db.get()?;
+    fetch_reply_settings_query(&conn).map_err(AppError::from)
 }
 
 #[tauri::command]
-pub fn delete_reply_settings(id: i64, state: State<AppState>) -> Result<(), String> {
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    delete_reply_setting(&conn, id).map_err(|e| e.to_string())
+pub fn delete_reply_settings(id: i64, state: State<AppState>) -> Result<(), AppError> {
+    let conn = state.db.get()?;
+    delete_reply_setting(&conn, id).map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -61,18 +65,9 @@ pub fn update_last_checked_tweet(
     tweet_id: String,
     reply_bot_id: i64,
     state: State<AppState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let now = Utc::now().to_rfc3339();
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
+    let conn = state.db.get()?;
     update_last_checked_tweet_query(&conn, reply_bot_id, target_bot_id, &tweet_id, &now)
-        .map_err(|e| e.to_string())
-}
-
-#[tauri::command]
-pub fn cleanup_orphaned_reply_settings_cmd(state: State<AppState>) -> Result<i32, String> {
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    let initial = count_active_reply_settings(&conn).map_err(|e| e.to_string())?;
-    cleanup_orphaned_reply_settings(&conn).map_err(|e| e.to_string())?;
-    let final_count = count_active_reply_settings(&conn).map_err(|e| e.to_string())?;
-    Ok(initial - final_count)
+        .map_err(AppError::from)
 }
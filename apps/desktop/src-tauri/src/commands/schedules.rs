@@ -2,11 +2,16 @@ use chrono::Utc;
 use tauri::State;
 
 use crate::db::queries::schedules::{
-    add_scheduled_tweet as add_scheduled_tweet_query, fetch_scheduled_tweets,
+    add_scheduled_post as add_scheduled_post_query,
+    add_scheduled_tweet as add_scheduled_tweet_query, fetch_scheduled_posts,
+    fetch_scheduled_tweets, reorder_scheduled_posts as reorder_scheduled_posts_query,
     save_scheduled_tweet_list as save_scheduled_tweet_list_query, save_single_scheduled_tweet,
-    update_post_index as update_post_index_query,
+    set_scheduled_post_enabled as set_scheduled_post_enabled_query,
+    update_post_index as update_post_index_query, update_scheduled_post_content,
 };
-use crate::models::ScheduledTweet;
+use crate::error::AppError;
+use crate::i18n::Message;
+use crate::models::{ScheduledPost, ScheduledTweet};
 use crate::state::AppState;
 
 #[tauri::command]
@@ -15,11 +20,11 @@ pub fn save_scheduled_tweet_list(
     scheduled_times: String,
     content_list: Vec<String>,
     state: State<AppState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let now = Utc::now().to_rfc3339();
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
+    let conn = state.db.get()?;
     save_scheduled_tweet_list_query(&conn, account_id, &scheduled_times, &content_list, &now)
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -28,32 +33,97 @@ pub fn save_scheduled_tweet(
     scheduled_times: String,
     content: String,
     state: State<AppState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let now = Utc::now().to_rfc3339();
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
+    let conn = state.db.get()?;
     save_single_scheduled_tweet(&conn, account_id, &scheduled_times, &content, &now)
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
-pub fn update_post_index(account_id: i64, state: State<AppState>) -> Result<(), String> {
+pub fn update_post_index(account_id: i64, state: State<AppState>) -> Result<(), AppError> {
     let now = Utc::now().to_rfc3339();
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    update_post_index_query(&conn, account_id, &now).map_err(|e| e.to_string())
+    let conn = state.db.get()?;
+    update_post_index_query(&conn, account_id, &now).map_err(AppError::from)
 }
 
 #[tauri::command]
-pub fn add_scheduled_tweet(tweet: ScheduledTweet, state: State<AppState>) -> Result<i64, String> {
+pub fn add_scheduled_tweet(tweet: ScheduledTweet, state: State<AppState>) -> Result<i64, AppError> {
     let now = Utc::now().to_rfc3339();
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    add_scheduled_tweet_query(&conn, &tweet, &now).map_err(|e| e.to_string())
+    let conn = state.db.get()?;
+    add_scheduled_tweet_query(&conn, &tweet, &now).map_err(AppError::from)
 }
 
 #[tauri::command]
 pub fn get_scheduled_tweets(
     account_id: Option<i64>,
     state: State<AppState>,
-) -> Result<Vec<ScheduledTweet>, String> {
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    fetch_scheduled_tweets(&conn, account_id).map_err(|e| e.to_string())
+) -> Result<Vec<ScheduledTweet>, AppError> {
+    let conn = state.db.get()?;
+    fetch_scheduled_tweets(&conn, account_id).map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn get_scheduled_posts(
+    schedule_id: i64,
+    state: State<AppState>,
+) -> Result<Vec<ScheduledPost>, AppError> {
+    let conn = state.db.get()?;
+    fetch_scheduled_posts(&conn, schedule_id).map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn add_scheduled_post(
+    schedule_id: i64,
+    content: String,
+    state: State<AppState>,
+) -> Result<i64, AppError> {
+    if content.trim().is_empty() {
+        return Err(AppError::Validation(Message::new(
+            "schedule.post_content_empty",
+        )));
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let conn = state.db.get()?;
+    add_scheduled_post_query(&conn, schedule_id, &content, &now).map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn update_scheduled_post(
+    post_id: i64,
+    content: String,
+    state: State<AppState>,
+) -> Result<(), AppError> {
+    if content.trim().is_empty() {
+        return Err(AppError::Validation(Message::new(
+            "schedule.post_content_empty",
+        )));
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let conn = state.db.get()?;
+    update_scheduled_post_content(&conn, post_id, &content, &now).map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn set_scheduled_post_enabled(
+    post_id: i64,
+    is_enabled: bool,
+    state: State<AppState>,
+) -> Result<(), AppError> {
+    let now = Utc::now().to_rfc3339();
+    let conn = state.db.get()?;
+    set_scheduled_post_enabled_query(&conn, post_id, is_enabled, &now).map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn reorder_scheduled_posts(
+    schedule_id: i64,
+    post_ids: Vec<i64>,
+    state: State<AppState>,
+) -> Result<(), AppError> {
+    let now = Utc::now().to_rfc3339();
+    let conn = state.db.get()?;
+    reorder_scheduled_posts_query(&conn, schedule_id, &post_ids, &now).map_err(AppError::from)
 }
@@ -0,0 +1,69 @@
+use chrono::Utc;
+use tauri::State;
+
+use crate::db::queries::settings::{
+    fetch_locale, fetch_retry_policy, save_locale, save_retry_policy,
+};
+use crate::error::AppError;
+use crate::i18n::{set_current_locale, Locale, Message};
+use crate::models::RetryPolicy;
+use crate::state::AppState;
+
+const MAX_RETRY_ATTEMPTS: u32 = 10;
+const MAX_RETRY_DEADLINE_SECS: u64 = 600;
+
+#[tauri::command]
+pub fn get_locale(state: State<AppState>) -> Result<Locale, AppError> {
+    let conn = state.db.get()?;
+    Ok(fetch_locale(&conn)?)
+}
+
+/// Stores the locale used for backend messages and applies it immediately.
+#[tauri::command]
+pub fn set_locale(locale: String, state: State<AppState>) -> Result<Locale, AppError> {
+    let locale = Locale::from_code(&locale).ok_or_else(|| {
+        AppError::Validation(Message::new("settings.unsupported_locale").with("locale", &locale))
+    })?;
+
+    let now = Utc::now().to_rfc3339();
+    let conn = state.db.get()?;
+    save_locale(&conn, locale, &now)?;
+    set_current_locale(locale);
+
+    Ok(locale)
+}
+
+#[tauri::command]
+pub fn get_retry_policy(state: State<AppState>) -> Result<RetryPolicy, AppError> {
+    let conn = state.db.get()?;
+    Ok(fetch_retry_policy(&conn)?)
+}
+
+#[tauri::command]
+pub fn set_retry_policy(
+    policy: RetryPolicy,
+    state: State<AppState>,
+) -> Result<RetryPolicy, AppError> {
+    if policy.max_attempts == 0 || policy.max_attempts > MAX_RETRY_ATTEMPTS {
+        return Err(AppError::Validation(
+            Message::new("settings.retry_attempts_out_of_range").with("max", MAX_RETRY_ATTEMPTS),
+        ));
+    }
+    if policy.initial_backoff_ms > policy.max_backoff_ms {
+        return Err(AppError::Validation(Message::new(
+            "settings.retry_backoff_inverted",
+        )));
+    }
+    if policy.deadline_secs == 0 || policy.deadline_secs > MAX_RETRY_DEADLINE_SECS {
+        return Err(AppError::Validation(
+            Message::new("settings.retry_deadline_out_of_range")
+                .with("max", MAX_RETRY_DEADLINE_SECS),
+        ));
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let conn = state.db.get()?;
+    save_retry_policy(&conn, &policy, &now)?;
+
+    Ok(policy)
+}
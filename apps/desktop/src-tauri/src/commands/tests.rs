@@ -1,67 +1,123 @@
 ﻿use chrono::Utc;
 use tauri::State;
 
+use crate::db::pool::ConnectionPool;
 use crate::db::queries::bots::find_bot_account_by_id;
 use crate::db::queries::logs::insert_execution_log;
+use crate::error::AppError;
+use crate::i18n::Message;
 use crate::models::{ExecutionLog, TestTweetRequest, TwitterApiResponse};
-use crate::services::post_to_twitter;
+use crate::services::quota::check_post_quota;
+use crate::services::twitter::{retry, TwitterApi};
 use crate::state::AppState;
+use crate::utils::vault::CredentialVault;
 
 #[tauri::command]
 pub async fn test_tweet(
     request: TestTweetRequest,
     state: State<'_, AppState>,
-) -> Result<TwitterApiResponse, String> {
+) -> Result<TwitterApiResponse, AppError> {
+    send_test_tweet(&state.db, &state.vault, state.twitter.as_ref(), &request).await
+}
+
+/// Posts `request.content` through `api` and records the outcome in
+/// `execution_logs`, retrying transient failures per the stored
+/// `RetryPolicy`. An API failure is reported in the response, not as `Err`,
+/// except for an exhausted rate limit, which returns `AppError::RateLimited`
+/// so the caller knows when to retry. Posts beyond the tier's monthly budget
+/// are refused with `AppError::QuotaExceeded` before any request is sent.
+pub async fn send_test_tweet(
+    db: &ConnectionPool,
+    vault: &CredentialVault,
+    api: &dyn TwitterApi,
+    request: &TestTweetRequest,
+) -> Result<TwitterApiResponse, AppError> {
     let account = {
-        let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-        find_bot_account_by_id(&conn, request.account_id).map_err(|e| e.to_string())?
+        let conn = db.get()?;
+        let account = find_bot_account_by_id(&conn, vault, request.account_id)?;
+
+        if let Some(warning) = check_post_quota(&conn, request.account_id, Utc::now())? {
+            let mut log = ExecutionLog {
+                id: None,
+                account_id: request.account_id,
+                log_type: "warning".to_string(),
+                message: String::new(),
+                message_id: None,
+                message_params: Default::default(),
+                error_code: None,
+                tweet_id: None,
+                tweet_content: None,
+                status: "warning".to_string(),
+                created_at: Utc::now().to_rfc3339(),
+            };
+            log.set_message(warning);
+            insert_execution_log(&conn, &log, &log.created_at)?;
+        }
+
+        account
     };
 
-    match post_to_twitter(&account, &request.content).await {
+    match retry::post_tweet(db, api, &account, &request.content).await {
         Ok(tweet_id) => {
-            let log = ExecutionLog {
+            let mut log = ExecutionLog {
                 id: None,
                 account_id: request.account_id,
                 log_type: "tweet".to_string(),
-                message: "ツイート投稿が成功しました".to_string(),
+                message: String::new(),
+                message_id: None,
+                message_params: Default::default(),
+                error_code: None,
                 tweet_id: Some(tweet_id.clone()),
                 tweet_content: Some(request.content.clone()),
                 status: "success".to_string(),
                 created_at: Utc::now().to_rfc3339(),
             };
+            log.set_message(Message::new("tweet.posted"));
 
             {
-                let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-                insert_execution_log(&conn, &log, &log.created_at).map_err(|e| e.to_string())?;
+                let conn = db.get()?;
+                insert_execution_log(&conn, &log, &log.created_at)?;
             }
 
             Ok(TwitterApiResponse {
                 success: true,
                 tweet_id: Some(tweet_id),
-                message: "ツイート投稿が成功しました".to_string(),
+                message: Message::new("tweet.posted").to_string(),
             })
         }
         Err(error) => {
-            let log = ExecutionLog {
+            let mut log = ExecutionLog {
                 id: None,
                 account_id: request.account_id,
                 log_type: "error".to_string(),
-                message: format!("ツイート投稿に失敗しました: {}", error),
+                message: String::new(),
+                message_id: None,
+                message_params: Default::default(),
+                error_code: Some(error.log_code().to_string()),
                 tweet_id: None,
                 tweet_content: Some(request.content.clone()),
                 status: "error".to_string(),
                 created_at: Utc::now().to_rfc3339(),
             };
+            log.set_message(
+                Message::new("tweet.post_failed").with_message("error", error.message()),
+            );
 
             {
-                let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-                insert_execution_log(&conn, &log, &log.created_at).map_err(|e| e.to_string())?;
+                let conn = db.get()?;
+                insert_execution_log(&conn, &log, &log.created_at)?;
+            }
+
+            if let AppError::RateLimited { .. } = error {
+                return Err(error);
             }
 
             Ok(TwitterApiResponse {
                 success: false,
                 tweet_id: None,
-                message: format!("投稿に失敗しました: {}", error),
+                message: Message::new("tweet.test_failed")
+                    .with_message("error", error.message())
+                    .to_string(),
             })
         }
     }
@@ -4,18 +4,22 @@ use tauri::State;
 use crate::db::queries::users::{
     fetch_user_settings, update_user_settings as update_user_settings_query,
 };
+use crate::error::AppError;
 use crate::models::UserSettings;
 use crate::state::AppState;
 
 #[tauri::command]
-pub fn get_user_settings(state: State<AppState>) -> Result<UserSettings, String> {
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    fetch_user_settings(&conn).map_err(|e| e.to_string())
+pub fn get_user_settings(state: State<AppState>) -> Result<UserSettings, AppError> {
+    let conn = state.db.get()?;
+    fetch_user_settings(&conn).map_err(AppError::from)
 }
 
 #[tauri::command]
-pub fn update_user_settings(settings: UserSettings, state: State<AppState>) -> Result<(), String> {
+pub fn update_user_settings(
+    settings: UserSettings,
+    state: State<AppState>,
+) -> Result<(), AppError> {
     let now = Utc::now().to_rfc3339();
-    let conn = state.db.lock().map_err(|_| "Failed to lock database")?;
-    update_user_settings_query(&conn, &settings, &now).map_err(|e| e.to_string())
+    let conn = state.db.get()?;
+    update_user_settings_query(&conn, &settings, &now).map_err(AppError::from)
 }
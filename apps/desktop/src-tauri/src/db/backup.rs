@@ -0,0 +1,316 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use rusqlite::{Connection, DatabaseName, OpenFlags};
+
+use super::connection::{data_directory, database_path};
+use super::migrations::{
+    latest_schema_version, run_database_migrations, schema_version, MigrationContext,
+};
+use super::queries::bots::find_encrypted_credential;
+use crate::error::AppError;
+use crate::i18n::Message;
+use crate::models::{BackupInfo, BackupReason};
+use crate::utils::vault::CredentialVault;
+
+const BACKUP_DIR: &str = "backups";
+const BACKUP_PREFIX: &str = "twilia-";
+const BACKUP_EXTENSION: &str = ".sqlite";
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S%3f";
+/// Second-resolution names written before milliseconds were added.
+const LEGACY_TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+const DAILY_BACKUP_HOURS: i64 = 24;
+const DAILY_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Tables a backup must contain before it may replace the live database.
+const REQUIRED_TABLES: &[&str] = &[
+    "bot_accounts",
+    "bot_configs",
+    "reply_settings",
+    "execution_logs",
+    "scheduled_tweets",
+];
+
+fn retention(reason: BackupReason) -> usize {
+    match reason {
+        BackupReason::Manual => 10,
+        BackupReason::Daily => 7,
+        BackupReason::PreMigration => 5,
+        BackupReason::PreRestore => 5,
+    }
+}
+
+pub fn backup_directory() -> Result<PathBuf> {
+    let dir = data_directory()?.join(BACKUP_DIR);
+    fs::create_dir_all(&dir).context("Failed to create backup directory")?;
+    Ok(dir)
+}
+
+/// Copies the live database with SQLite's online backup API and prunes older
+/// backups taken for the same reason.
+pub fn create_backup(conn: &Connection, reason: BackupReason) -> Result<BackupInfo> {
+    let dir = backup_directory()?;
+    // Two backups in the same millisecond (a pre-restore copy right after a
+    // manual one, say) would otherwise overwrite each other.
+    let mut taken_at = Utc::now();
+    let (file_name, path) = loop {
+        let file_name = format!(
+            "{}{}-{}{}",
+            BACKUP_PREFIX,
+            taken_at.format(TIMESTAMP_FORMAT),
+            reason.slug(),
+            BACKUP_EXTENSION
+        );
+        let path = dir.join(&file_name);
+        if !path.exists() {
+            break (file_name, path);
+        }
+        taken_at += chrono::Duration::milliseconds(1);
+    };
+
+    conn.backup(DatabaseName::Main, &path, None)
+        .with_context(|| format!("Failed to write backup {}", path.display()))?;
+    println!("Created {} backup: {}", reason.slug(), path.display());
+
+    prune_backups(&dir, reason)?;
+
+    backup_info(&path).ok_or_else(|| anyhow!("Failed to read backup {}", file_name))
+}
+
+/// Returns every backup in the backup directory, newest first.
+pub fn list_backups() -> Result<Vec<BackupInfo>> {
+    list_backups_in(&backup_directory()?)
+}
+
+/// Takes a daily backup if the newest one is older than 24 hours.
+pub fn ensure_daily_backup(conn: &Connection) -> Result<Option<BackupInfo>> {
+    let latest = list_backups()?
+        .into_iter()
+        .find(|backup| backup.reason == BackupReason::Daily);
+
+    let due = match latest {
+        Some(backup) => DateTime::parse_from_rfc3339(&backup.created_at)
+            .map(|created_at| {
+                Utc::now().signed_duration_since(created_at)
+                    >= chrono::Duration::hours(DAILY_BACKUP_HOURS)
+            })
+            .unwrap_or(true),
+        None => true,
+    };
+
+    if due {
+        create_backup(conn, BackupReason::Daily).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Checks once an hour whether a daily backup is due, using a separate
+/// connection so the UI connection is never held for the copy.
+pub fn spawn_daily_backups() {
+    thread::spawn(|| loop {
+        thread::sleep(DAILY_CHECK_INTERVAL);
+
+        let result = database_path()
+            .and_then(|path| Connection::open(path).map_err(|e| anyhow!(e)))
+            .and_then(|conn| ensure_daily_backup(&conn));
+
+        if let Err(e) = result {
+            eprintln!("Daily backup failed: {}", e);
+        }
+    });
+}
+
+/// Rejects a backup that is not an intact SQLite file, is missing core
+/// tables, or was written by a newer schema than this build understands.
+pub fn validate_backup(path: &Path) -> Result<()> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .with_context(|| format!("Failed to open backup {}", path.display()))?;
+
+    let integrity: String = conn
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .context("Backup is not a readable SQLite database")?;
+    if integrity != "ok" {
+        return Err(anyhow!("Backup failed integrity check: {}", integrity));
+    }
+
+    for table in REQUIRED_TABLES {
+        let exists: i32 = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name=?",
+            [table],
+            |row| row.get(0),
+        )?;
+        if exists == 0 {
+            return Err(anyhow!("Backup is missing the {} table", table));
+        }
+    }
+
+    let version = schema_version(&conn)?;
+    if version > latest_schema_version() {
+        return Err(anyhow!(
+            "Backup uses schema version {}, but this version of the app only supports up to {}",
+            version,
+            latest_schema_version()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Replaces the live database with `file_name` from the backup directory.
+///
+/// The current database is saved as a pre-restore backup first. The restored
+/// copy must be readable with the current vault key and is migrated forward
+/// if it came from an older schema; if either fails, the pre-restore backup
+/// is put back.
+/// Returns the pre-restore backup.
+pub fn restore_backup(
+    conn: &mut Connection,
+    vault: &CredentialVault,
+    file_name: &str,
+) -> Result<BackupInfo> {
+    let dir = backup_directory()?;
+    if Path::new(file_name)
+        .file_name()
+        .and_then(|name| name.to_str())
+        != Some(file_name)
+    {
+        return Err(anyhow!(AppError::Validation(
+            Message::new("backup.invalid_name").with("file_name", file_name)
+        )));
+    }
+
+    let path = dir.join(file_name);
+    if !path.is_file() {
+        return Err(anyhow!(AppError::NotFound(
+            Message::new("backup.not_found").with("file_name", file_name)
+        )));
+    }
+
+    validate_backup(&path)?;
+
+    let safety_copy = create_backup(conn, BackupReason::PreRestore)?;
+
+    conn.restore(
+        DatabaseName::Main,
+        &path,
+        None::<fn(rusqlite::backup::Progress)>,
+    )
+    .with_context(|| format!("Failed to restore backup {}", file_name))?;
+    println!("Restored database from backup: {}", path.display());
+
+    let checked = find_encrypted_credential(conn)
+        .and_then(|sample| vault.confirm_key(&data_directory()?, sample.as_deref()))
+        .and_then(|_| run_database_migrations(conn, &MigrationContext { vault }));
+    if let Err(e) = checked {
+        conn.restore(
+            DatabaseName::Main,
+            &safety_copy.path,
+            None::<fn(rusqlite::backup::Progress)>,
+        )
+        .with_context(|| {
+            format!(
+                "Backup {} could not be used ({}), and putting back {} also failed",
+                file_name, e, safety_copy.file_name
+            )
+        })?;
+        println!(
+            "Put back {} after backup {} could not be used",
+            safety_copy.file_name, file_name
+        );
+        return Err(e.context(format!("Failed to restore backup {}", file_name)));
+    }
+
+    Ok(safety_copy)
+}
+
+fn list_backups_in(dir: &Path) -> Result<Vec<BackupInfo>> {
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(dir).context("Failed to read backup directory")? {
+        if let Some(info) = backup_info(&entry?.path()) {
+            backups.push(info);
+        }
+    }
+
+    backups.sort_by(|a, b| b.file_name.cmp(&a.file_name));
+    Ok(backups)
+}
+
+fn prune_backups(dir: &Path, reason: BackupReason) -> Result<()> {
+    let expired = list_backups_in(dir)?
+        .into_iter()
+        .filter(|backup| backup.reason == reason)
+        .skip(retention(reason));
+
+    for backup in expired {
+        fs::remove_file(&backup.path)
+            .with_context(|| format!("Failed to remove old backup {}", backup.file_name))?;
+        println!("Removed old backup: {}", backup.file_name);
+    }
+
+    Ok(())
+}
+
+/// Parses `twilia-<YYYYmmdd-HHMMSSmmm>-<reason>.sqlite` (or the older name
+/// without milliseconds); anything else in the directory is ignored.
+fn backup_info(path: &Path) -> Option<BackupInfo> {
+    let file_name = path.file_name()?.to_str()?;
+    let stem = file_name
+        .strip_prefix(BACKUP_PREFIX)?
+        .strip_suffix(BACKUP_EXTENSION)?;
+
+    // The timestamp is fixed-width: 8 date digits, '-', 6 time digits and,
+    // since milliseconds were added, 3 more.
+    let (width, format) = if stem.get(15..16) == Some("-") {
+        (15, LEGACY_TIMESTAMP_FORMAT)
+    } else {
+        (18, TIMESTAMP_FORMAT)
+    };
+    let timestamp = stem.get(..width)?;
+    let reason = BackupReason::from_slug(stem.get(width..)?.strip_prefix('-')?)?;
+    let created_at = NaiveDateTime::parse_from_str(timestamp, format).ok()?;
+
+    let metadata = fs::metadata(path).ok()?;
+    if !metadata.is_file() {
+        return None;
+    }
+
+    Some(BackupInfo {
+        file_name: file_name.to_string(),
+        path: path.to_string_lossy().to_string(),
+        reason,
+        created_at: Utc.from_utc_datetime(&created_at).to_rfc3339(),
+        size_bytes: metadata.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn reads_current_and_legacy_backup_names() {
+        let dir = TempDir::new().unwrap();
+        for name in [
+            "twilia-20240101-120000123-manual.sqlite",
+            "twilia-20240101-120000-daily.sqlite",
+            "twilia-notes.sqlite",
+        ] {
+            fs::write(dir.path().join(name), b"").unwrap();
+        }
+
+        let backups = list_backups_in(dir.path()).unwrap();
+        assert_eq!(backups.len(), 2);
+        assert_eq!(backups[0].reason, BackupReason::Manual);
+        assert_eq!(backups[0].created_at, "2024-01-01T12:00:00.123+00:00");
+        assert_eq!(backups[1].reason, BackupReason::Daily);
+        assert_eq!(backups[1].created_at, "2024-01-01T12:00:00+00:00");
+    }
+}
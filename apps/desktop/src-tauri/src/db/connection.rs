@@ -1,146 +1,73 @@
 ﻿use std::fs;
+use std::path::PathBuf;
 
 use anyhow::{Context, Result};
-use chrono::Utc;
 use directories::ProjectDirs;
-use rusqlite::{params, Connection};
-
-use super::{cleanup::cleanup_orphaned_reply_settings, migrations::run_database_migrations};
-
-pub fn init_database() -> Result<Connection> {
+use rusqlite::{Connection, OpenFlags};
+
+use super::backup::{create_backup, ensure_daily_backup};
+use super::migrations::{
+    pending_migrations, run_database_migrations, MigrationContext, PendingMigration,
+};
+use super::pool::{configure_connection, ConnectionPool};
+use super::queries::bots::find_encrypted_credential;
+use super::queries::settings::fetch_locale;
+use crate::i18n::set_current_locale;
+use crate::models::BackupReason;
+use crate::utils::vault::CredentialVault;
+
+const DATABASE_FILE: &str = "twilia.sqlite";
+
+pub fn data_directory() -> Result<PathBuf> {
     let proj_dirs = ProjectDirs::from("com", "twilia", "bot-manager")
         .context("Failed to determine project directories")?;
 
-    let data_dir = proj_dirs.data_dir();
-    fs::create_dir_all(data_dir).context("Failed to create data directory")?;
+    let data_dir = proj_dirs.data_dir().to_path_buf();
+    fs::create_dir_all(&data_dir).context("Failed to create data directory")?;
+
+    Ok(data_dir)
+}
+
+pub fn database_path() -> Result<PathBuf> {
+    Ok(data_directory()?.join(DATABASE_FILE))
+}
 
-    let db_path = data_dir.join("twilia.sqlite");
+/// Opens the database, confirms the vault key can read its credentials,
+/// migrates it, and hands the migrated connection to a new
+/// [`ConnectionPool`].
+pub fn init_database(vault: &CredentialVault) -> Result<ConnectionPool> {
+    let db_path = database_path()?;
+    let existed = db_path.exists();
     let conn = Connection::open(&db_path)?;
+    configure_connection(&conn)?;
 
-    let table_exists: i32 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='bot_accounts'",
-            [],
-            |row| row.get(0),
-        )
-        .unwrap_or(0);
-
-    if table_exists == 0 {
-        create_initial_schema(&conn)?;
-    } else {
-        run_database_migrations(&conn)?;
+    let sample = find_encrypted_credential(&conn)?;
+    vault.confirm_key(&data_directory()?, sample.as_deref())?;
+
+    if existed && !pending_migrations(&conn)?.is_empty() {
+        create_backup(&conn, BackupReason::PreMigration)
+            .context("Refusing to migrate without a backup")?;
+    }
+
+    run_database_migrations(&conn, &MigrationContext { vault })?;
+
+    if let Err(e) = ensure_daily_backup(&conn) {
+        eprintln!("Daily backup failed: {}", e);
     }
 
-    cleanup_orphaned_reply_settings(&conn)?;
+    set_current_locale(fetch_locale(&conn)?);
 
-    Ok(conn)
+    ConnectionPool::new(&db_path, conn)
 }
 
-fn create_initial_schema(conn: &Connection) -> Result<()> {
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS bot_accounts (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            account_name TEXT NOT NULL UNIQUE,
-            api_key TEXT NOT NULL,
-            api_key_secret TEXT NOT NULL,
-            access_token TEXT NOT NULL,
-            access_token_secret TEXT NOT NULL,
-            api_type TEXT NOT NULL DEFAULT 'Free',
-            status TEXT DEFAULT 'inactive',
-            created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL
-        )",
-        [],
-    )?;
-
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS bot_configs (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            account_id INTEGER NOT NULL,
-            is_enabled BOOLEAN DEFAULT 0,
-            auto_tweet_enabled BOOLEAN DEFAULT 0,
-            tweet_interval_minutes INTEGER DEFAULT 60,
-            tweet_templates TEXT,
-            hashtags TEXT,
-            created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL,
-            FOREIGN KEY (account_id) REFERENCES bot_accounts(id) ON DELETE CASCADE
-        )",
-        [],
-    )?;
-
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS reply_settings (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            target_bot_ids TEXT NOT NULL,
-            reply_bot_id INTEGER NOT NULL,
-            reply_content TEXT NOT NULL,
-            is_active BOOLEAN DEFAULT 1,
-            last_checked_tweet_ids TEXT,
-            created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL,
-            FOREIGN KEY (reply_bot_id) REFERENCES bot_accounts(id) ON DELETE CASCADE
-        )",
-        [],
-    )?;
-
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS execution_logs (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            account_id INTEGER NOT NULL,
-            log_type TEXT NOT NULL,
-            message TEXT NOT NULL,
-            tweet_id TEXT,
-            tweet_content TEXT,
-            status TEXT NOT NULL,
-            created_at TEXT NOT NULL,
-            FOREIGN KEY (account_id) REFERENCES bot_accounts(id) ON DELETE CASCADE
-        )",
-        [],
-    )?;
-
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS scheduled_tweets (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            account_id INTEGER NOT NULL,
-            content TEXT NOT NULL,
-            content_list TEXT,
-            current_index INTEGER DEFAULT 0,
-            scheduled_times TEXT NOT NULL,
-            is_active BOOLEAN DEFAULT 1,
-            created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL,
-            FOREIGN KEY (account_id) REFERENCES bot_accounts(id) ON DELETE CASCADE
-        )",
-        [],
-    )?;
-
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS user_settings (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            user_id TEXT NOT NULL UNIQUE DEFAULT 'default',
-            plan_type TEXT DEFAULT 'starter',
-            max_accounts INTEGER DEFAULT 999,
-            created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL
-        )",
-        [],
-    )?;
-
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS app_settings (
-            key TEXT PRIMARY KEY,
-            value TEXT NOT NULL,
-            updated_at TEXT NOT NULL
-        )",
-        [],
-    )?;
-
-    let now = Utc::now().to_rfc3339();
-    conn.execute(
-        "INSERT OR IGNORE INTO user_settings (user_id, created_at, updated_at) VALUES ('default', ?, ?)",
-        params![now, now],
-    )?;
-
-    Ok(())
+/// Reports pending migrations for `--dry-run` without creating or modifying
+/// the database.
+pub fn dry_run_migrations() -> Result<Vec<PendingMigration>> {
+    let db_path = database_path()?;
+    if !db_path.exists() {
+        return pending_migrations(&Connection::open_in_memory()?);
+    }
+
+    let conn = Connection::open_with_flags(&db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    pending_migrations(&conn)
 }
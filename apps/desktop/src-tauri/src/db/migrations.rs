@@ -1,65 +1,300 @@
-use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{anyhow, Result};
 use chrono::Utc;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
 use serde_json;
 
-pub fn run_database_migrations(conn: &Connection) -> Result<()> {
-    let reply_table_exists: i32 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='reply_settings'",
-            [],
-            |row| row.get(0),
-        )
-        .unwrap_or(0);
+use crate::utils::vault::CredentialVault;
 
-    if reply_table_exists == 0 {
-        conn.execute(
-            "CREATE TABLE reply_settings (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                target_bot_ids TEXT NOT NULL,
-                reply_bot_id INTEGER NOT NULL,
-                reply_content TEXT NOT NULL,
-                is_active BOOLEAN DEFAULT 1,
-                last_checked_tweet_ids TEXT,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL,
-                FOREIGN KEY (reply_bot_id) REFERENCES bot_accounts(id) ON DELETE CASCADE
-            )",
-            [],
+/// Values a migration may need beyond the connection itself.
+pub struct MigrationContext<'a> {
+    pub vault: &'a CredentialVault,
+}
+
+struct Migration {
+    version: i64,
+    name: &'static str,
+    apply: fn(&Connection, &MigrationContext<'_>) -> Result<()>,
+}
+
+/// Ordered list of schema migrations. Append new entries with the next
+/// version number; never renumber or edit an entry that has shipped.
+///
+/// Versions 1-4 predate `schema_migrations`, so they still inspect the schema
+/// before changing it: a legacy database may already contain any of them.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "create_initial_schema",
+        apply: |conn, _| create_initial_schema(conn),
+    },
+    Migration {
+        version: 2,
+        name: "migrate_reply_settings_table",
+        apply: |conn, _| migrate_reply_settings_table(conn),
+    },
+    Migration {
+        version: 3,
+        name: "ensure_scheduled_tweets_columns",
+        apply: |conn, _| ensure_scheduled_tweets_columns(conn),
+    },
+    Migration {
+        version: 4,
+        name: "encrypt_bot_credentials",
+        apply: |conn, ctx| encrypt_bot_credentials(conn, ctx.vault),
+    },
+    Migration {
+        version: 5,
+        name: "normalize_reply_targets",
+        apply: |conn, _| normalize_reply_targets(conn),
+    },
+    Migration {
+        version: 6,
+        name: "create_scheduled_posts",
+        apply: |conn, _| create_scheduled_posts(conn),
+    },
+    Migration {
+        version: 7,
+        name: "add_execution_log_message_ids",
+        apply: |conn, _| add_execution_log_message_ids(conn),
+    },
+    Migration {
+        version: 8,
+        name: "create_rate_limits",
+        apply: |conn, _| create_rate_limits(conn),
+    },
+    Migration {
+        version: 9,
+        name: "add_execution_log_error_codes",
+        apply: |conn, _| add_execution_log_error_codes(conn),
+    },
+    Migration {
+        version: 10,
+        name: "add_bot_account_twitter_identity",
+        apply: |conn, _| add_bot_account_twitter_identity(conn),
+    },
+    Migration {
+        version: 11,
+        name: "add_bot_account_profile",
+        apply: |conn, _| add_bot_account_profile(conn),
+    },
+];
+
+#[derive(Debug, Serialize)]
+pub struct PendingMigration {
+    pub version: i64,
+    pub name: &'static str,
+}
+
+/// Applies every pending migration, each in its own transaction.
+///
+/// Foreign keys are switched off for the duration so that rebuilding a parent
+/// table does not cascade into its children; `PRAGMA foreign_keys` cannot be
+/// changed inside a transaction, so this happens around the loop rather than
+/// per migration. Rebuilt tables are checked with `foreign_key_check` instead.
+pub fn run_database_migrations(conn: &Connection, ctx: &MigrationContext<'_>) -> Result<()> {
+    conn.pragma_update(None, "foreign_keys", false)?;
+    let result = apply_pending_migrations(conn, ctx);
+    conn.pragma_update(None, "foreign_keys", true)?;
+    result
+}
+
+fn apply_pending_migrations(conn: &Connection, ctx: &MigrationContext<'_>) -> Result<()> {
+    ensure_migrations_table(conn)?;
+    let current = current_schema_version(conn)?;
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        let tx = conn.unchecked_transaction()?;
+
+        (migration.apply)(&tx, ctx).map_err(|e| {
+            anyhow!(
+                "Migration {} ({}) failed: {}",
+                migration.version,
+                migration.name,
+                e
+            )
+        })?;
+
+        tx.execute(
+            "INSERT INTO schema_migrations (version, name, applied_at) VALUES (?, ?, ?)",
+            params![migration.version, migration.name, Utc::now().to_rfc3339()],
         )?;
-        println!("Created reply_settings table with new schema (multiple targets, single replier)");
-    } else {
-        migrate_reply_settings_table(conn)?;
+        tx.commit()?;
+
+        println!(
+            "Applied migration {} ({})",
+            migration.version, migration.name
+        );
     }
 
-    let table_exists: i32 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='scheduled_tweets'",
-            [],
-            |row| row.get(0),
-        )
-        .unwrap_or(0);
+    Ok(())
+}
+
+/// Lists migrations that `run_database_migrations` would apply, without
+/// touching the database.
+pub fn pending_migrations(conn: &Connection) -> Result<Vec<PendingMigration>> {
+    let current = schema_version(conn)?;
+
+    Ok(MIGRATIONS
+        .iter()
+        .filter(|m| m.version > current)
+        .map(|m| PendingMigration {
+            version: m.version,
+            name: m.name,
+        })
+        .collect())
+}
+
+/// Highest migration version this build knows how to apply.
+pub fn latest_schema_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// Version recorded in `schema_migrations`, or 0 for a database that
+/// predates the table.
+pub fn schema_version(conn: &Connection) -> Result<i64> {
+    let table_exists: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='schema_migrations'",
+        [],
+        |row| row.get(0),
+    )?;
 
     if table_exists == 0 {
-        conn.execute(
-            "CREATE TABLE scheduled_tweets (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                account_id INTEGER NOT NULL,
-                content TEXT NOT NULL,
-                content_list TEXT,
-                current_index INTEGER DEFAULT 0,
-                scheduled_times TEXT NOT NULL,
-                is_active BOOLEAN DEFAULT 1,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL,
-                FOREIGN KEY (account_id) REFERENCES bot_accounts(id) ON DELETE CASCADE
-            )",
-            [],
-        )?;
-        println!("Created scheduled_tweets table with content_list support");
+        Ok(0)
     } else {
-        ensure_scheduled_tweets_columns(conn)?;
+        current_schema_version(conn)
     }
+}
+
+fn ensure_migrations_table(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn current_schema_version(conn: &Connection) -> Result<i64> {
+    let version: Option<i64> = conn
+        .query_row("SELECT MAX(version) FROM schema_migrations", [], |row| {
+            row.get(0)
+        })
+        .optional()?
+        .flatten();
+    Ok(version.unwrap_or(0))
+}
+
+fn create_initial_schema(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS bot_accounts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            account_name TEXT NOT NULL UNIQUE,
+            api_key TEXT NOT NULL,
+            api_key_secret TEXT NOT NULL,
+            access_token TEXT NOT NULL,
+            access_token_secret TEXT NOT NULL,
+            api_type TEXT NOT NULL DEFAULT 'Free',
+            status TEXT DEFAULT 'inactive',
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS bot_configs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            account_id INTEGER NOT NULL,
+            is_enabled BOOLEAN DEFAULT 0,
+            auto_tweet_enabled BOOLEAN DEFAULT 0,
+            tweet_interval_minutes INTEGER DEFAULT 60,
+            tweet_templates TEXT,
+            hashtags TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (account_id) REFERENCES bot_accounts(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS reply_settings (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            target_bot_ids TEXT NOT NULL,
+            reply_bot_id INTEGER NOT NULL,
+            reply_content TEXT NOT NULL,
+            is_active BOOLEAN DEFAULT 1,
+            last_checked_tweet_ids TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (reply_bot_id) REFERENCES bot_accounts(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS execution_logs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            account_id INTEGER NOT NULL,
+            log_type TEXT NOT NULL,
+            message TEXT NOT NULL,
+            tweet_id TEXT,
+            tweet_content TEXT,
+            status TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (account_id) REFERENCES bot_accounts(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS scheduled_tweets (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            account_id INTEGER NOT NULL,
+            content TEXT NOT NULL,
+            content_list TEXT,
+            current_index INTEGER DEFAULT 0,
+            scheduled_times TEXT NOT NULL,
+            is_active BOOLEAN DEFAULT 1,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (account_id) REFERENCES bot_accounts(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS user_settings (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id TEXT NOT NULL UNIQUE DEFAULT 'default',
+            plan_type TEXT DEFAULT 'starter',
+            max_accounts INTEGER DEFAULT 999,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS app_settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    let now = Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT OR IGNORE INTO user_settings (user_id, created_at, updated_at) VALUES ('default', ?, ?)",
+        params![now, now],
+    )?;
 
     Ok(())
 }
@@ -132,6 +367,11 @@ fn ensure_scheduled_tweets_columns(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Fans each legacy `reply_bot_ids` list out into one row per reply bot.
+///
+/// Ids of bots that were deleted before this migration ran are dropped, since
+/// the old JSON column had no foreign key to clean them up; the v2 backup
+/// table keeps the originals.
 fn migrate_reply_settings_table(conn: &Connection) -> Result<()> {
     let target_bot_ids_exists: i32 = conn
         .query_row(
@@ -143,21 +383,395 @@ fn migrate_reply_settings_table(conn: &Connection) -> Result<()> {
 
     if target_bot_ids_exists == 0 {
         println!("Migrating reply_settings table to new schema...");
+        rewrite_table_with_backup(conn, "reply_settings", 2, |conn, backup| {
+            let mut existing_settings = Vec::new();
+            {
+                let mut stmt = conn.prepare(&format!("SELECT * FROM {}", backup))?;
+                let rows = stmt.query_map([], |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, i64>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                        row.get::<_, Option<bool>>("is_active")?.unwrap_or(true),
+                        row.get::<_, Option<String>>(5)?,
+                        row.get::<_, String>(6)?,
+                    ))
+                })?;
+
+                for row in rows {
+                    existing_settings.push(row?);
+                }
+            }
+
+            let mut bot_ids = HashSet::new();
+            {
+                let mut stmt = conn.prepare("SELECT id FROM bot_accounts")?;
+                for id in stmt.query_map([], |row| row.get::<_, i64>(0))? {
+                    bot_ids.insert(id?);
+                }
+            }
+
+            let mut unparseable = Vec::new();
+            let mut orphaned = Vec::new();
+            let mut migrated = Vec::new();
+            for (
+                id,
+                target_bot_id,
+                reply_bot_ids,
+                reply_content,
+                is_active,
+                last_checked,
+                created_at,
+            ) in existing_settings
+            {
+                match serde_json::from_str::<Vec<i64>>(&reply_bot_ids) {
+                    Ok(reply_bot_id_list) => {
+                        for reply_bot_id in reply_bot_id_list {
+                            if !bot_ids.contains(&reply_bot_id) {
+                                orphaned.push((id, reply_bot_id));
+                                continue;
+                            }
+                            migrated.push((
+                                target_bot_id,
+                                reply_bot_id,
+                                reply_content.clone(),
+                                is_active,
+                                last_checked.clone(),
+                                created_at.clone(),
+                            ));
+                        }
+                    }
+                    Err(_) => unparseable.push(id),
+                }
+            }
+
+            if !unparseable.is_empty() {
+                return Err(anyhow!(
+                    "reply_settings rows {:?} have unreadable reply_bot_ids; fix or delete them and restart",
+                    unparseable
+                ));
+            }
+            if !orphaned.is_empty() {
+                println!(
+                    "Dropping reply bots {:?} (setting id, bot id): those bots no longer exist",
+                    orphaned
+                );
+            }
+
+            // Counted by SQLite straight from the backup so the row count
+            // check does not just echo back what the loop above produced.
+            let expected: i64 = conn.query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM {} AS b, json_each(b.reply_bot_ids) AS j
+                     WHERE j.value IN (SELECT id FROM bot_accounts)",
+                    backup
+                ),
+                [],
+                |row| row.get(0),
+            )?;
+
+            conn.execute("DROP TABLE reply_settings", [])?;
+
+            conn.execute(
+                "CREATE TABLE reply_settings (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    target_bot_ids TEXT NOT NULL,
+                    reply_bot_id INTEGER NOT NULL,
+                    reply_content TEXT NOT NULL,
+                    is_active BOOLEAN DEFAULT 1,
+                    last_checked_tweet_ids TEXT,
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL,
+                    FOREIGN KEY (reply_bot_id) REFERENCES bot_accounts(id) ON DELETE CASCADE
+                )",
+                [],
+            )?;
+
+            let now = Utc::now().to_rfc3339();
+            for (target_bot_id, reply_bot_id, reply_content, is_active, last_checked, created_at) in
+                &migrated
+            {
+                let last_checked_tweet_ids = match last_checked {
+                    Some(tweet_id) => {
+                        serde_json::to_string(&vec![format!("{}:{}", target_bot_id, tweet_id)])?
+                    }
+                    None => "[]".to_string(),
+                };
+
+                conn.execute(
+                    "INSERT INTO reply_settings (target_bot_ids, reply_bot_id, reply_content, is_active, last_checked_tweet_ids, created_at, updated_at)
+                     VALUES (?, ?, ?, ?, ?, ?, ?)",
+                    params![
+                        serde_json::to_string(&vec![target_bot_id])?,
+                        reply_bot_id,
+                        reply_content,
+                        is_active,
+                        last_checked_tweet_ids,
+                        created_at,
+                        now
+                    ],
+                )?;
+            }
+
+            Ok(expected as usize)
+        })?;
+
+        println!("Successfully migrated reply_settings table to new schema");
+    }
+
+    Ok(())
+}
+
+/// Snapshots `table` into `<table>_backup_v<version>` and then runs a
+/// destructive `rewrite` against it. `rewrite` receives the backup table name
+/// and returns how many rows `table` should hold afterwards; any mismatch is
+/// an error, which rolls back the enclosing migration transaction.
+///
+/// Every migration that drops or rebuilds a table should go through here. The
+/// backup table is kept after a successful run so the pre-migration data can
+/// still be inspected.
+fn rewrite_table_with_backup<F>(
+    conn: &Connection,
+    table: &str,
+    version: i64,
+    rewrite: F,
+) -> Result<()>
+where
+    F: FnOnce(&Connection, &str) -> Result<usize>,
+{
+    let backup = format!("{}_backup_v{}", table, version);
+    conn.execute(&format!("DROP TABLE IF EXISTS {}", backup), [])?;
+    conn.execute(
+        &format!("CREATE TABLE {} AS SELECT * FROM {}", backup, table),
+        [],
+    )?;
+    println!("Backed up {} to {}", table, backup);
+
+    let expected = rewrite(conn, &backup)?;
+
+    verify_row_count(conn, table, expected)?;
+    check_foreign_keys(conn, table)
+}
+
+fn verify_row_count(conn: &Connection, table: &str, expected: usize) -> Result<()> {
+    let actual: i64 = conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+        row.get(0)
+    })?;
+    if actual != expected as i64 {
+        return Err(anyhow!(
+            "Row count check failed for {}: expected {}, found {}",
+            table,
+            expected,
+            actual
+        ));
+    }
+
+    Ok(())
+}
+
+/// Fails if any row in `table` references a missing parent. Migrations run
+/// with foreign keys disabled, so rebuilt tables have to be checked by hand.
+fn check_foreign_keys(conn: &Connection, table: &str) -> Result<()> {
+    let violations: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_foreign_key_check(?)",
+        [table],
+        |row| row.get(0),
+    )?;
+    if violations > 0 {
+        return Err(anyhow!(
+            "{} has {} row(s) referencing missing parents",
+            table,
+            violations
+        ));
+    }
+
+    Ok(())
+}
+
+fn encrypt_bot_credentials(conn: &Connection, vault: &CredentialVault) -> Result<()> {
+    let mut plaintext_rows = Vec::new();
+    {
+        let mut stmt = conn.prepare(
+            "SELECT id, api_key, api_key_secret, access_token, access_token_secret FROM bot_accounts",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                [
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                ],
+            ))
+        })?;
+
+        for (id, secrets) in rows.collect::<Result<Vec<_>, _>>()? {
+            if secrets
+                .iter()
+                .any(|secret| !CredentialVault::is_encrypted(secret))
+            {
+                plaintext_rows.push((id, secrets));
+            }
+        }
+    }
+
+    if plaintext_rows.is_empty() {
+        return Ok(());
+    }
 
+    for (id, secrets) in &plaintext_rows {
+        let mut encrypted = Vec::with_capacity(secrets.len());
+        for secret in secrets {
+            if CredentialVault::is_encrypted(secret) {
+                encrypted.push(secret.clone());
+            } else {
+                encrypted.push(vault.encrypt(secret)?);
+            }
+        }
+
+        conn.execute(
+            "UPDATE bot_accounts
+             SET api_key = ?, api_key_secret = ?, access_token = ?, access_token_secret = ?
+             WHERE id = ?",
+            params![encrypted[0], encrypted[1], encrypted[2], encrypted[3], id],
+        )?;
+    }
+
+    println!(
+        "Encrypted stored credentials for {} bot accounts",
+        plaintext_rows.len()
+    );
+
+    Ok(())
+}
+
+/// Moves `reply_settings.target_bot_ids` and `last_checked_tweet_ids` out of
+/// JSON strings into `reply_targets`, so deleting a bot cascades to the
+/// settings that watch it instead of leaving dangling ids behind.
+///
+/// Ids that no longer point at a bot are dropped, along with settings left
+/// with no reply bot or no targets; the v5 backup table keeps the originals.
+fn normalize_reply_targets(conn: &Connection) -> Result<()> {
+    rewrite_table_with_backup(conn, "reply_settings", 5, |conn, backup| {
         let mut existing_settings = Vec::new();
         {
-            let mut stmt = conn.prepare("SELECT * FROM reply_settings WHERE is_active = 1")?;
+            let mut stmt = conn.prepare(&format!(
+                "SELECT id, target_bot_ids, reply_bot_id, reply_content, is_active, last_checked_tweet_ids, created_at, updated_at
+                 FROM {}",
+                backup
+            ))?;
             let rows = stmt.query_map([], |row| {
                 Ok((
-                    row.get::<_, i64>(1)?,
-                    row.get::<_, String>(2)?,
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
                     row.get::<_, String>(3)?,
-                    row.get::<_, String>(6)?,
+                    row.get::<_, Option<bool>>(4)?.unwrap_or(true),
                     row.get::<_, Option<String>>(5)?,
+                    row.get::<_, String>(6)?,
+                    row.get::<_, String>(7)?,
                 ))
             })?;
 
-            existing_settings.extend(rows.flatten());
+            for row in rows {
+                existing_settings.push(row?);
+            }
+        }
+
+        let mut bot_ids = HashSet::new();
+        {
+            let mut stmt = conn.prepare("SELECT id FROM bot_accounts")?;
+            for id in stmt.query_map([], |row| row.get::<_, i64>(0))? {
+                bot_ids.insert(id?);
+            }
+        }
+
+        let mut unparseable = Vec::new();
+        let mut dropped = Vec::new();
+        let mut migrated = Vec::new();
+        for (
+            id,
+            target_bot_ids,
+            reply_bot_id,
+            reply_content,
+            is_active,
+            last_checked_tweet_ids,
+            created_at,
+            updated_at,
+        ) in existing_settings
+        {
+            let Ok(target_ids) = serde_json::from_str::<Vec<i64>>(&target_bot_ids) else {
+                unparseable.push(id);
+                continue;
+            };
+
+            let last_checked = parse_last_checked_tweet_ids(last_checked_tweet_ids.as_deref());
+            let mut seen = HashSet::new();
+            let targets: Vec<(i64, Option<String>)> = target_ids
+                .into_iter()
+                .filter(|target_id| bot_ids.contains(target_id) && seen.insert(*target_id))
+                .map(|target_id| (target_id, last_checked.get(&target_id).cloned()))
+                .collect();
+
+            if !bot_ids.contains(&reply_bot_id) || targets.is_empty() {
+                dropped.push(id);
+                continue;
+            }
+
+            migrated.push((
+                id,
+                reply_bot_id,
+                reply_content,
+                is_active,
+                created_at,
+                updated_at,
+                targets,
+            ));
+        }
+
+        if !unparseable.is_empty() {
+            return Err(anyhow!(
+                "reply_settings rows {:?} have unreadable target_bot_ids; fix or delete them and restart",
+                unparseable
+            ));
+        }
+        // Expected sizes are counted by SQLite from the backup, not taken
+        // from the loop above, so the checks below can actually catch a
+        // setting or target the loop lost.
+        let (expected_settings, expected_targets): (i64, i64) = conn.query_row(
+            &format!(
+                "SELECT COUNT(DISTINCT id), COUNT(*) FROM (
+                     SELECT DISTINCT b.id, j.value
+                     FROM {} AS b, json_each(b.target_bot_ids) AS j
+                     WHERE b.reply_bot_id IN (SELECT id FROM bot_accounts)
+                       AND j.value IN (SELECT id FROM bot_accounts)
+                 )",
+                backup
+            ),
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        let backup_rows: i64 =
+            conn.query_row(&format!("SELECT COUNT(*) FROM {}", backup), [], |row| {
+                row.get(0)
+            })?;
+        if backup_rows != expected_settings + dropped.len() as i64 {
+            return Err(anyhow!(
+                "reply_settings has {} row(s), but {} would be kept and {} dropped",
+                backup_rows,
+                expected_settings,
+                dropped.len()
+            ));
+        }
+        if !dropped.is_empty() {
+            println!(
+                "Dropping {} of {} reply setting(s) {:?}: their reply bot or every target bot no longer exists",
+                dropped.len(),
+                backup_rows,
+                dropped
+            );
         }
 
         conn.execute("DROP TABLE reply_settings", [])?;
@@ -165,11 +779,9 @@ fn migrate_reply_settings_table(conn: &Connection) -> Result<()> {
         conn.execute(
             "CREATE TABLE reply_settings (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
-                target_bot_ids TEXT NOT NULL,
                 reply_bot_id INTEGER NOT NULL,
                 reply_content TEXT NOT NULL,
                 is_active BOOLEAN DEFAULT 1,
-                last_checked_tweet_ids TEXT,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
                 FOREIGN KEY (reply_bot_id) REFERENCES bot_accounts(id) ON DELETE CASCADE
@@ -177,37 +789,375 @@ fn migrate_reply_settings_table(conn: &Connection) -> Result<()> {
             [],
         )?;
 
-        let now = Utc::now().to_rfc3339();
-        for (target_bot_id, reply_bot_ids, reply_content, created_at, last_checked_tweet_id) in
-            existing_settings
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS reply_targets (
+                reply_setting_id INTEGER NOT NULL,
+                target_bot_id INTEGER NOT NULL,
+                last_checked_tweet_id TEXT,
+                PRIMARY KEY (reply_setting_id, target_bot_id),
+                FOREIGN KEY (reply_setting_id) REFERENCES reply_settings(id) ON DELETE CASCADE,
+                FOREIGN KEY (target_bot_id) REFERENCES bot_accounts(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_reply_targets_target_bot_id ON reply_targets(target_bot_id)",
+            [],
+        )?;
+
+        // A setting with nothing left to watch is useless; remove it once its
+        // last target goes away (normally via the bot_accounts cascade).
+        conn.execute(
+            "CREATE TRIGGER IF NOT EXISTS reply_targets_drop_empty_settings
+             AFTER DELETE ON reply_targets
+             WHEN NOT EXISTS (SELECT 1 FROM reply_targets WHERE reply_setting_id = OLD.reply_setting_id)
+             BEGIN
+                 DELETE FROM reply_settings WHERE id = OLD.reply_setting_id;
+             END",
+            [],
+        )?;
+
+        for (id, reply_bot_id, reply_content, is_active, created_at, updated_at, targets) in
+            &migrated
         {
-            if let Ok(reply_bot_id_list) = serde_json::from_str::<Vec<i64>>(&reply_bot_ids) {
-                for reply_bot_id in reply_bot_id_list {
-                    let last_checked_tweet_ids = if let Some(tweet_id) = &last_checked_tweet_id {
-                        serde_json::to_string(&vec![format!("{}:{}", target_bot_id, tweet_id)])
-                            .unwrap_or_else(|_| "[]".to_string())
-                    } else {
-                        "[]".to_string()
-                    };
-
-                    conn.execute(
-                        "INSERT INTO reply_settings (target_bot_ids, reply_bot_id, reply_content, is_active, last_checked_tweet_ids, created_at, updated_at)
-                         VALUES (?, ?, ?, 1, ?, ?, ?)",
-                        params![
-                            serde_json::to_string(&vec![target_bot_id]).unwrap(),
-                            reply_bot_id,
-                            reply_content,
-                            last_checked_tweet_ids,
-                            created_at,
-                            now
-                        ],
-                    )?;
-                }
+            conn.execute(
+                "INSERT INTO reply_settings (id, reply_bot_id, reply_content, is_active, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?)",
+                params![id, reply_bot_id, reply_content, is_active, created_at, updated_at],
+            )?;
+
+            for (target_bot_id, last_checked_tweet_id) in targets {
+                conn.execute(
+                    "INSERT INTO reply_targets (reply_setting_id, target_bot_id, last_checked_tweet_id)
+                     VALUES (?, ?, ?)",
+                    params![id, target_bot_id, last_checked_tweet_id],
+                )?;
             }
         }
 
-        println!("Successfully migrated reply_settings table to new schema");
+        verify_row_count(conn, "reply_targets", expected_targets as usize)?;
+        check_foreign_keys(conn, "reply_targets")?;
+
+        Ok(expected_settings as usize)
+    })
+}
+
+/// Parses the legacy `["<target_bot_id>:<tweet_id>", ...]` format.
+fn parse_last_checked_tweet_ids(json: Option<&str>) -> HashMap<i64, String> {
+    json.and_then(|json| serde_json::from_str::<Vec<String>>(json).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|entry| {
+            let (bot_id, tweet_id) = entry.split_once(':')?;
+            Some((bot_id.parse().ok()?, tweet_id.to_string()))
+        })
+        .collect()
+}
+
+/// Splits each schedule's `content_list` JSON into one `scheduled_posts`
+/// row per post, keeping list order as the ordinal. Deactivated schedules
+/// are split too, so reactivating or exporting one keeps its posts; the
+/// `content_list` column is no longer written.
+fn create_scheduled_posts(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS scheduled_posts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            schedule_id INTEGER NOT NULL,
+            ordinal INTEGER NOT NULL,
+            content TEXT NOT NULL,
+            is_enabled BOOLEAN NOT NULL DEFAULT 1,
+            last_posted_at TEXT,
+            post_count INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (schedule_id) REFERENCES scheduled_tweets(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_scheduled_posts_schedule_ordinal ON scheduled_posts(schedule_id, ordinal)",
+        [],
+    )?;
+
+    let mut schedules = Vec::new();
+    {
+        let mut stmt = conn.prepare(
+            "SELECT id, content, content_list, created_at, updated_at FROM scheduled_tweets",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+            ))
+        })?;
+
+        for row in rows {
+            schedules.push(row?);
+        }
+    }
+
+    let mut unparseable = Vec::new();
+    for (schedule_id, content, content_list, created_at, updated_at) in schedules {
+        let posts = match content_list {
+            Some(json) => match serde_json::from_str::<Vec<String>>(&json) {
+                Ok(posts) => posts,
+                Err(_) => {
+                    unparseable.push(schedule_id);
+                    continue;
+                }
+            },
+            None => vec![content],
+        };
+
+        for (ordinal, post) in posts.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO scheduled_posts (schedule_id, ordinal, content, is_enabled, post_count, created_at, updated_at)
+                 VALUES (?, ?, ?, 1, 0, ?, ?)",
+                params![schedule_id, ordinal as i64, post, created_at, updated_at],
+            )?;
+        }
+    }
+
+    if !unparseable.is_empty() {
+        return Err(anyhow!(
+            "scheduled_tweets rows {:?} have an unreadable content_list; fix or delete them and restart",
+            unparseable
+        ));
     }
 
+    // Counted by SQLite from the source lists rather than from the inserts.
+    let expected: i64 = conn.query_row(
+        "SELECT COALESCE(SUM(CASE WHEN content_list IS NULL THEN 1 ELSE json_array_length(content_list) END), 0)
+         FROM scheduled_tweets",
+        [],
+        |row| row.get(0),
+    )?;
+    verify_row_count(conn, "scheduled_posts", expected as usize)?;
+    check_foreign_keys(conn, "scheduled_posts")
+}
+
+/// Lets a log row carry a catalog message id and its parameters so it can be
+/// rendered in the viewer's language. `message` keeps the text as written for
+/// rows that have no id (older rows and logs added by the frontend).
+fn add_execution_log_message_ids(conn: &Connection) -> Result<()> {
+    conn.execute("ALTER TABLE execution_logs ADD COLUMN message_id TEXT", [])?;
+    conn.execute(
+        "ALTER TABLE execution_logs ADD COLUMN message_params TEXT",
+        [],
+    )?;
     Ok(())
 }
+
+/// Last `x-rate-limit-*` state reported per account and endpoint, so a
+/// restart does not forget an exhausted window. `reset_at` is Unix time.
+fn create_rate_limits(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS rate_limits (
+            account_id INTEGER NOT NULL,
+            endpoint TEXT NOT NULL,
+            limit_total INTEGER,
+            remaining INTEGER NOT NULL,
+            reset_at INTEGER NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (account_id, endpoint),
+            FOREIGN KEY (account_id) REFERENCES bot_accounts(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Classified failure code (`TwitterErrorCode` or `AppError` kind) for error
+/// rows, so they can be grouped without parsing `message`.
+fn add_execution_log_error_codes(conn: &Connection) -> Result<()> {
+    conn.execute("ALTER TABLE execution_logs ADD COLUMN error_code TEXT", [])?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_execution_logs_error_code ON execution_logs(error_code, created_at)",
+        [],
+    )?;
+    Ok(())
+}
+
+/// The Twitter user id and handle an account's credentials resolved to when
+/// last verified; NULL until the first verification.
+fn add_bot_account_twitter_identity(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "ALTER TABLE bot_accounts ADD COLUMN twitter_user_id TEXT",
+        [],
+    )?;
+    conn.execute(
+        "ALTER TABLE bot_accounts ADD COLUMN twitter_username TEXT",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Display name, time of the last credential check and its outcome. Accounts
+/// already flagged `invalid_credentials` start out `invalid`.
+fn add_bot_account_profile(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "ALTER TABLE bot_accounts ADD COLUMN twitter_display_name TEXT",
+        [],
+    )?;
+    conn.execute(
+        "ALTER TABLE bot_accounts ADD COLUMN last_verified_at TEXT",
+        [],
+    )?;
+    conn.execute(
+        "ALTER TABLE bot_accounts ADD COLUMN credential_health TEXT NOT NULL DEFAULT 'unknown'",
+        [],
+    )?;
+    conn.execute(
+        "UPDATE bot_accounts SET credential_health = 'invalid' WHERE status = 'invalid_credentials'",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_bot_accounts_twitter_user_id ON bot_accounts(twitter_user_id)",
+        [],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{database_path, NOW};
+    use tempfile::TempDir;
+
+    /// A database from before `schema_migrations`, with bot 1 present and
+    /// the original one-row-per-target `reply_settings` table.
+    fn legacy_db(dir: &TempDir, reply_bot_ids: &str) -> (Connection, CredentialVault) {
+        let vault = CredentialVault::open(dir.path()).unwrap();
+        let conn = Connection::open(database_path(dir.path())).unwrap();
+        conn.execute(
+            "CREATE TABLE reply_settings (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                target_bot_id INTEGER NOT NULL,
+                reply_bot_ids TEXT NOT NULL,
+                reply_content TEXT NOT NULL,
+                is_active BOOLEAN DEFAULT 1,
+                last_checked_tweet_id TEXT,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )
+        .unwrap();
+        create_initial_schema(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO bot_accounts (id, account_name, api_key, api_key_secret, access_token, access_token_secret, created_at, updated_at)
+             VALUES (1, 'first', 'k', 's', 't', 'ts', ?, ?)",
+            params![NOW, NOW],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO reply_settings (target_bot_id, reply_bot_ids, reply_content, created_at)
+             VALUES (1, ?, 'hi', ?)",
+            params![reply_bot_ids, NOW],
+        )
+        .unwrap();
+        (conn, vault)
+    }
+
+    #[test]
+    fn v2_drops_reply_bot_ids_of_deleted_bots() {
+        let dir = TempDir::new().unwrap();
+        let (conn, vault) = legacy_db(&dir, "[1, 9]");
+
+        run_database_migrations(&conn, &MigrationContext { vault: &vault }).unwrap();
+
+        let reply_bots: Vec<i64> = conn
+            .prepare("SELECT reply_bot_id FROM reply_settings")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        assert_eq!(reply_bots, vec![1]);
+        let backed_up: String = conn
+            .query_row(
+                "SELECT reply_bot_ids FROM reply_settings_backup_v2",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(backed_up, "[1, 9]");
+    }
+
+    #[test]
+    fn v5_drops_settings_whose_targets_are_gone() {
+        let dir = TempDir::new().unwrap();
+        let (conn, vault) = legacy_db(&dir, "[1]");
+        conn.execute(
+            "INSERT INTO reply_settings (target_bot_id, reply_bot_ids, reply_content, created_at)
+             VALUES (9, '[1]', 'hi', ?)",
+            params![NOW],
+        )
+        .unwrap();
+
+        run_database_migrations(&conn, &MigrationContext { vault: &vault }).unwrap();
+
+        let count = |table: &str| -> i64 {
+            conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+                row.get(0)
+            })
+            .unwrap()
+        };
+        assert_eq!(count("reply_settings_backup_v5"), 2);
+        assert_eq!(count("reply_settings"), 1);
+        assert_eq!(count("reply_targets"), 1);
+    }
+
+    #[test]
+    fn v6_splits_inactive_schedules_too() {
+        let dir = TempDir::new().unwrap();
+        let (conn, vault) = legacy_db(&dir, "[1]");
+        for (content_list, is_active) in [("[\"a\",\"b\"]", 1), ("[\"c\",\"d\",\"e\"]", 0)] {
+            conn.execute(
+                "INSERT INTO scheduled_tweets (account_id, content, content_list, scheduled_times, is_active, created_at, updated_at)
+                 VALUES (1, 'a', ?, '09:00', ?, ?, ?)",
+                params![content_list, is_active, NOW, NOW],
+            )
+            .unwrap();
+        }
+
+        run_database_migrations(&conn, &MigrationContext { vault: &vault }).unwrap();
+
+        let posts: Vec<(i64, String)> = conn
+            .prepare(
+                "SELECT schedule_id, content FROM scheduled_posts ORDER BY schedule_id, ordinal",
+            )
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        let contents: Vec<&str> = posts.iter().map(|(_, content)| content.as_str()).collect();
+        assert_eq!(contents, vec!["a", "b", "c", "d", "e"]);
+        assert_eq!(posts[2].0, 2);
+    }
+
+    #[test]
+    fn v2_refuses_unreadable_reply_bot_ids() {
+        let dir = TempDir::new().unwrap();
+        let (conn, vault) = legacy_db(&dir, "1,9");
+
+        let err = run_database_migrations(&conn, &MigrationContext { vault: &vault })
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("unreadable reply_bot_ids"), "{}", err);
+        let legacy_rows: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM reply_settings WHERE reply_bot_ids = '1,9'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(legacy_rows, 1);
+    }
+}
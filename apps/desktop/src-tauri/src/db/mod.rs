@@ -1,6 +1,8 @@
-﻿pub mod cleanup;
+﻿pub mod backup;
 pub mod connection;
 pub mod migrations;
+pub mod pool;
 pub mod queries;
 
-pub use connection::init_database;
+pub use backup::spawn_daily_backups;
+pub use connection::{data_directory, dry_run_migrations, init_database};
@@ -0,0 +1,114 @@
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use rusqlite::Connection;
+use thiserror::Error;
+
+const POOL_SIZE: usize = 4;
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+const CHECKOUT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Error)]
+pub enum PoolError {
+    #[error("Database pool lock was poisoned by a panicking command")]
+    Poisoned,
+    #[error("Timed out waiting for a free database connection")]
+    Timeout,
+}
+
+/// A fixed-size set of SQLite connections shared by Tauri commands.
+///
+/// Every connection runs in WAL mode with a busy timeout, so readers keep
+/// working while another connection writes.
+pub struct ConnectionPool {
+    idle: Mutex<Vec<Connection>>,
+    available: Condvar,
+}
+
+impl ConnectionPool {
+    /// Builds the pool around `first`, which has already been migrated, and
+    /// opens the remaining connections to `path`.
+    pub fn new(path: &Path, first: Connection) -> Result<Self> {
+        let mut connections = vec![first];
+        while connections.len() < POOL_SIZE {
+            let conn = Connection::open(path)?;
+            configure_connection(&conn)?;
+            connections.push(conn);
+        }
+
+        Ok(Self {
+            idle: Mutex::new(connections),
+            available: Condvar::new(),
+        })
+    }
+
+    /// Checks out a connection, waiting up to ten seconds for one to free up.
+    pub fn get(&self) -> Result<PooledConnection<'_>, PoolError> {
+        let idle = self.idle.lock().map_err(|_| PoolError::Poisoned)?;
+        let (mut idle, timeout) = self
+            .available
+            .wait_timeout_while(idle, CHECKOUT_TIMEOUT, |idle| idle.is_empty())
+            .map_err(|_| PoolError::Poisoned)?;
+
+        if timeout.timed_out() && idle.is_empty() {
+            return Err(PoolError::Timeout);
+        }
+
+        Ok(PooledConnection {
+            pool: self,
+            conn: idle.pop(),
+        })
+    }
+
+    fn put_back(&self, conn: Connection) {
+        // A poisoned pool is already unusable; dropping the connection is
+        // the best we can do.
+        if let Ok(mut idle) = self.idle.lock() {
+            idle.push(conn);
+            self.available.notify_one();
+        }
+    }
+}
+
+/// Applies the per-connection settings every pooled connection needs.
+pub fn configure_connection(conn: &Connection) -> Result<()> {
+    conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    conn.pragma_update(None, "foreign_keys", true)?;
+    Ok(())
+}
+
+/// A connection on loan from [`ConnectionPool`]; returned when dropped.
+pub struct PooledConnection<'a> {
+    pool: &'a ConnectionPool,
+    conn: Option<Connection>,
+}
+
+impl Deref for PooledConnection<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn
+            .as_ref()
+            .expect("pooled connection already returned")
+    }
+}
+
+impl DerefMut for PooledConnection<'_> {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn
+            .as_mut()
+            .expect("pooled connection already returned")
+    }
+}
+
+impl Drop for PooledConnection<'_> {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            self.pool.put_back(conn);
+        }
+    }
+}
@@ -1,46 +1,42 @@
 use anyhow::{anyhow, Result};
 use chrono::Utc;
-use rusqlite::{params, Connection};
-use serde_json;
+use rusqlite::{params, Connection, OptionalExtension};
 
-use crate::db::cleanup::cleanup_orphaned_reply_settings;
-use crate::models::{BotAccount, BotConfig, DashboardStats};
+use crate::models::{
+    BotAccount, BotAccountUpdate, BotConfig, CredentialHealth, DashboardStats, ErrorCodeCount,
+};
+use crate::utils::vault::CredentialVault;
 
-pub fn fetch_all_bots(conn: &Connection) -> Result<Vec<BotAccount>> {
+pub fn fetch_all_bots(conn: &Connection, vault: &CredentialVault) -> Result<Vec<BotAccount>> {
     let mut stmt = conn
         .prepare("SELECT * FROM bot_accounts ORDER BY created_at DESC")
         .map_err(|e| anyhow!(e))?;
 
     let rows = stmt
-        .query_map([], |row| {
-            Ok(BotAccount {
-                id: row.get(0)?,
-                account_name: row.get(1)?,
-                api_key: row.get(2)?,
-                api_key_secret: row.get(3)?,
-                access_token: row.get(4)?,
-                access_token_secret: row.get(5)?,
-                api_type: row.get(6)?,
-                status: row.get(7)?,
-                created_at: Some(row.get(8)?),
-                updated_at: Some(row.get(9)?),
-            })
-        })
+        .query_map([], row_to_bot_account)
         .map_err(|e| anyhow!(e))?;
 
-    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    rows.collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .map(|account| decrypt_bot_account(vault, account))
+        .collect()
 }
 
-pub fn insert_bot_account(conn: &Connection, account: &BotAccount, now: &str) -> Result<i64> {
+pub fn insert_bot_account(
+    conn: &Connection,
+    vault: &CredentialVault,
+    account: &BotAccount,
+    now: &str,
+) -> Result<i64> {
     conn.execute(
         "INSERT INTO bot_accounts (account_name, api_key, api_key_secret, access_token, access_token_secret, api_type, status, created_at, updated_at)
          VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
         params![
             account.account_name,
-            account.api_key,
-            account.api_key_secret,
-            account.access_token,
-            account.access_token_secret,
+            vault.encrypt(&account.api_key)?,
+            vault.encrypt(&account.api_key_secret)?,
+            vault.encrypt(&account.access_token)?,
+            vault.encrypt(&account.access_token_secret)?,
             account.api_type,
             account.status,
             now,
@@ -61,26 +57,38 @@ pub fn insert_bot_account(conn: &Connection, account: &BotAccount, now: &str) ->
     Ok(account_id)
 }
 
-pub fn update_bot_account(conn: &Connection, account: &BotAccount, now: &str) -> Result<()> {
-    let id = account
-        .id
-        .ok_or_else(|| anyhow!("account id is required for update"))?;
+pub fn update_bot_account(
+    conn: &Connection,
+    vault: &CredentialVault,
+    account: &BotAccountUpdate,
+    now: &str,
+) -> Result<()> {
+    let encrypt_if_changed = |secret: &Option<String>| -> Result<Option<String>> {
+        match secret.as_deref().map(str::trim) {
+            Some(value) if !value.is_empty() => Ok(Some(vault.encrypt(value)?)),
+            _ => Ok(None),
+        }
+    };
 
     conn.execute(
         "UPDATE bot_accounts
-         SET account_name = ?, api_key = ?, api_key_secret = ?, access_token = ?, access_token_secret = ?,
+         SET account_name = ?,
+             api_key = COALESCE(?, api_key),
+             api_key_secret = COALESCE(?, api_key_secret),
+             access_token = COALESCE(?, access_token),
+             access_token_secret = COALESCE(?, access_token_secret),
              api_type = ?, status = ?, updated_at = ?
          WHERE id = ?",
         params![
             account.account_name,
-            account.api_key,
-            account.api_key_secret,
-            account.access_token,
-            account.access_token_secret,
+            encrypt_if_changed(&account.api_key)?,
+            encrypt_if_changed(&account.api_key_secret)?,
+            encrypt_if_changed(&account.access_token)?,
+            encrypt_if_changed(&account.access_token_secret)?,
             account.api_type,
             account.status,
             now,
-            id
+            account.id
         ],
     )
     .map_err(|e| anyhow!(e))?;
@@ -88,75 +96,13 @@ pub fn update_bot_account(conn: &Connection, account: &BotAccount, now: &str) ->
     Ok(())
 }
 
-pub fn delete_bot_account(conn: &Connection, id: i64, now: &str) -> Result<()> {
-    let deleted_reply_settings = conn
-        .execute(
-            "DELETE FROM reply_settings WHERE reply_bot_id = ?",
-            params![id],
-        )
-        .map_err(|e| anyhow!(e))?;
-
-    let mut settings_to_update = Vec::new();
-    {
-        let mut stmt = conn
-            .prepare("SELECT id, target_bot_ids FROM reply_settings WHERE is_active = 1")
-            .map_err(|e| anyhow!(e))?;
-        let rows = stmt
-            .query_map([], |row| {
-                let setting_id: i64 = row.get(0)?;
-                let target_bot_ids_json: String = row.get(1)?;
-                Ok((setting_id, target_bot_ids_json))
-            })
-            .map_err(|e| anyhow!(e))?;
-
-        for (setting_id, target_bot_ids_json) in rows.collect::<Result<Vec<_>, _>>()? {
-            if let Ok(target_bot_ids) = serde_json::from_str::<Vec<i64>>(&target_bot_ids_json) {
-                let original_len = target_bot_ids.len();
-                let updated_target_bot_ids: Vec<i64> = target_bot_ids
-                    .into_iter()
-                    .filter(|&target_id| target_id != id)
-                    .collect();
-
-                if updated_target_bot_ids.len() != original_len {
-                    if updated_target_bot_ids.is_empty() {
-                        settings_to_update.push((setting_id, None));
-                    } else {
-                        let updated_json = serde_json::to_string(&updated_target_bot_ids)?;
-                        settings_to_update.push((setting_id, Some(updated_json)));
-                    }
-                }
-            }
-        }
-    }
-
-    for (setting_id, updated_targets) in settings_to_update {
-        match updated_targets {
-            Some(json) => {
-                conn.execute(
-                    "UPDATE reply_settings SET target_bot_ids = ?, updated_at = ? WHERE id = ?",
-                    params![json, now, setting_id],
-                )
-                .map_err(|e| anyhow!(e))?;
-            }
-            None => {
-                conn.execute(
-                    "DELETE FROM reply_settings WHERE id = ?",
-                    params![setting_id],
-                )
-                .map_err(|e| anyhow!(e))?;
-            }
-        }
-    }
-
+/// Deletes the account; bot_configs, execution_logs, scheduled_tweets,
+/// reply_settings and reply_targets rows follow via `ON DELETE CASCADE`.
+pub fn delete_bot_account(conn: &Connection, id: i64) -> Result<()> {
     conn.execute("DELETE FROM bot_accounts WHERE id = ?", params![id])
         .map_err(|e| anyhow!(e))?;
 
-    cleanup_orphaned_reply_settings(conn)?;
-
-    println!(
-        "Deleted bot account {} and cleaned up {} reply settings",
-        id, deleted_reply_settings
-    );
+    println!("Deleted bot account {}", id);
 
     Ok(())
 }
@@ -203,25 +149,133 @@ pub fn update_bot_config(conn: &Connection, config: &BotConfig, now: &str) -> Re
     Ok(())
 }
 
-pub fn find_bot_account_by_id(conn: &Connection, id: i64) -> Result<BotAccount> {
+pub fn find_bot_account_by_id(
+    conn: &Connection,
+    vault: &CredentialVault,
+    id: i64,
+) -> Result<BotAccount> {
+    let account = conn
+        .query_row(
+            "SELECT * FROM bot_accounts WHERE id = ?",
+            params![id],
+            row_to_bot_account,
+        )
+        .map_err(|e| anyhow!(e))?;
+
+    decrypt_bot_account(vault, account)
+}
+
+/// Stores the profile `GET /2/users/me` returned for the account's
+/// credentials and marks them valid.
+pub fn update_bot_profile(
+    conn: &Connection,
+    id: i64,
+    twitter_user_id: &str,
+    twitter_username: &str,
+    twitter_display_name: &str,
+    status: &str,
+    now: &str,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE bot_accounts
+         SET twitter_user_id = ?, twitter_username = ?, twitter_display_name = ?,
+             credential_health = ?, last_verified_at = ?, status = ?, updated_at = ?
+         WHERE id = ?",
+        params![
+            twitter_user_id,
+            twitter_username,
+            twitter_display_name,
+            CredentialHealth::Valid.as_str(),
+            now,
+            status,
+            now,
+            id
+        ],
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    Ok(())
+}
+
+/// Records a check that returned no profile, keeping the stored one.
+pub fn update_bot_credential_health(
+    conn: &Connection,
+    id: i64,
+    health: CredentialHealth,
+    status: &str,
+    now: &str,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE bot_accounts
+         SET credential_health = ?, last_verified_at = ?, status = ?, updated_at = ?
+         WHERE id = ?",
+        params![health.as_str(), now, status, now, id],
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    Ok(())
+}
+
+/// Copies the Twitter identity an imported account was exported with.
+pub fn restore_bot_identity(
+    conn: &Connection,
+    id: i64,
+    account: &BotAccount,
+    health: CredentialHealth,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE bot_accounts
+         SET twitter_user_id = ?, twitter_username = ?, twitter_display_name = ?,
+             last_verified_at = ?, credential_health = ?
+         WHERE id = ?",
+        params![
+            account.twitter_user_id,
+            account.twitter_username,
+            account.twitter_display_name,
+            account.last_verified_at,
+            health.as_str(),
+            id
+        ],
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    Ok(())
+}
+
+/// Every account's id and name, without touching the credentials.
+pub fn fetch_account_names(conn: &Connection) -> Result<Vec<(i64, String)>> {
+    let mut stmt = conn
+        .prepare("SELECT id, account_name FROM bot_accounts ORDER BY id")
+        .map_err(|e| anyhow!(e))?;
+
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| anyhow!(e))?;
+
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// One stored credential in the vault's ciphertext format, if any, for
+/// checking the vault key before it is used. Databases that predate
+/// `bot_accounts` have none.
+pub fn find_encrypted_credential(conn: &Connection) -> Result<Option<String>> {
+    let has_table: bool = conn
+        .query_row(
+            "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'bot_accounts'",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| anyhow!(e))?;
+    if !has_table {
+        return Ok(None);
+    }
+
     conn.query_row(
-        "SELECT * FROM bot_accounts WHERE id = ?",
-        params![id],
-        |row| {
-            Ok(BotAccount {
-                id: Some(row.get(0)?),
-                account_name: row.get(1)?,
-                api_key: row.get(2)?,
-                api_key_secret: row.get(3)?,
-                access_token: row.get(4)?,
-                access_token_secret: row.get(5)?,
-                api_type: row.get(6)?,
-                status: row.get(7)?,
-                created_at: Some(row.get(8)?),
-                updated_at: Some(row.get(9)?),
-            })
-        },
+        "SELECT api_key FROM bot_accounts WHERE api_key LIKE 'enc:%' LIMIT 1",
+        [],
+        |row| row.get(0),
     )
+    .optional()
     .map_err(|e| anyhow!(e))
 }
 
@@ -275,11 +329,61 @@ pub fn fetch_dashboard_stats(conn: &Connection) -> Result<DashboardStats> {
         )
         .map_err(|e| anyhow!(e))?;
 
+    // Rows written before error codes existed group under "other".
+    let mut stmt = conn
+        .prepare(
+            "SELECT COALESCE(error_code, 'other') AS code, COUNT(*) FROM execution_logs
+             WHERE status = 'error' AND date(created_at) = ?
+             GROUP BY code ORDER BY COUNT(*) DESC, code",
+        )
+        .map_err(|e| anyhow!(e))?;
+    let errors_by_code = stmt
+        .query_map(params![today], |row| {
+            Ok(ErrorCodeCount {
+                error_code: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })
+        .map_err(|e| anyhow!(e))?
+        .collect::<Result<Vec<_>, _>>()?;
+
     Ok(DashboardStats {
         total_accounts,
         active_accounts,
         today_tweets,
         total_tweets,
         error_count,
+        errors_by_code,
     })
 }
+
+pub(crate) fn row_to_bot_account(row: &rusqlite::Row<'_>) -> rusqlite::Result<BotAccount> {
+    Ok(BotAccount {
+        id: Some(row.get(0)?),
+        account_name: row.get(1)?,
+        api_key: row.get(2)?,
+        api_key_secret: row.get(3)?,
+        access_token: row.get(4)?,
+        access_token_secret: row.get(5)?,
+        api_type: row.get(6)?,
+        status: row.get(7)?,
+        twitter_user_id: row.get("twitter_user_id")?,
+        twitter_username: row.get("twitter_username")?,
+        twitter_display_name: row.get("twitter_display_name")?,
+        last_verified_at: row.get("last_verified_at")?,
+        credential_health: CredentialHealth::from_db(&row.get::<_, String>("credential_health")?),
+        created_at: Some(row.get(8)?),
+        updated_at: Some(row.get(9)?),
+    })
+}
+
+pub(crate) fn decrypt_bot_account(
+    vault: &CredentialVault,
+    mut account: BotAccount,
+) -> Result<BotAccount> {
+    account.api_key = vault.decrypt(&account.api_key)?;
+    account.api_key_secret = vault.decrypt(&account.api_key_secret)?;
+    account.access_token = vault.decrypt(&account.access_token)?;
+    account.access_token_secret = vault.decrypt(&account.access_token_secret)?;
+    Ok(account)
+}
@@ -30,9 +30,14 @@ pub fn fetch_execution_logs(
 }
 
 pub fn insert_execution_log(conn: &Connection, log: &ExecutionLog, timestamp: &str) -> Result<i64> {
+    let message_params = match log.message_id {
+        Some(_) => Some(serde_json::to_string(&log.message_params)?),
+        None => None,
+    };
+
     conn.execute(
-        "INSERT INTO execution_logs (account_id, log_type, message, tweet_id, tweet_content, status, created_at)
-         VALUES (?, ?, ?, ?, ?, ?, ?)",
+        "INSERT INTO execution_logs (account_id, log_type, message, tweet_id, tweet_content, status, created_at, message_id, message_params, error_code)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         params![
             log.account_id,
             log.log_type,
@@ -40,7 +45,10 @@ pub fn insert_execution_log(conn: &Connection, log: &ExecutionLog, timestamp: &s
             log.tweet_id,
             log.tweet_content,
             log.status,
-            timestamp
+            timestamp,
+            log.message_id,
+            message_params,
+            log.error_code
         ],
     )
     .map_err(|e| anyhow!(e))?;
@@ -48,12 +56,19 @@ pub fn insert_execution_log(conn: &Connection, log: &ExecutionLog, timestamp: &s
     Ok(conn.last_insert_rowid())
 }
 
-fn row_to_execution_log(row: &rusqlite::Row<'_>) -> rusqlite::Result<ExecutionLog> {
+pub fn row_to_execution_log(row: &rusqlite::Row<'_>) -> rusqlite::Result<ExecutionLog> {
+    let message_params: Option<String> = row.get("message_params")?;
+
     Ok(ExecutionLog {
         id: row.get(0)?,
         account_id: row.get(1)?,
         log_type: row.get(2)?,
         message: row.get(3)?,
+        message_id: row.get("message_id")?,
+        message_params: message_params
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+        error_code: row.get("error_code")?,
         tweet_id: row.get(4)?,
         tweet_content: row.get(5)?,
         status: row.get(6)?,
@@ -1,5 +1,8 @@
 ﻿pub mod bots;
 pub mod logs;
+pub mod quota;
+pub mod rate_limits;
 pub mod replies;
 pub mod schedules;
+pub mod settings;
 pub mod users;
@@ -0,0 +1,48 @@
+use anyhow::{anyhow, Result};
+use rusqlite::{params, Connection};
+
+use crate::models::QuotaUsage;
+
+/// Usage ledger over `execution_logs`: successful `tweet` rows count as
+/// posts and `read` rows as reads, grouped by UTC calendar month.
+const QUOTA_USAGE_QUERY: &str = "SELECT b.id, b.account_name, b.api_type,
+        (SELECT COUNT(*) FROM execution_logs l
+         WHERE l.account_id = b.id AND l.log_type = 'tweet' AND l.status = 'success'
+           AND strftime('%Y-%m', l.created_at) = ?1),
+        (SELECT COUNT(*) FROM execution_logs l
+         WHERE l.account_id = b.id AND l.log_type = 'read'
+           AND strftime('%Y-%m', l.created_at) = ?1)
+     FROM bot_accounts b";
+
+fn row_to_quota_usage(row: &rusqlite::Row<'_>, month: &str) -> rusqlite::Result<QuotaUsage> {
+    Ok(QuotaUsage::new(
+        row.get(0)?,
+        row.get(1)?,
+        row.get(2)?,
+        month.to_string(),
+        row.get(3)?,
+        row.get(4)?,
+    ))
+}
+
+/// Usage of every account in `month` (`YYYY-MM`).
+pub fn fetch_quota_usage(conn: &Connection, month: &str) -> Result<Vec<QuotaUsage>> {
+    let mut stmt = conn
+        .prepare(&format!("{} ORDER BY b.id", QUOTA_USAGE_QUERY))
+        .map_err(|e| anyhow!(e))?;
+
+    let rows = stmt
+        .query_map(params![month], |row| row_to_quota_usage(row, month))
+        .map_err(|e| anyhow!(e))?;
+
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+pub fn find_quota_usage(conn: &Connection, account_id: i64, month: &str) -> Result<QuotaUsage> {
+    conn.query_row(
+        &format!("{} WHERE b.id = ?2", QUOTA_USAGE_QUERY),
+        params![month, account_id],
+        |row| row_to_quota_usage(row, month),
+    )
+    .map_err(|e| anyhow!(e))
+}
@@ -0,0 +1,81 @@
+use anyhow::{anyhow, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::models::{AccountRateLimit, RateLimit};
+
+pub fn find_rate_limit(
+    conn: &Connection,
+    account_id: i64,
+    endpoint: &str,
+) -> Result<Option<RateLimit>> {
+    conn.query_row(
+        "SELECT account_id, endpoint, limit_total, remaining, reset_at, updated_at
+         FROM rate_limits WHERE account_id = ? AND endpoint = ?",
+        params![account_id, endpoint],
+        |row| {
+            Ok(RateLimit {
+                account_id: row.get(0)?,
+                endpoint: row.get(1)?,
+                limit: row.get(2)?,
+                remaining: row.get(3)?,
+                reset_at: row.get(4)?,
+                updated_at: row.get(5)?,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| anyhow!(e))
+}
+
+pub fn upsert_rate_limit(conn: &Connection, rate_limit: &RateLimit) -> Result<()> {
+    conn.execute(
+        "INSERT INTO rate_limits (account_id, endpoint, limit_total, remaining, reset_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?)
+         ON CONFLICT(account_id, endpoint) DO UPDATE SET
+            limit_total = COALESCE(excluded.limit_total, rate_limits.limit_total),
+            remaining = excluded.remaining,
+            reset_at = excluded.reset_at,
+            updated_at = excluded.updated_at",
+        params![
+            rate_limit.account_id,
+            rate_limit.endpoint,
+            rate_limit.limit,
+            rate_limit.remaining,
+            rate_limit.reset_at,
+            rate_limit.updated_at
+        ],
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    Ok(())
+}
+
+/// Every account with each endpoint it has a recorded window for, in account
+/// order.
+pub fn fetch_account_rate_limits(conn: &Connection) -> Result<Vec<AccountRateLimit>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT b.id, b.account_name, b.api_type, r.endpoint, r.limit_total, r.remaining, r.reset_at, r.updated_at
+             FROM bot_accounts b
+             LEFT JOIN rate_limits r ON r.account_id = b.id
+             ORDER BY b.id, r.endpoint",
+        )
+        .map_err(|e| anyhow!(e))?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(AccountRateLimit {
+                account_id: row.get(0)?,
+                account_name: row.get(1)?,
+                api_type: row.get(2)?,
+                endpoint: row.get(3)?,
+                limit: row.get(4)?,
+                remaining: row.get(5)?,
+                reset_at: row.get(6)?,
+                updated_at: row.get(7)?,
+            })
+        })
+        .map_err(|e| anyhow!(e))?;
+
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
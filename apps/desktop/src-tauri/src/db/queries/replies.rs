@@ -1,12 +1,8 @@
-use std::collections::HashMap;
-
 use anyhow::{anyhow, Result};
-use chrono::Utc;
 use rusqlite::{params, Connection};
 use serde_json;
 
-use super::bots::bot_exists;
-use crate::models::ReplySettings;
+use crate::models::{ReplySettings, ReplyTargetState};
 
 pub fn save_reply_settings(
     conn: &Connection,
@@ -15,8 +11,21 @@ pub fn save_reply_settings(
     reply_content: &str,
     now: &str,
 ) -> Result<i64> {
-    let target_bot_ids_json = serde_json::to_string(target_bot_ids)?;
+    let tx = conn.unchecked_transaction().map_err(|e| anyhow!(e))?;
+    let setting_id = insert_reply_setting(&tx, reply_bot_id, target_bot_ids, reply_content, now)?;
+    tx.commit().map_err(|e| anyhow!(e))?;
+    Ok(setting_id)
+}
 
+/// Deactivates the reply bot's current setting and inserts the new one with
+/// its targets, inside the caller's transaction.
+pub fn insert_reply_setting(
+    conn: &Connection,
+    reply_bot_id: i64,
+    target_bot_ids: &[i64],
+    reply_content: &str,
+    now: &str,
+) -> Result<i64> {
     conn.execute(
         "UPDATE reply_settings SET is_active = 0, updated_at = ? WHERE reply_bot_id = ?",
         params![now, reply_bot_id],
@@ -24,22 +33,33 @@ pub fn save_reply_settings(
     .map_err(|e| anyhow!(e))?;
 
     conn.execute(
-        "INSERT INTO reply_settings (target_bot_ids, reply_bot_id, reply_content, is_active, last_checked_tweet_ids, created_at, updated_at)
-         VALUES (?, ?, ?, 1, '[]', ?, ?)",
-        params![target_bot_ids_json, reply_bot_id, reply_content, now, now],
+        "INSERT INTO reply_settings (reply_bot_id, reply_content, is_active, created_at, updated_at)
+         VALUES (?, ?, 1, ?, ?)",
+        params![reply_bot_id, reply_content, now, now],
     )
     .map_err(|e| anyhow!(e))?;
+    let setting_id = conn.last_insert_rowid();
 
-    Ok(conn.last_insert_rowid())
+    for target_bot_id in target_bot_ids {
+        conn.execute(
+            "INSERT OR IGNORE INTO reply_targets (reply_setting_id, target_bot_id) VALUES (?, ?)",
+            params![setting_id, target_bot_id],
+        )
+        .map_err(|e| anyhow!(e))?;
+    }
+
+    Ok(setting_id)
 }
 
+/// Returns active reply settings with their targets folded back into the
+/// JSON strings the UI and the Actions config expect.
 pub fn fetch_reply_settings(conn: &Connection) -> Result<Vec<ReplySettings>> {
     let mut stmt = conn
         .prepare(
-            "SELECT rs.* FROM reply_settings rs
-         INNER JOIN bot_accounts ba ON rs.reply_bot_id = ba.id
-         WHERE rs.is_active = 1 
-         ORDER BY rs.created_at DESC",
+            "SELECT id, reply_bot_id, reply_content, is_active, created_at, updated_at
+             FROM reply_settings
+             WHERE is_active = 1
+             ORDER BY created_at DESC",
         )
         .map_err(|e| anyhow!(e))?;
 
@@ -47,48 +67,60 @@ pub fn fetch_reply_settings(conn: &Connection) -> Result<Vec<ReplySettings>> {
         .query_map([], |row| {
             Ok(ReplySettings {
                 id: row.get(0)?,
-                target_bot_ids: row.get(1)?,
-                reply_bot_id: row.get(2)?,
-                reply_content: row.get(3)?,
-                is_active: row.get(4)?,
-                last_checked_tweet_ids: row.get(5)?,
-                created_at: row.get(6)?,
-                updated_at: row.get(7)?,
+                target_bot_ids: String::new(),
+                reply_bot_id: row.get(1)?,
+                reply_content: row.get(2)?,
+                is_active: row.get(3)?,
+                last_checked_tweet_ids: None,
+                created_at: row.get(4)?,
+                updated_at: row.get(5)?,
             })
         })
         .map_err(|e| anyhow!(e))?;
 
-    let mut valid_settings = Vec::new();
-    let now = Utc::now().to_rfc3339();
-
-    for mut setting in settings_iter.collect::<Result<Vec<_>, _>>()? {
-        if let Ok(target_ids) = serde_json::from_str::<Vec<i64>>(&setting.target_bot_ids) {
-            let original_len = target_ids.len();
-            let mut valid_targets = Vec::new();
-
-            for target_id in target_ids {
-                if bot_exists(conn, target_id)? {
-                    valid_targets.push(target_id);
-                }
-            }
-
-            if !valid_targets.is_empty() {
-                if valid_targets.len() != original_len {
-                    setting.target_bot_ids = serde_json::to_string(&valid_targets)?;
-                    conn.execute(
-                        "UPDATE reply_settings SET target_bot_ids = ?, updated_at = ? WHERE id = ?",
-                        params![setting.target_bot_ids, now, setting.id],
-                    )
-                    .map_err(|e| anyhow!(e))?;
-                    setting.updated_at = now.clone();
-                }
-
-                valid_settings.push(setting);
-            }
-        }
+    let mut settings = Vec::new();
+    for setting in settings_iter {
+        let mut setting = setting?;
+        let targets = fetch_reply_targets(conn, setting.id.unwrap_or_default())?;
+
+        let target_bot_ids: Vec<i64> = targets.iter().map(|(id, _)| *id).collect();
+        let last_checked_tweet_ids: Vec<String> = targets
+            .iter()
+            .filter_map(|(id, tweet_id)| {
+                tweet_id
+                    .as_ref()
+                    .map(|tweet_id| format!("{}:{}", id, tweet_id))
+            })
+            .collect();
+
+        setting.target_bot_ids = serde_json::to_string(&target_bot_ids)?;
+        setting.last_checked_tweet_ids = Some(serde_json::to_string(&last_checked_tweet_ids)?);
+        settings.push(setting);
     }
 
-    Ok(valid_settings)
+    Ok(settings)
+}
+
+fn fetch_reply_targets(
+    conn: &Connection,
+    reply_setting_id: i64,
+) -> Result<Vec<(i64, Option<String>)>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT target_bot_id, last_checked_tweet_id FROM reply_targets
+             WHERE reply_setting_id = ?
+             ORDER BY rowid",
+        )
+        .map_err(|e| anyhow!(e))?;
+
+    let targets = stmt
+        .query_map(params![reply_setting_id], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .map_err(|e| anyhow!(e))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(targets)
 }
 
 pub fn delete_reply_setting(conn: &Connection, id: i64) -> Result<()> {
@@ -104,56 +136,68 @@ pub fn update_last_checked_tweet(
     tweet_id: &str,
     now: &str,
 ) -> Result<()> {
-    let current_ids: String = conn
-        .query_row(
-            "SELECT last_checked_tweet_ids FROM reply_settings WHERE reply_bot_id = ? AND is_active = 1",
-            params![reply_bot_id],
-            |row| row.get(0),
-        )
-        .unwrap_or_else(|_| "[]".to_string());
-
-    let mut tweet_id_map: HashMap<String, String> =
-        if let Ok(ids) = serde_json::from_str::<Vec<String>>(&current_ids) {
-            ids.into_iter()
-                .filter_map(|entry| {
-                    let parts: Vec<&str> = entry.split(':').collect();
-                    if parts.len() == 2 {
-                        Some((parts[0].to_string(), parts[1].to_string()))
-                    } else {
-                        None
-                    }
-                })
-                .collect()
-        } else {
-            HashMap::new()
-        };
-
-    tweet_id_map.insert(target_bot_id.to_string(), tweet_id.to_string());
-
-    let updated_ids: Vec<String> = tweet_id_map
-        .into_iter()
-        .map(|(bot_id, tweet)| format!("{}:{}", bot_id, tweet))
-        .collect();
-
-    let updated_ids_json = serde_json::to_string(&updated_ids)?;
+    conn.execute(
+        "UPDATE reply_targets SET last_checked_tweet_id = ?
+         WHERE target_bot_id = ?
+           AND reply_setting_id IN (SELECT id FROM reply_settings WHERE reply_bot_id = ? AND is_active = 1)",
+        params![tweet_id, target_bot_id, reply_bot_id],
+    )
+    .map_err(|e| anyhow!(e))?;
 
     conn.execute(
-        "UPDATE reply_settings SET last_checked_tweet_ids = ?, updated_at = ? 
-         WHERE reply_bot_id = ? AND is_active = 1",
-        params![updated_ids_json, now, reply_bot_id],
+        "UPDATE reply_settings SET updated_at = ? WHERE reply_bot_id = ? AND is_active = 1",
+        params![now, reply_bot_id],
     )
     .map_err(|e| anyhow!(e))?;
 
     Ok(())
 }
 
-pub fn count_active_reply_settings(conn: &Connection) -> Result<i32> {
-    let count: i32 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM reply_settings WHERE is_active = 1",
-            [],
-            |row| row.get(0),
+/// Every target of an active reply setting.
+pub fn fetch_reply_target_states(conn: &Connection) -> Result<Vec<ReplyTargetState>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT s.id, s.reply_bot_id, t.target_bot_id, t.last_checked_tweet_id
+             FROM reply_settings s
+             JOIN reply_targets t ON t.reply_setting_id = s.id
+             WHERE s.is_active = 1
+             ORDER BY s.id, t.rowid",
         )
         .map_err(|e| anyhow!(e))?;
-    Ok(count)
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(ReplyTargetState {
+                reply_setting_id: row.get(0)?,
+                reply_bot_id: row.get(1)?,
+                target_bot_id: row.get(2)?,
+                last_checked_tweet_id: row.get(3)?,
+            })
+        })
+        .map_err(|e| anyhow!(e))?;
+
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+pub fn set_reply_target_checked(
+    conn: &Connection,
+    reply_setting_id: i64,
+    target_bot_id: i64,
+    tweet_id: &str,
+    now: &str,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE reply_targets SET last_checked_tweet_id = ?
+         WHERE reply_setting_id = ? AND target_bot_id = ?",
+        params![tweet_id, reply_setting_id, target_bot_id],
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    conn.execute(
+        "UPDATE reply_settings SET updated_at = ? WHERE id = ?",
+        params![now, reply_setting_id],
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    Ok(())
 }
@@ -2,7 +2,9 @@ use anyhow::{anyhow, Result};
 use rusqlite::{params, Connection, Row};
 use serde_json;
 
-use crate::models::ScheduledTweet;
+use crate::error::AppError;
+use crate::i18n::Message;
+use crate::models::{ScheduledPost, ScheduledTweet};
 
 pub fn save_scheduled_tweet_list(
     conn: &Connection,
@@ -11,24 +13,28 @@ pub fn save_scheduled_tweet_list(
     content_list: &[String],
     now: &str,
 ) -> Result<()> {
-    conn.execute(
+    let tx = conn.unchecked_transaction().map_err(|e| anyhow!(e))?;
+
+    tx.execute(
         "UPDATE scheduled_tweets SET is_active = 0, updated_at = ? WHERE account_id = ?",
         params![now, account_id],
     )
     .map_err(|e| anyhow!(e))?;
 
     if !scheduled_times.is_empty() && !content_list.is_empty() {
-        let content_list_json = serde_json::to_string(content_list)?;
         let first_content = content_list.first().cloned().unwrap_or_default();
 
-        conn.execute(
-            "INSERT INTO scheduled_tweets (account_id, content, content_list, current_index, scheduled_times, is_active, created_at, updated_at)
-             VALUES (?, ?, ?, 0, ?, 1, ?, ?)",
-            params![account_id, first_content, content_list_json, scheduled_times, now, now],
+        tx.execute(
+            "INSERT INTO scheduled_tweets (account_id, content, current_index, scheduled_times, is_active, created_at, updated_at)
+             VALUES (?, ?, 0, ?, 1, ?, ?)",
+            params![account_id, first_content, scheduled_times, now, now],
         )
         .map_err(|e| anyhow!(e))?;
+
+        insert_scheduled_posts(&tx, tx.last_insert_rowid(), content_list, now)?;
     }
 
+    tx.commit().map_err(|e| anyhow!(e))?;
     Ok(())
 }
 
@@ -39,32 +45,43 @@ pub fn save_single_scheduled_tweet(
     content: &str,
     now: &str,
 ) -> Result<()> {
-    conn.execute(
+    let tx = conn.unchecked_transaction().map_err(|e| anyhow!(e))?;
+
+    tx.execute(
         "UPDATE scheduled_tweets SET is_active = 0, updated_at = ? WHERE account_id = ?",
         params![now, account_id],
     )
     .map_err(|e| anyhow!(e))?;
 
     if !scheduled_times.is_empty() && !content.trim().is_empty() {
-        conn.execute(
+        tx.execute(
             "INSERT INTO scheduled_tweets (account_id, content, scheduled_times, is_active, created_at, updated_at)
              VALUES (?, ?, ?, 1, ?, ?)",
             params![account_id, content, scheduled_times, now, now],
         )
         .map_err(|e| anyhow!(e))?;
+
+        insert_scheduled_posts(&tx, tx.last_insert_rowid(), &[content.to_string()], now)?;
     }
 
+    tx.commit().map_err(|e| anyhow!(e))?;
     Ok(())
 }
 
 pub fn add_scheduled_tweet(conn: &Connection, tweet: &ScheduledTweet, now: &str) -> Result<i64> {
-    conn.execute(
-        "INSERT INTO scheduled_tweets (account_id, content, content_list, current_index, scheduled_times, is_active, created_at, updated_at)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    let posts = match &tweet.content_list {
+        Some(json) => serde_json::from_str::<Vec<String>>(json).map_err(|e| anyhow!(e))?,
+        None => vec![tweet.content.clone()],
+    };
+
+    let tx = conn.unchecked_transaction().map_err(|e| anyhow!(e))?;
+
+    tx.execute(
+        "INSERT INTO scheduled_tweets (account_id, content, current_index, scheduled_times, is_active, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
         params![
             tweet.account_id,
             tweet.content,
-            tweet.content_list,
             tweet.current_index.unwrap_or(0),
             tweet.scheduled_times.clone(),
             tweet.is_active,
@@ -73,43 +90,133 @@ pub fn add_scheduled_tweet(conn: &Connection, tweet: &ScheduledTweet, now: &str)
         ],
     )
     .map_err(|e| anyhow!(e))?;
+    let schedule_id = tx.last_insert_rowid();
 
-    Ok(conn.last_insert_rowid())
+    insert_scheduled_posts(&tx, schedule_id, &posts, now)?;
+
+    tx.commit().map_err(|e| anyhow!(e))?;
+    Ok(schedule_id)
+}
+
+/// Makes `tweet` with `posts` the account's active schedule, keeping the
+/// posts' order, enabled flags and counters. Runs inside the caller's
+/// transaction; used when restoring an export.
+pub fn restore_schedule(
+    conn: &Connection,
+    account_id: i64,
+    tweet: &ScheduledTweet,
+    posts: &[ScheduledPost],
+    now: &str,
+) -> Result<i64> {
+    conn.execute(
+        "UPDATE scheduled_tweets SET is_active = 0, updated_at = ? WHERE account_id = ? AND is_active = 1",
+        params![now, account_id],
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    conn.execute(
+        "INSERT INTO scheduled_tweets (account_id, content, current_index, scheduled_times, is_active, created_at, updated_at)
+         VALUES (?, ?, ?, ?, 1, ?, ?)",
+        params![
+            account_id,
+            tweet.content,
+            tweet.current_index.unwrap_or(0),
+            tweet.scheduled_times,
+            tweet.created_at,
+            now
+        ],
+    )
+    .map_err(|e| anyhow!(e))?;
+    let schedule_id = conn.last_insert_rowid();
+
+    for post in posts {
+        conn.execute(
+            "INSERT INTO scheduled_posts (schedule_id, ordinal, content, is_enabled, last_posted_at, post_count, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                schedule_id,
+                post.ordinal,
+                post.content,
+                post.is_enabled,
+                post.last_posted_at,
+                post.post_count,
+                post.created_at,
+                now
+            ],
+        )
+        .map_err(|e| anyhow!(e))?;
+    }
+
+    Ok(schedule_id)
 }
 
+/// Records that the post at `current_index` went out and moves the cursor to
+/// the next enabled post, wrapping around at the end of the list.
 pub fn update_post_index(conn: &Connection, account_id: i64, now: &str) -> Result<()> {
-    let (current_index, content_list_json): (i32, Option<String>) = conn
+    let (schedule_id, current_index): (i64, i32) = conn
         .query_row(
-            "SELECT current_index, content_list FROM scheduled_tweets WHERE account_id = ? AND is_active = 1",
+            "SELECT id, current_index FROM scheduled_tweets WHERE account_id = ? AND is_active = 1",
             params![account_id],
-            |row| Ok((row.get(0)?, row.get(1)?)),
+            |row| Ok((row.get(0)?, row.get::<_, Option<i32>>(1)?.unwrap_or(0))),
         )
-        .map_err(|e| anyhow!("インデックス取得エラー: {}", e))?;
-
-    if let Some(content_list_str) = content_list_json {
-        let content_list: Vec<String> = serde_json::from_str(&content_list_str)
-            .map_err(|e| anyhow!("JSON解析エラー: {}", e))?;
-
-        if !content_list.is_empty() {
-            let next_index = if current_index + 1 >= content_list.len() as i32 {
-                0
-            } else {
-                current_index + 1
-            };
-
-            conn.execute(
-                "UPDATE scheduled_tweets SET current_index = ?, updated_at = ? WHERE account_id = ? AND is_active = 1",
-                params![next_index, now, account_id],
-            )
-            .map_err(|e| anyhow!("インデックス更新エラー: {}", e))?;
-
-            println!(
-                "Updated post index for account {}: {} -> {}",
-                account_id, current_index, next_index
-            );
-        }
+        .map_err(|e| anyhow!(e))?;
+
+    let post_ids = fetch_enabled_post_ids(conn, schedule_id)?;
+    if post_ids.is_empty() {
+        return Ok(());
     }
 
+    let current = current_index.max(0) as usize % post_ids.len();
+    let next_index = (current + 1) % post_ids.len();
+
+    conn.execute(
+        "UPDATE scheduled_posts SET last_posted_at = ?, post_count = post_count + 1, updated_at = ? WHERE id = ?",
+        params![now, now, post_ids[current]],
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    conn.execute(
+        "UPDATE scheduled_tweets SET current_index = ?, updated_at = ? WHERE id = ?",
+        params![next_index as i32, now, schedule_id],
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    println!(
+        "Updated post index for account {}: {} -> {}",
+        account_id, current, next_index
+    );
+
+    Ok(())
+}
+
+/// `(account_id, schedule_id, current_index)` of every active schedule.
+pub fn fetch_schedule_indices(conn: &Connection) -> Result<Vec<(i64, i64, i64)>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT account_id, id, COALESCE(current_index, 0) FROM scheduled_tweets
+             WHERE is_active = 1",
+        )
+        .map_err(|e| anyhow!(e))?;
+
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .map_err(|e| anyhow!(e))?;
+
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+pub fn set_schedule_index(
+    conn: &Connection,
+    schedule_id: i64,
+    current_index: i64,
+    now: &str,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE scheduled_tweets SET current_index = ?, updated_at = ? WHERE id = ?",
+        params![current_index, now, schedule_id],
+    )
+    .map_err(|e| anyhow!(e))?;
+
     Ok(())
 }
 
@@ -117,7 +224,7 @@ pub fn fetch_scheduled_tweets(
     conn: &Connection,
     account_id: Option<i64>,
 ) -> Result<Vec<ScheduledTweet>> {
-    let query = "SELECT id, account_id, content, content_list, current_index, scheduled_times, is_active, created_at, updated_at \
+    let query = "SELECT id, account_id, content, current_index, scheduled_times, is_active, created_at, updated_at \
                  FROM scheduled_tweets WHERE ";
 
     let mut stmt;
@@ -141,18 +248,212 @@ pub fn fetch_scheduled_tweets(
         }
     };
 
+    let mut tweets = Vec::new();
+    for tweet in rows {
+        let mut tweet = tweet?;
+        if let Some(schedule_id) = tweet.id {
+            tweet.content_list = Some(enabled_content_list(conn, schedule_id)?);
+        }
+        tweets.push(tweet);
+    }
+
+    Ok(tweets)
+}
+
+/// JSON array of a schedule's enabled posts in ordinal order, the format the
+/// UI and the Actions config use for `content_list`.
+pub fn enabled_content_list(conn: &Connection, schedule_id: i64) -> Result<String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT content FROM scheduled_posts
+             WHERE schedule_id = ? AND is_enabled = 1
+             ORDER BY ordinal, id",
+        )
+        .map_err(|e| anyhow!(e))?;
+
+    let contents = stmt
+        .query_map(params![schedule_id], |row| row.get::<_, String>(0))
+        .map_err(|e| anyhow!(e))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(serde_json::to_string(&contents)?)
+}
+
+pub fn fetch_scheduled_posts(conn: &Connection, schedule_id: i64) -> Result<Vec<ScheduledPost>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, schedule_id, ordinal, content, is_enabled, last_posted_at, post_count, created_at, updated_at
+             FROM scheduled_posts
+             WHERE schedule_id = ?
+             ORDER BY ordinal, id",
+        )
+        .map_err(|e| anyhow!(e))?;
+
+    let rows = stmt
+        .query_map(params![schedule_id], row_to_scheduled_post)
+        .map_err(|e| anyhow!(e))?;
+
     Ok(rows.collect::<Result<Vec<_>, _>>()?)
 }
 
+/// Appends a post after the schedule's current last ordinal.
+pub fn add_scheduled_post(
+    conn: &Connection,
+    schedule_id: i64,
+    content: &str,
+    now: &str,
+) -> Result<i64> {
+    let next_ordinal: i32 = conn
+        .query_row(
+            "SELECT COALESCE(MAX(ordinal) + 1, 0) FROM scheduled_posts WHERE schedule_id = ?",
+            params![schedule_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| anyhow!(e))?;
+
+    conn.execute(
+        "INSERT INTO scheduled_posts (schedule_id, ordinal, content, is_enabled, post_count, created_at, updated_at)
+         VALUES (?, ?, ?, 1, 0, ?, ?)",
+        params![schedule_id, next_ordinal, content, now, now],
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn update_scheduled_post_content(
+    conn: &Connection,
+    post_id: i64,
+    content: &str,
+    now: &str,
+) -> Result<()> {
+    let updated = conn
+        .execute(
+            "UPDATE scheduled_posts SET content = ?, updated_at = ? WHERE id = ?",
+            params![content, now, post_id],
+        )
+        .map_err(|e| anyhow!(e))?;
+
+    if updated == 0 {
+        return Err(anyhow!(AppError::NotFound(
+            Message::new("schedule.post_not_found").with("id", post_id)
+        )));
+    }
+    Ok(())
+}
+
+pub fn set_scheduled_post_enabled(
+    conn: &Connection,
+    post_id: i64,
+    is_enabled: bool,
+    now: &str,
+) -> Result<()> {
+    let updated = conn
+        .execute(
+            "UPDATE scheduled_posts SET is_enabled = ?, updated_at = ? WHERE id = ?",
+            params![is_enabled, now, post_id],
+        )
+        .map_err(|e| anyhow!(e))?;
+
+    if updated == 0 {
+        return Err(anyhow!(AppError::NotFound(
+            Message::new("schedule.post_not_found").with("id", post_id)
+        )));
+    }
+    Ok(())
+}
+
+/// Renumbers a schedule's posts to follow `post_ids`, which must list every
+/// post of the schedule exactly once.
+pub fn reorder_scheduled_posts(
+    conn: &Connection,
+    schedule_id: i64,
+    post_ids: &[i64],
+    now: &str,
+) -> Result<()> {
+    let mut existing: Vec<i64> = fetch_scheduled_posts(conn, schedule_id)?
+        .into_iter()
+        .filter_map(|post| post.id)
+        .collect();
+    let mut requested = post_ids.to_vec();
+    existing.sort_unstable();
+    requested.sort_unstable();
+    if existing != requested {
+        return Err(anyhow!(AppError::Validation(
+            Message::new("schedule.reorder_mismatch").with("schedule_id", schedule_id)
+        )));
+    }
+
+    let tx = conn.unchecked_transaction().map_err(|e| anyhow!(e))?;
+    for (ordinal, post_id) in post_ids.iter().enumerate() {
+        tx.execute(
+            "UPDATE scheduled_posts SET ordinal = ?, updated_at = ? WHERE id = ?",
+            params![ordinal as i32, now, post_id],
+        )
+        .map_err(|e| anyhow!(e))?;
+    }
+    tx.commit().map_err(|e| anyhow!(e))?;
+
+    Ok(())
+}
+
+fn insert_scheduled_posts(
+    conn: &Connection,
+    schedule_id: i64,
+    contents: &[String],
+    now: &str,
+) -> Result<()> {
+    for (ordinal, content) in contents.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO scheduled_posts (schedule_id, ordinal, content, is_enabled, post_count, created_at, updated_at)
+             VALUES (?, ?, ?, 1, 0, ?, ?)",
+            params![schedule_id, ordinal as i32, content, now, now],
+        )
+        .map_err(|e| anyhow!(e))?;
+    }
+    Ok(())
+}
+
+fn fetch_enabled_post_ids(conn: &Connection, schedule_id: i64) -> Result<Vec<i64>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id FROM scheduled_posts
+             WHERE schedule_id = ? AND is_enabled = 1
+             ORDER BY ordinal, id",
+        )
+        .map_err(|e| anyhow!(e))?;
+
+    let ids = stmt
+        .query_map(params![schedule_id], |row| row.get(0))
+        .map_err(|e| anyhow!(e))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ids)
+}
+
 fn row_to_scheduled_tweet(row: &Row<'_>) -> rusqlite::Result<ScheduledTweet> {
     Ok(ScheduledTweet {
         id: row.get(0)?,
         account_id: row.get(1)?,
         content: row.get(2)?,
-        content_list: row.get(3)?,
-        current_index: row.get(4)?,
-        scheduled_times: row.get(5)?,
-        is_active: row.get(6)?,
+        content_list: None,
+        current_index: row.get(3)?,
+        scheduled_times: row.get(4)?,
+        is_active: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+fn row_to_scheduled_post(row: &Row<'_>) -> rusqlite::Result<ScheduledPost> {
+    Ok(ScheduledPost {
+        id: row.get(0)?,
+        schedule_id: row.get(1)?,
+        ordinal: row.get(2)?,
+        content: row.get(3)?,
+        is_enabled: row.get(4)?,
+        last_posted_at: row.get(5)?,
+        post_count: row.get(6)?,
         created_at: row.get(7)?,
         updated_at: row.get(8)?,
     })
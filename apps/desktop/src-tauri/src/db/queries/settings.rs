@@ -0,0 +1,53 @@
+use anyhow::{anyhow, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::i18n::Locale;
+use crate::models::RetryPolicy;
+
+const LOCALE_KEY: &str = "locale";
+const RETRY_POLICY_KEY: &str = "retry_policy";
+
+pub fn get_app_setting(conn: &Connection, key: &str) -> Result<Option<String>> {
+    conn.query_row(
+        "SELECT value FROM app_settings WHERE key = ?",
+        [key],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| anyhow!(e))
+}
+
+pub fn set_app_setting(conn: &Connection, key: &str, value: &str, now: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO app_settings (key, value, updated_at) VALUES (?, ?, ?)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        params![key, value, now],
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    Ok(())
+}
+
+/// Reads the UI locale, falling back to the default for a missing or
+/// unrecognised value.
+pub fn fetch_locale(conn: &Connection) -> Result<Locale> {
+    Ok(get_app_setting(conn, LOCALE_KEY)?
+        .and_then(|code| Locale::from_code(&code))
+        .unwrap_or_default())
+}
+
+pub fn save_locale(conn: &Connection, locale: Locale, now: &str) -> Result<()> {
+    set_app_setting(conn, LOCALE_KEY, locale.code(), now)
+}
+
+/// Reads the post retry policy; a missing or unreadable value yields the
+/// defaults, and missing fields take their default.
+pub fn fetch_retry_policy(conn: &Connection) -> Result<RetryPolicy> {
+    Ok(get_app_setting(conn, RETRY_POLICY_KEY)?
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default())
+}
+
+pub fn save_retry_policy(conn: &Connection, policy: &RetryPolicy, now: &str) -> Result<()> {
+    set_app_setting(conn, RETRY_POLICY_KEY, &serde_json::to_string(policy)?, now)
+}
@@ -0,0 +1,238 @@
+use std::fmt;
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::db::pool::PoolError;
+use crate::i18n::Message;
+use crate::services::exporter::scanner::SecretLeakError;
+use crate::services::twitter::TwitterErrorCode;
+
+/// Error returned by every Tauri command.
+///
+/// Serializes to `{ kind, message, details }`; the frontend branches on
+/// `kind`. `message` is rendered from the catalog in the app's locale when
+/// the error crosses the command boundary.
+#[derive(Debug, Error)]
+pub enum AppError {
+    Validation(Message),
+    NotFound(Message),
+    Database(Message),
+    /// A failed API response; `message` is the problem body's explanation.
+    TwitterApi {
+        status: u16,
+        code: TwitterErrorCode,
+        message: String,
+    },
+    /// A rate limit is exhausted; `retry_after` is in seconds and
+    /// `reset_at` is Unix time.
+    RateLimited {
+        endpoint: String,
+        limit: Option<i64>,
+        reset_at: i64,
+        retry_after: i64,
+    },
+    /// The account's monthly post budget for its tier is used up.
+    QuotaExceeded {
+        api_type: String,
+        month: String,
+        used: i64,
+        budget: i64,
+    },
+    Network(Message),
+    Io(Message),
+    SecretLeak(#[from] SecretLeakError),
+    Internal(Message),
+}
+
+impl AppError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::Validation(_) => "validation",
+            AppError::NotFound(_) => "not_found",
+            AppError::Database(_) => "database",
+            AppError::TwitterApi { .. } => "twitter_api",
+            AppError::RateLimited { .. } => "rate_limited",
+            AppError::QuotaExceeded { .. } => "quota_exceeded",
+            AppError::Network(_) => "network",
+            AppError::Io(_) => "io",
+            AppError::SecretLeak(_) => "secret_leak",
+            AppError::Internal(_) => "internal",
+        }
+    }
+
+    /// Whether the same request may succeed if sent again unchanged:
+    /// connection failures and 5xx responses. Never true for a 4xx, so a
+    /// retry cannot double-post something the API rejected.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            AppError::Network(_)
+                | AppError::TwitterApi {
+                    code: TwitterErrorCode::ServerError,
+                    ..
+                }
+        )
+    }
+
+    /// The classification stored with failure logs: the API error code for
+    /// Twitter errors, the kind otherwise.
+    pub fn log_code(&self) -> &'static str {
+        match self {
+            AppError::TwitterApi { code, .. } => code.as_str(),
+            other => other.kind(),
+        }
+    }
+
+    /// The catalog message describing this error, for callers that store it
+    /// and render it later (execution logs).
+    pub fn message(&self) -> Message {
+        match self {
+            AppError::Validation(message)
+            | AppError::NotFound(message)
+            | AppError::Database(message)
+            | AppError::Network(message)
+            | AppError::Io(message)
+            | AppError::Internal(message) => message.clone(),
+            AppError::TwitterApi {
+                status,
+                code,
+                message,
+            } => {
+                let id = match code {
+                    TwitterErrorCode::Other => "error.twitter_api".to_string(),
+                    code => format!("twitter_error.{}", code.as_str()),
+                };
+                Message::new(&id)
+                    .with("status", status)
+                    .with("message", message)
+            }
+            AppError::RateLimited {
+                endpoint,
+                retry_after,
+                ..
+            } => Message::new("error.rate_limited")
+                .with("endpoint", endpoint)
+                .with("retry_after", retry_after),
+            AppError::QuotaExceeded {
+                api_type, budget, ..
+            } => Message::new("error.quota_exceeded")
+                .with("api_type", api_type)
+                .with("budget", budget),
+            AppError::SecretLeak(leak) => Message::new("error.secret_leak")
+                .with("count", leak.findings.len())
+                .with(
+                    "paths",
+                    leak.findings
+                        .iter()
+                        .map(|finding| finding.path.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+        }
+    }
+
+    fn details(&self) -> Value {
+        match self {
+            AppError::TwitterApi { status, code, .. } => json!({ "status": status, "code": code }),
+            AppError::RateLimited {
+                endpoint,
+                limit,
+                reset_at,
+                retry_after,
+            } => json!({
+                "endpoint": endpoint,
+                "limit": limit,
+                "reset_at": reset_at,
+                "retry_after": retry_after,
+            }),
+            AppError::QuotaExceeded {
+                api_type,
+                month,
+                used,
+                budget,
+            } => json!({
+                "api_type": api_type,
+                "month": month,
+                "used": used,
+                "budget": budget,
+            }),
+            AppError::SecretLeak(leak) => json!({ "findings": leak.findings }),
+            _ => Value::Null,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.message().fmt(f)
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("details", &self.details())?;
+        state.end()
+    }
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(error: rusqlite::Error) -> Self {
+        match error {
+            rusqlite::Error::QueryReturnedNoRows => {
+                AppError::NotFound(Message::new("error.record_not_found"))
+            }
+            other => AppError::Database(Message::new("error.database").with("detail", other)),
+        }
+    }
+}
+
+impl From<PoolError> for AppError {
+    fn from(error: PoolError) -> Self {
+        AppError::Database(Message::new("error.database").with("detail", error))
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(error: std::io::Error) -> Self {
+        AppError::Io(Message::new("error.io").with("detail", error))
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(error: serde_json::Error) -> Self {
+        AppError::Internal(Message::new("error.json").with("detail", error))
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(error: reqwest::Error) -> Self {
+        AppError::Network(Message::new("error.network").with("detail", error))
+    }
+}
+
+/// The db layer returns `anyhow::Error`; recover the typed error when one
+/// was wrapped, and treat everything else as a database failure.
+impl From<anyhow::Error> for AppError {
+    fn from(error: anyhow::Error) -> Self {
+        let error = match error.downcast::<AppError>() {
+            Ok(app_error) => return app_error,
+            Err(error) => error,
+        };
+        let error = match error.downcast::<rusqlite::Error>() {
+            Ok(sqlite_error) => return sqlite_error.into(),
+            Err(error) => error,
+        };
+        match error.downcast::<std::io::Error>() {
+            Ok(io_error) => io_error.into(),
+            Err(error) => AppError::Database(
+                Message::new("error.database").with("detail", format!("{:#}", error)),
+            ),
+        }
+    }
+}
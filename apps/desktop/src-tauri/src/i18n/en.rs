@@ -0,0 +1,106 @@
+pub(super) const MESSAGES: &[(&str, &str)] = &[
+    // Errors surfaced through AppError
+    ("error.database", "Database error: {detail}"),
+    ("error.record_not_found", "Record not found"),
+    ("error.network", "Network error: {detail}"),
+    ("error.io", "File error: {detail}"),
+    ("error.internal", "Internal error: {detail}"),
+    ("error.json", "JSON error: {detail}"),
+    ("error.twitter_api", "Twitter API error ({status}): {message}"),
+    ("error.rate_limited", "Rate limit reached for {endpoint}. Retry in {retry_after} seconds"),
+    ("error.quota_exceeded", "This month's post limit for the {api_type} tier ({budget}) has been reached"),
+    (
+        "error.secret_leak",
+        "Export aborted: {count} credential-like value(s) found at {paths}",
+    ),
+    // Twitter API errors by classified code
+    ("twitter_error.unauthorized", "Authentication failed; check the API key and access token ({status}): {message}"),
+    ("twitter_error.forbidden", "This action is not permitted ({status}): {message}"),
+    ("twitter_error.duplicate_content", "The same tweet cannot be posted twice in a row: {message}"),
+    ("twitter_error.account_suspended", "The account is suspended: {message}"),
+    ("twitter_error.rate_limited", "Rate limit reached: {message}"),
+    ("twitter_error.usage_capped", "The Twitter API monthly usage cap has been reached: {message}"),
+    ("twitter_error.invalid_request", "Invalid request ({status}): {message}"),
+    ("twitter_error.server_error", "The Twitter API is temporarily unavailable ({status}): {message}"),
+    // Bot accounts
+    ("bot.account_name_required", "Account name is required"),
+    ("bot.api_key_required", "API key is required"),
+    ("bot.api_key_secret_required", "API key secret is required"),
+    ("bot.access_token_required", "Access token is required"),
+    (
+        "bot.access_token_secret_required",
+        "Access token secret is required",
+    ),
+    ("bot.unknown_api_type", "Unsupported API type: {api_type}"),
+    ("bot.credentials_verified", "Credentials verified (@{username})"),
+    (
+        "bot.credentials_name_mismatch",
+        "Credentials are valid, but they belong to @{username} rather than {account_name}",
+    ),
+    ("bot.credentials_invalid", "Credentials are invalid: {error}"),
+    ("bot.credentials_check_failed", "Could not verify credentials: {error}"),
+    // Reply settings
+    ("reply.content_empty", "Reply content is empty"),
+    ("reply.no_targets", "No target bots are selected"),
+    ("reply.reply_bot_not_found", "Reply bot ID {id} does not exist"),
+    ("reply.target_bot_not_found", "Target bot ID {id} does not exist"),
+    // Scheduled posts
+    ("schedule.post_content_empty", "Post content is empty"),
+    ("schedule.post_not_found", "Post ID {id} does not exist"),
+    (
+        "schedule.reorder_mismatch",
+        "The posts to reorder do not match the posts of schedule {schedule_id}",
+    ),
+    // Backups
+    ("backup.invalid_name", "Invalid backup file name: {file_name}"),
+    ("backup.not_found", "Backup not found: {file_name}"),
+    // Export
+    ("export.create_directory_failed", "Failed to create {path}: {detail}"),
+    ("export.write_failed", "Failed to write {path}: {detail}"),
+    ("export.read_failed", "Failed to read {path}: {detail}"),
+    (
+        "export.invalid_system_state",
+        "{path} is not a valid system state file: {detail}. Fix or remove it before exporting.",
+    ),
+    ("export.schema_violation", "Refusing to write {path}: it does not match the Actions config schema: {detail}"),
+    ("export.system_state_not_found", "No system state file at {path}"),
+    (
+        "export.plaintext_tracked",
+        "Refusing to write plaintext credentials to {path}: the path is tracked by git. Export with secret references instead, or explicitly allow exporting to a tracked path.",
+    ),
+    // Import
+    ("import.invalid_json", "{path} is not valid JSON: {detail}"),
+    ("import.unknown_format", "{path} is not an export file of this app"),
+    ("import.invalid_version", "{path} has an unreadable version: {version}"),
+    (
+        "import.newer_version",
+        "{path} was written by a newer version of the app ({format} v{version}; this version reads up to v{supported}). Update the app to import it.",
+    ),
+    ("import.invalid_content", "{path} could not be read as {format} v{version}: {detail}"),
+    // API quota
+    ("quota.post_warning", "Monthly post usage is nearing the limit ({api_type}: {used}/{budget})"),
+    // Settings
+    ("settings.unsupported_locale", "Unsupported language: {locale}"),
+    ("settings.retry_attempts_out_of_range", "Attempts must be between 1 and {max}"),
+    ("settings.retry_backoff_inverted", "The initial backoff must not exceed the maximum backoff"),
+    ("settings.retry_deadline_out_of_range", "The retry deadline must be between 1 and {max} seconds"),
+    // Tweets
+    ("tweet.posted", "Tweet posted successfully"),
+    ("tweet.post_failed", "Failed to post tweet: {error}"),
+    ("tweet.test_failed", "Posting failed: {error}"),
+    ("tweet.retry_user_found", "Looked up @{username} to check for an earlier attempt"),
+    (
+        "tweet.retry_user_lookup_failed",
+        "Could not look up the account to check for an earlier attempt: {error}",
+    ),
+    ("tweet.retry_timeline_checked", "Checked {count} recent tweet(s) for an earlier attempt"),
+    (
+        "tweet.retry_timeline_check_failed",
+        "Could not check recent tweets for an earlier attempt: {error}",
+    ),
+    ("twitter.request_failed", "Failed to send request: {detail}"),
+    ("twitter.response_failed", "Failed to read response: {detail}"),
+    ("twitter.missing_tweet_id", "The response did not include a tweet ID"),
+    ("twitter.missing_user", "The response did not include the user"),
+    ("twitter.signing_failed", "Failed to initialize HMAC: {detail}"),
+];
@@ -0,0 +1,106 @@
+pub(super) const MESSAGES: &[(&str, &str)] = &[
+    // Errors surfaced through AppError
+    ("error.database", "データベースエラー: {detail}"),
+    ("error.record_not_found", "データが見つかりません"),
+    ("error.network", "通信エラー: {detail}"),
+    ("error.io", "ファイルエラー: {detail}"),
+    ("error.internal", "内部エラー: {detail}"),
+    ("error.json", "JSONエラー: {detail}"),
+    ("error.twitter_api", "Twitter API エラー ({status}): {message}"),
+    ("error.rate_limited", "{endpoint} のレート制限に達しました。{retry_after}秒後に再試行してください"),
+    ("error.quota_exceeded", "{api_type} プランの今月の投稿上限 ({budget}件) に達しています"),
+    (
+        "error.secret_leak",
+        "認証情報らしき値が {count} 件見つかったためエクスポートを中止しました: {paths}",
+    ),
+    // Twitter API errors by classified code
+    ("twitter_error.unauthorized", "認証に失敗しました。API Key と Access Token を確認してください ({status}): {message}"),
+    ("twitter_error.forbidden", "この操作は許可されていません ({status}): {message}"),
+    ("twitter_error.duplicate_content", "同じ内容のツイートは続けて投稿できません: {message}"),
+    ("twitter_error.account_suspended", "アカウントが凍結されています: {message}"),
+    ("twitter_error.rate_limited", "レート制限に達しました: {message}"),
+    ("twitter_error.usage_capped", "Twitter API の月間利用上限に達しました: {message}"),
+    ("twitter_error.invalid_request", "リクエストが不正です ({status}): {message}"),
+    ("twitter_error.server_error", "Twitter API が一時的に利用できません ({status}): {message}"),
+    // Bot accounts
+    ("bot.account_name_required", "アカウント名を入力してください"),
+    ("bot.api_key_required", "API Key を入力してください"),
+    ("bot.api_key_secret_required", "API Key Secret を入力してください"),
+    ("bot.access_token_required", "Access Token を入力してください"),
+    (
+        "bot.access_token_secret_required",
+        "Access Token Secret を入力してください",
+    ),
+    ("bot.unknown_api_type", "API種別 {api_type} には対応していません"),
+    ("bot.credentials_verified", "認証情報を確認しました (@{username})"),
+    (
+        "bot.credentials_name_mismatch",
+        "認証情報は有効ですが、アカウント名 {account_name} と実際のユーザー名 @{username} が一致しません",
+    ),
+    ("bot.credentials_invalid", "認証情報が無効です: {error}"),
+    ("bot.credentials_check_failed", "認証情報を確認できませんでした: {error}"),
+    // Reply settings
+    ("reply.content_empty", "返信内容が空です"),
+    ("reply.no_targets", "監視対象Botが選択されていません"),
+    ("reply.reply_bot_not_found", "返信Bot ID {id} が存在しません"),
+    ("reply.target_bot_not_found", "監視対象Bot ID {id} が存在しません"),
+    // Scheduled posts
+    ("schedule.post_content_empty", "投稿内容が空です"),
+    ("schedule.post_not_found", "投稿 ID {id} が存在しません"),
+    (
+        "schedule.reorder_mismatch",
+        "並び替え対象の投稿がスケジュール {schedule_id} の投稿と一致しません",
+    ),
+    // Backups
+    ("backup.invalid_name", "バックアップファイル名が不正です: {file_name}"),
+    ("backup.not_found", "バックアップが見つかりません: {file_name}"),
+    // Export
+    ("export.create_directory_failed", "{path} の作成に失敗しました: {detail}"),
+    ("export.write_failed", "{path} の書き込みに失敗しました: {detail}"),
+    ("export.read_failed", "{path} の読み込みに失敗しました: {detail}"),
+    (
+        "export.invalid_system_state",
+        "{path} はシステム状態ファイルとして読み込めません: {detail}。修正または削除してからエクスポートしてください。",
+    ),
+    ("export.schema_violation", "{path} が Actions 設定のスキーマに適合しないため書き出しを中止しました: {detail}"),
+    ("export.system_state_not_found", "{path} にシステム状態ファイルがありません"),
+    (
+        "export.plaintext_tracked",
+        "{path} は git で管理されているため、平文の認証情報は書き出せません。シークレット参照でエクスポートするか、git 管理下への書き出しを明示的に許可してください。",
+    ),
+    // Import
+    ("import.invalid_json", "{path} はJSONとして読み込めません: {detail}"),
+    ("import.unknown_format", "{path} はこのアプリのエクスポートファイルではありません"),
+    ("import.invalid_version", "{path} のバージョン {version} を読み取れません"),
+    (
+        "import.newer_version",
+        "{path} は新しいバージョンのアプリで書き出されています ({format} v{version}、このアプリは v{supported} まで対応)。アプリを更新してから読み込んでください。",
+    ),
+    ("import.invalid_content", "{path} を {format} v{version} として読み込めません: {detail}"),
+    // API quota
+    ("quota.post_warning", "今月の投稿数が上限に近づいています ({api_type}: {used}/{budget}件)"),
+    // Settings
+    ("settings.unsupported_locale", "未対応の言語です: {locale}"),
+    ("settings.retry_attempts_out_of_range", "試行回数は 1〜{max} 回で指定してください"),
+    ("settings.retry_backoff_inverted", "初回の待ち時間は最大待ち時間以下にしてください"),
+    ("settings.retry_deadline_out_of_range", "再試行の期限は 1〜{max} 秒で指定してください"),
+    // Tweets
+    ("tweet.posted", "ツイート投稿が成功しました"),
+    ("tweet.post_failed", "ツイート投稿に失敗しました: {error}"),
+    ("tweet.test_failed", "投稿に失敗しました: {error}"),
+    ("tweet.retry_user_found", "再試行前の投稿確認のため @{username} を取得しました"),
+    (
+        "tweet.retry_user_lookup_failed",
+        "再試行前の投稿確認のためのアカウント取得に失敗しました: {error}",
+    ),
+    ("tweet.retry_timeline_checked", "再試行前に直近のツイート {count} 件を確認しました"),
+    (
+        "tweet.retry_timeline_check_failed",
+        "再試行前の直近ツイートの確認に失敗しました: {error}",
+    ),
+    ("twitter.request_failed", "リクエスト送信エラー: {detail}"),
+    ("twitter.response_failed", "レスポンス取得エラー: {detail}"),
+    ("twitter.missing_tweet_id", "ツイートIDを取得できませんでした"),
+    ("twitter.missing_user", "ユーザー情報を取得できませんでした"),
+    ("twitter.signing_failed", "HMAC初期化エラー: {detail}"),
+];
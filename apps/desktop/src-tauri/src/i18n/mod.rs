@@ -0,0 +1,132 @@
+mod en;
+mod ja;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+/// Languages the backend can render messages in. Japanese is the default and
+/// the fallback for ids missing from another bundle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    Ja,
+    En,
+}
+
+impl Locale {
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::Ja => "ja",
+            Locale::En => "en",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "ja" => Some(Locale::Ja),
+            "en" => Some(Locale::En),
+            _ => None,
+        }
+    }
+
+    fn bundle(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::Ja => ja::MESSAGES,
+            Locale::En => en::MESSAGES,
+        }
+    }
+}
+
+static CURRENT_LOCALE: AtomicU8 = AtomicU8::new(0);
+
+/// The locale stored in `app_settings`, cached for code that has no
+/// connection at hand (error `Display`, log rendering).
+pub fn current_locale() -> Locale {
+    match CURRENT_LOCALE.load(Ordering::Relaxed) {
+        1 => Locale::En,
+        _ => Locale::Ja,
+    }
+}
+
+pub fn set_current_locale(locale: Locale) {
+    let value = match locale {
+        Locale::Ja => 0,
+        Locale::En => 1,
+    };
+    CURRENT_LOCALE.store(value, Ordering::Relaxed);
+}
+
+/// A parameter value: plain text, or another catalog message rendered in the
+/// same locale as its parent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MessageParam {
+    Text(String),
+    Message(Message),
+}
+
+/// A catalog message id plus named parameters. Rendering is deferred so the
+/// same message can be shown in whichever language the viewer uses.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Message {
+    pub id: String,
+    #[serde(default)]
+    pub params: BTreeMap<String, MessageParam>,
+}
+
+impl Message {
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            params: BTreeMap::new(),
+        }
+    }
+
+    pub fn with(mut self, name: &str, value: impl ToString) -> Self {
+        self.params
+            .insert(name.to_string(), MessageParam::Text(value.to_string()));
+        self
+    }
+
+    pub fn with_message(mut self, name: &str, value: Message) -> Self {
+        self.params
+            .insert(name.to_string(), MessageParam::Message(value));
+        self
+    }
+
+    /// Fills `{name}` placeholders in the template for `locale`. Unknown ids
+    /// render as the id itself so a missing entry is visible, not fatal.
+    pub fn render(&self, locale: Locale) -> String {
+        let template = lookup(locale, &self.id)
+            .or_else(|| lookup(Locale::Ja, &self.id))
+            .unwrap_or(&self.id);
+
+        let mut text = template.to_string();
+        for (name, value) in &self.params {
+            let value = match value {
+                MessageParam::Text(value) => value.clone(),
+                MessageParam::Message(message) => message.render(locale),
+            };
+            text = text.replace(&format!("{{{}}}", name), &value);
+        }
+        text
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(current_locale()))
+    }
+}
+
+fn lookup(locale: Locale, id: &str) -> Option<&'static str> {
+    locale
+        .bundle()
+        .iter()
+        .find(|(key, _)| *key == id)
+        .map(|(_, template)| *template)
+}
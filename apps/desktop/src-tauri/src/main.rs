@@ -5,16 +5,47 @@
 
 mod commands;
 mod db;
+mod error;
+mod i18n;
 mod models;
 mod services;
 mod state;
+#[cfg(test)]
+mod test_support;
 mod utils;
 
-use crate::db::init_database;
+use std::sync::Arc;
+
+use crate::db::{data_directory, dry_run_migrations, init_database, spawn_daily_backups};
+use crate::services::TwitterClient;
 use crate::state::AppState;
+use crate::utils::vault::CredentialVault;
 
 fn main() {
-    let db_conn = match init_database() {
+    if std::env::args().any(|arg| arg == "--dry-run") {
+        match dry_run_migrations() {
+            Ok(pending) if pending.is_empty() => println!("Database schema is up to date"),
+            Ok(pending) => {
+                println!("{} pending migration(s):", pending.len());
+                for migration in pending {
+                    println!("  {:>3}  {}", migration.version, migration.name);
+                }
+            }
+            Err(e) => eprintln!("Failed to inspect database migrations: {}", e),
+        }
+        return;
+    }
+
+    let vault = match data_directory().and_then(|dir| CredentialVault::open(&dir)) {
+        Ok(vault) => vault,
+        Err(e) => {
+            eprintln!("Failed to open credential vault: {}", e);
+            return;
+        }
+    };
+    println!("Credential vault key source: {:?}", vault.source());
+
+    let db_conn = match init_database(&vault) {
         Ok(conn) => conn,
         Err(e) => {
             eprintln!("Failed to initialize database: {}", e);
@@ -22,32 +53,59 @@ fn main() {
         }
     };
 
+    let twitter = match TwitterClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to create Twitter API client: {}", e);
+            return;
+        }
+    };
+
+    spawn_daily_backups();
+
     tauri::Builder::default()
-        .manage(AppState::new(db_conn))
+        .manage(AppState::new(db_conn, vault, Arc::new(twitter)))
         .invoke_handler(tauri::generate_handler![
             commands::dashboard::get_dashboard_stats,
+            commands::dashboard::get_rate_limits,
+            commands::dashboard::get_quota_usage,
             commands::bots::get_bot_accounts,
             commands::bots::add_bot_account,
             commands::bots::update_bot_account,
             commands::bots::delete_bot_account,
+            commands::bots::verify_bot_credentials,
+            commands::bots::refresh_bot_profiles,
             commands::bots::get_bot_config,
             commands::bots::update_bot_config,
             commands::replies::save_reply_settings,
             commands::replies::get_reply_settings,
             commands::replies::delete_reply_settings,
             commands::replies::update_last_checked_tweet,
-            commands::replies::cleanup_orphaned_reply_settings_cmd,
             commands::schedules::save_scheduled_tweet_list,
             commands::schedules::save_scheduled_tweet,
             commands::schedules::update_post_index,
             commands::schedules::add_scheduled_tweet,
             commands::schedules::get_scheduled_tweets,
+            commands::schedules::get_scheduled_posts,
+            commands::schedules::add_scheduled_post,
+            commands::schedules::update_scheduled_post,
+            commands::schedules::set_scheduled_post_enabled,
+            commands::schedules::reorder_scheduled_posts,
             commands::logs::get_execution_logs,
             commands::logs::add_execution_log,
             commands::users::get_user_settings,
             commands::users::update_user_settings,
+            commands::settings::get_locale,
+            commands::settings::set_locale,
+            commands::settings::get_retry_policy,
+            commands::settings::set_retry_policy,
             commands::export::export_data,
             commands::export::export_github_config,
+            commands::export::import_system_state,
+            commands::export::import_data,
+            commands::backups::create_backup,
+            commands::backups::list_backups,
+            commands::backups::restore_backup,
             commands::tests::test_tweet
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupReason {
+    /// Requested from the settings screen.
+    Manual,
+    /// Taken at most once every 24 hours while the app is running.
+    Daily,
+    /// Taken before pending schema migrations are applied.
+    PreMigration,
+    /// Snapshot of the live database taken before a restore replaces it.
+    PreRestore,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupInfo {
+    pub file_name: String,
+    pub path: String,
+    pub reason: BackupReason,
+    pub created_at: String,
+    pub size_bytes: u64,
+}
+
+impl BackupReason {
+    pub fn slug(self) -> &'static str {
+        match self {
+            BackupReason::Manual => "manual",
+            BackupReason::Daily => "daily",
+            BackupReason::PreMigration => "pre_migration",
+            BackupReason::PreRestore => "pre_restore",
+        }
+    }
+
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        match slug {
+            "manual" => Some(BackupReason::Manual),
+            "daily" => Some(BackupReason::Daily),
+            "pre_migration" => Some(BackupReason::PreMigration),
+            "pre_restore" => Some(BackupReason::PreRestore),
+            _ => None,
+        }
+    }
+}
@@ -1,6 +1,7 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct BotAccount {
     pub id: Option<i64>,
     pub account_name: String,
@@ -10,12 +11,160 @@ pub struct BotAccount {
     pub access_token: String,
     pub access_token_secret: String,
     pub status: String,
+    /// Set by credential verification, never by the frontend. The user id
+    /// is stable; the handle and display name follow renames on Twitter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub twitter_user_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub twitter_username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub twitter_display_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_verified_at: Option<String>,
+    #[serde(default)]
+    pub credential_health: CredentialHealth,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<String>,
 }
 
+/// Outcome of the last credential check, stored as
+/// `bot_accounts.credential_health`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialHealth {
+    /// Never checked.
+    #[default]
+    Unknown,
+    Valid,
+    /// Rejected with a 401.
+    Invalid,
+    /// The check itself failed (network, 5xx, rate limit), so the
+    /// credentials may still be fine.
+    CheckFailed,
+}
+
+impl CredentialHealth {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CredentialHealth::Unknown => "unknown",
+            CredentialHealth::Valid => "valid",
+            CredentialHealth::Invalid => "invalid",
+            CredentialHealth::CheckFailed => "check_failed",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "valid" => CredentialHealth::Valid,
+            "invalid" => CredentialHealth::Invalid,
+            "check_failed" => CredentialHealth::CheckFailed,
+            _ => CredentialHealth::Unknown,
+        }
+    }
+}
+
+/// Listing view of a bot account that never carries the raw credentials.
+#[derive(Debug, Serialize)]
+pub struct BotAccountSummary {
+    pub id: i64,
+    pub account_name: String,
+    pub api_type: String,
+    pub api_key_masked: String,
+    pub api_key_secret_masked: String,
+    pub access_token_masked: String,
+    pub access_token_secret_masked: String,
+    pub status: String,
+    pub twitter_user_id: Option<String>,
+    pub twitter_username: Option<String>,
+    pub twitter_display_name: Option<String>,
+    pub last_verified_at: Option<String>,
+    pub credential_health: CredentialHealth,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+impl From<&BotAccount> for BotAccountSummary {
+    fn from(account: &BotAccount) -> Self {
+        Self {
+            id: account.id.unwrap_or_default(),
+            account_name: account.account_name.clone(),
+            api_type: account.api_type.clone(),
+            api_key_masked: mask_secret(&account.api_key),
+            api_key_secret_masked: mask_secret(&account.api_key_secret),
+            access_token_masked: mask_secret(&account.access_token),
+            access_token_secret_masked: mask_secret(&account.access_token_secret),
+            status: account.status.clone(),
+            twitter_user_id: account.twitter_user_id.clone(),
+            twitter_username: account.twitter_username.clone(),
+            twitter_display_name: account.twitter_display_name.clone(),
+            last_verified_at: account.last_verified_at.clone(),
+            credential_health: account.credential_health,
+            created_at: account.created_at.clone(),
+            updated_at: account.updated_at.clone(),
+        }
+    }
+}
+
+/// Outcome of checking an account's credentials against `GET /2/users/me`.
+#[derive(Debug, Serialize)]
+pub struct CredentialVerification {
+    pub account_id: i64,
+    /// The account's status afterwards, `invalid_credentials` if rejected.
+    pub status: String,
+    pub credential_health: CredentialHealth,
+    pub twitter_user_id: Option<String>,
+    pub twitter_username: Option<String>,
+    pub twitter_display_name: Option<String>,
+    /// The verified handle is not the one typed in as `account_name`.
+    pub name_mismatch: bool,
+    pub message: String,
+}
+
+/// Result of refreshing every account's profile, one entry per account.
+#[derive(Debug, Serialize)]
+pub struct ProfileRefreshReport {
+    pub verified: Vec<CredentialVerification>,
+    pub failed: Vec<ProfileRefreshFailure>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProfileRefreshFailure {
+    pub account_id: i64,
+    pub account_name: String,
+    pub message: String,
+}
+
+/// Update payload for a bot account. Secrets left as `None` (or blank) keep
+/// their stored value, so the UI never has to round-trip the originals.
+#[derive(Debug, Deserialize)]
+pub struct BotAccountUpdate {
+    pub id: i64,
+    pub account_name: String,
+    pub api_type: String,
+    #[serde(default)]
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub api_key_secret: Option<String>,
+    #[serde(default)]
+    pub access_token: Option<String>,
+    #[serde(default)]
+    pub access_token_secret: Option<String>,
+    pub status: String,
+}
+
+pub fn mask_secret(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.len() <= 4 {
+        return "****".to_string();
+    }
+    let visible: String = chars[chars.len() - 4..].iter().collect();
+    format!("****{}", visible)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BotConfig {
     pub id: Option<i64>,
@@ -34,10 +183,26 @@ pub struct ScheduledTweet {
     pub id: Option<i64>,
     pub account_id: i64,
     pub content: String,
+    /// JSON array of the enabled posts in order, assembled from
+    /// `scheduled_posts`.
     pub content_list: Option<String>,
+    /// Position of the next post within `content_list`.
     pub current_index: Option<i32>,
     pub scheduled_times: String,
     pub is_active: bool,
     pub created_at: String,
     pub updated_at: String,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScheduledPost {
+    pub id: Option<i64>,
+    pub schedule_id: i64,
+    pub ordinal: i32,
+    pub content: String,
+    pub is_enabled: bool,
+    pub last_posted_at: Option<String>,
+    pub post_count: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
@@ -0,0 +1,382 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{BotAccount, ExecutionLog, ReplySettings, ScheduledPost, ScheduledTweet, UserSettings};
+
+/// The kinds of file the app exports, named by the `format` field every
+/// export carries next to its `version`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    /// `export_data`: everything in the database, for backups and moving
+    /// between machines.
+    #[serde(rename = "tam-data-export")]
+    Data,
+    /// `export_github_config`: the `user-config.json` the workflows run from.
+    #[serde(rename = "tam-actions-config")]
+    ActionsConfig,
+}
+
+impl ExportFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExportFormat::Data => "tam-data-export",
+            ExportFormat::ActionsConfig => "tam-actions-config",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretMode {
+    /// Write `${{ secrets.BOT_<ID>_* }}` references and a manifest of required secrets.
+    #[default]
+    References,
+    /// Write decrypted credentials into the config file.
+    Plaintext,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanPolicy {
+    /// Refuse to write the file and report where the secrets were found.
+    #[default]
+    Abort,
+    /// Replace every detected secret with `[REDACTED]` and continue.
+    Redact,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GithubExportOptions {
+    #[serde(default)]
+    pub secret_mode: SecretMode,
+    #[serde(default)]
+    pub scan_policy: ScanPolicy,
+    /// Allow plaintext credentials to be written inside a git working tree.
+    #[serde(default)]
+    pub allow_tracked: bool,
+    /// Write whatever the secret scanner finds instead of aborting.
+    #[serde(default)]
+    pub ignore_scan: bool,
+}
+
+/// The account credentials are always written; the scan covers the rest of
+/// the file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DataExportOptions {
+    pub scan_policy: ScanPolicy,
+    /// Write detected credentials anyway instead of aborting.
+    pub force: bool,
+}
+
+/// The file `export_data` writes, in its current version.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DataExport {
+    pub format: ExportFormat,
+    pub version: u32,
+    pub exported_at: String,
+    #[serde(default)]
+    pub accounts: Vec<BotAccount>,
+    #[serde(default)]
+    pub scheduled_tweets: Vec<ScheduledTweet>,
+    #[serde(default)]
+    pub scheduled_posts: Vec<ScheduledPost>,
+    #[serde(default)]
+    pub reply_settings: Vec<ReplySettings>,
+    #[serde(default)]
+    pub logs: Vec<ExecutionLog>,
+    #[serde(default)]
+    pub user_settings: Option<UserSettings>,
+    /// Paths the scanner replaced with `[REDACTED]`; the values there are
+    /// gone, not literal.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redacted_paths: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DataExportReport {
+    pub path: String,
+    pub redacted_paths: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RequiredSecret {
+    pub name: String,
+    pub account_id: i64,
+    pub account_name: String,
+    pub field: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SecretsManifest {
+    pub generated_at: String,
+    pub config_path: String,
+    pub secrets: Vec<RequiredSecret>,
+}
+
+/// The GitHub Actions config the workers run from, `ActionsConfig` in
+/// `@tam/shared`. `user-config.json` is this without the progress fields,
+/// which go to `system-state.json`.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ActionsConfig {
+    /// Always `tam-actions-config`.
+    pub format: String,
+    /// The major version as a string, which is what the workers expect.
+    pub version: String,
+    pub bots: Vec<ActionsBot>,
+    pub reply_settings: Vec<ReplySettings>,
+    pub updated_at: String,
+}
+
+/// One active bot and its active schedule, if it has one.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ActionsBot {
+    pub account: BotAccount,
+    /// The enabled posts in order, as a JSON-encoded array of strings
+    /// rather than an array. Set when the bot has a schedule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_content_list: Option<String>,
+    /// Single post of bots without a schedule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_content: Option<String>,
+    /// Position of the next post within `scheduled_content_list`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(range(min = 0))]
+    pub current_index: Option<i64>,
+    pub scheduled_times: Option<String>,
+}
+
+/// `system-state.json`: the part of the Actions config the workflows advance
+/// and commit back. Field order follows what the workers write.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SystemState {
+    #[serde(default)]
+    pub bot_state: Vec<BotState>,
+    #[serde(default)]
+    pub reply_state: Vec<ReplyState>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BotState {
+    pub account_id: Option<i64>,
+    pub account_name: Option<String>,
+    /// Position of the next post within the bot's content list.
+    #[serde(default)]
+    pub current_index: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplyState {
+    pub id: Option<i64>,
+    pub reply_bot_id: Option<i64>,
+    /// JSON array of `"<target_bot_id>:<tweet_id>"` entries.
+    #[serde(default = "empty_json_array")]
+    pub last_checked_tweet_ids: String,
+}
+
+fn empty_json_array() -> String {
+    "[]".to_string()
+}
+
+/// What importing `system-state.json` changes, or changed once `applied`.
+#[derive(Debug, Serialize)]
+pub struct StateImportReport {
+    pub path: String,
+    pub applied: bool,
+    pub bot_changes: Vec<BotStateChange>,
+    pub reply_changes: Vec<ReplyStateChange>,
+    /// Entries whose account id and name point at different accounts (or
+    /// only one of them matches). They are left out of `bot_changes`.
+    pub conflicts: Vec<AccountConflict>,
+    /// Reply entries whose setting id and reply bot disagree. They are left
+    /// out of `reply_changes`.
+    pub reply_conflicts: Vec<ReplySettingConflict>,
+    /// Entries with nothing in the database to apply them to.
+    pub unknown: Vec<UnknownStateEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BotStateChange {
+    pub account_id: i64,
+    pub account_name: String,
+    pub schedule_id: i64,
+    pub current_index_before: i64,
+    pub current_index_after: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplyStateChange {
+    pub reply_setting_id: i64,
+    pub reply_bot_id: i64,
+    pub target_bot_id: i64,
+    pub last_checked_before: Option<String>,
+    pub last_checked_after: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccountConflict {
+    /// Id and name as written in the state file.
+    pub state_account_id: i64,
+    pub state_account_name: String,
+    /// Name of the account that has `state_account_id`, if any.
+    pub name_for_id: Option<String>,
+    /// Id of the account named `state_account_name`, if any.
+    pub id_for_name: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplySettingConflict {
+    /// Setting id and reply bot as written in the state file.
+    pub state_id: i64,
+    pub state_reply_bot_id: i64,
+    /// Reply bot of the setting that has `state_id`, if any.
+    pub reply_bot_for_id: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UnknownStateEntry {
+    /// No account has this id or name.
+    Account {
+        account_id: Option<i64>,
+        account_name: Option<String>,
+    },
+    /// The account has no active schedule to hold the index.
+    Schedule {
+        account_id: i64,
+        account_name: String,
+    },
+    /// No active reply setting has this id or reply bot.
+    ReplySetting {
+        id: Option<i64>,
+        reply_bot_id: Option<i64>,
+    },
+    /// The reply setting does not watch this target (any more).
+    ReplyTarget {
+        reply_setting_id: i64,
+        target_bot_id: i64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    /// Add the file's data next to what is already in the database.
+    #[default]
+    Merge,
+    /// Delete every account (and with it schedules, reply settings and
+    /// logs) before restoring the file.
+    Replace,
+}
+
+/// What to do with an imported account whose `account_name` is taken.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NameConflict {
+    /// Keep the existing account; the file's schedule, reply setting and
+    /// logs for it are skipped.
+    #[default]
+    Skip,
+    /// Update the existing account and replace its schedule and reply
+    /// setting with the file's.
+    Overwrite,
+    /// Create the account under a free name such as `name_2`.
+    Rename,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DataImportOptions {
+    pub mode: ImportMode,
+    pub on_conflict: NameConflict,
+    /// Work out the changes without writing them.
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportOutcome {
+    Created,
+    Updated,
+    Skipped,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ImportCounts {
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+}
+
+impl ImportCounts {
+    pub fn add(&mut self, outcome: ImportOutcome) {
+        match outcome {
+            ImportOutcome::Created => self.created += 1,
+            ImportOutcome::Updated => self.updated += 1,
+            ImportOutcome::Skipped => self.skipped += 1,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccountImport {
+    /// The account's id in the file, which schedules and replies refer to.
+    pub source_id: Option<i64>,
+    pub account_name: String,
+    /// The name it has in the database, which differs after a rename.
+    pub imported_name: String,
+    /// Its id in the database (the would-be id on a dry run).
+    pub account_id: i64,
+    pub outcome: ImportOutcome,
+    /// The file had redacted credentials or secret references, so the
+    /// account needs its credentials entered again.
+    pub credentials_missing: bool,
+}
+
+/// What importing an export file changes, or changed once `applied`.
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub path: String,
+    pub format: ExportFormat,
+    /// The version the file was written in, inferred from its shape for
+    /// files from before versioning.
+    pub source_version: u32,
+    pub version: u32,
+    /// Upgrade steps applied, oldest first.
+    pub upgrades: Vec<String>,
+    pub mode: ImportMode,
+    pub applied: bool,
+    /// Accounts deleted before restoring in replace mode.
+    pub deleted_accounts: usize,
+    pub accounts: Vec<AccountImport>,
+    pub schedules: ImportCounts,
+    pub reply_settings: ImportCounts,
+    pub logs: ImportCounts,
+    pub user_settings: ImportCounts,
+    /// Backup of the database taken before the import was applied.
+    pub safety_backup: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GithubExportReport {
+    /// The written `user-config.json`.
+    pub config_path: String,
+    /// The written `system-state.json`.
+    pub state_path: String,
+    /// Bots and reply settings whose progress was kept from the existing
+    /// state file instead of taken from the database.
+    pub preserved_state_entries: usize,
+    pub secret_mode: SecretMode,
+    pub manifest_path: Option<String>,
+    pub required_secrets: usize,
+    /// Workflows whose secrets block was rewritten to match the manifest.
+    pub workflow_paths: Vec<String>,
+    pub redacted_paths: Vec<String>,
+    pub warnings: Vec<String>,
+}
@@ -1,13 +1,46 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::i18n::{Locale, Message, MessageParam};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExecutionLog {
     pub id: Option<i64>,
     pub account_id: i64,
     pub log_type: String,
+    /// Text as rendered when the log was written; replaced on read when
+    /// `message_id` is set.
     pub message: String,
+    /// Catalog id of `message`, if it came from the backend catalog.
+    #[serde(default)]
+    pub message_id: Option<String>,
+    #[serde(default)]
+    pub message_params: BTreeMap<String, MessageParam>,
+    /// Classified failure for `error` rows, see `AppError::log_code`.
+    #[serde(default)]
+    pub error_code: Option<String>,
     pub tweet_id: Option<String>,
     pub tweet_content: Option<String>,
     pub status: String,
     pub created_at: String,
 }
+
+impl ExecutionLog {
+    pub fn set_message(&mut self, message: Message) {
+        self.message = message.to_string();
+        self.message_id = Some(message.id);
+        self.message_params = message.params;
+    }
+
+    /// Renders `message` from its catalog id in `locale`.
+    pub fn localize(&mut self, locale: Locale) {
+        if let Some(id) = &self.message_id {
+            let message = Message {
+                id: id.clone(),
+                params: self.message_params.clone(),
+            };
+            self.message = message.render(locale);
+        }
+    }
+}
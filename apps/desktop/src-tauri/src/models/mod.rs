@@ -1,9 +1,29 @@
+pub mod backup;
 pub mod bots;
+pub mod export;
 pub mod logs;
+pub mod quota;
+pub mod rate_limits;
 pub mod replies;
 pub mod settings;
 
-pub use bots::{BotAccount, BotConfig, ScheduledTweet};
+pub use backup::{BackupInfo, BackupReason};
+pub use bots::{
+    BotAccount, BotAccountSummary, BotAccountUpdate, BotConfig, CredentialHealth,
+    CredentialVerification, ProfileRefreshFailure, ProfileRefreshReport, ScheduledPost,
+    ScheduledTweet,
+};
+pub use export::{
+    AccountConflict, AccountImport, ActionsBot, ActionsConfig, BotState, BotStateChange,
+    DataExport, DataExportOptions, DataExportReport, DataImportOptions, ExportFormat,
+    GithubExportOptions, GithubExportReport, ImportMode, ImportOutcome, ImportReport, NameConflict,
+    ReplySettingConflict, ReplyState, ReplyStateChange, RequiredSecret, ScanPolicy, SecretMode,
+    SecretsManifest, StateImportReport, SystemState, UnknownStateEntry,
+};
 pub use logs::ExecutionLog;
-pub use replies::ReplySettings;
-pub use settings::{DashboardStats, TestTweetRequest, TwitterApiResponse, UserSettings};
+pub use quota::{ApiTier, QuotaStatus, QuotaUsage};
+pub use rate_limits::{AccountRateLimit, RateLimit};
+pub use replies::{ReplySettings, ReplyTargetState};
+pub use settings::{
+    DashboardStats, ErrorCodeCount, RetryPolicy, TestTweetRequest, TwitterApiResponse, UserSettings,
+};
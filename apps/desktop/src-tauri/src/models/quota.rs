@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+/// Twitter API access tier, stored as `BotAccount.api_type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ApiTier {
+    Free,
+    Basic,
+    Pro,
+}
+
+impl ApiTier {
+    pub fn from_api_type(api_type: &str) -> Option<Self> {
+        match api_type {
+            "Free" => Some(ApiTier::Free),
+            "Basic" => Some(ApiTier::Basic),
+            "Pro" => Some(ApiTier::Pro),
+            _ => None,
+        }
+    }
+
+    /// Posts one user may create per calendar month on this tier.
+    pub fn monthly_post_budget(self) -> i64 {
+        match self {
+            ApiTier::Free => 500,
+            ApiTier::Basic => 3_000,
+            ApiTier::Pro => 300_000,
+        }
+    }
+
+    /// Posts and users that may be read per calendar month on this tier.
+    pub fn monthly_read_budget(self) -> i64 {
+        match self {
+            ApiTier::Free => 100,
+            ApiTier::Basic => 10_000,
+            ApiTier::Pro => 1_000_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotaStatus {
+    Ok,
+    /// At least 90% of the budget is used.
+    Warning,
+    Exhausted,
+}
+
+impl QuotaStatus {
+    pub fn of(used: i64, budget: i64) -> Self {
+        if used >= budget {
+            QuotaStatus::Exhausted
+        } else if used * 10 >= budget * 9 {
+            QuotaStatus::Warning
+        } else {
+            QuotaStatus::Ok
+        }
+    }
+}
+
+/// One account's usage in `month` (`YYYY-MM`, UTC) against its tier.
+#[derive(Debug, Serialize)]
+pub struct QuotaUsage {
+    pub account_id: i64,
+    pub account_name: String,
+    pub api_type: String,
+    pub tier: ApiTier,
+    pub month: String,
+    pub posts_used: i64,
+    pub post_budget: i64,
+    pub post_status: QuotaStatus,
+    pub reads_used: i64,
+    pub read_budget: i64,
+    pub read_status: QuotaStatus,
+}
+
+impl QuotaUsage {
+    /// Accounts with an unrecognised `api_type` are held to the Free tier.
+    pub fn new(
+        account_id: i64,
+        account_name: String,
+        api_type: String,
+        month: String,
+        posts_used: i64,
+        reads_used: i64,
+    ) -> Self {
+        let tier = ApiTier::from_api_type(&api_type).unwrap_or(ApiTier::Free);
+        let post_budget = tier.monthly_post_budget();
+        let read_budget = tier.monthly_read_budget();
+
+        Self {
+            account_id,
+            account_name,
+            api_type,
+            tier,
+            month,
+            posts_used,
+            post_budget,
+            post_status: QuotaStatus::of(posts_used, post_budget),
+            reads_used,
+            read_budget,
+            read_status: QuotaStatus::of(reads_used, read_budget),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// The rate-limit window last reported for one account and endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimit {
+    pub account_id: i64,
+    pub endpoint: String,
+    pub limit: Option<i64>,
+    pub remaining: i64,
+    /// Unix time in seconds at which the window resets.
+    pub reset_at: i64,
+    pub updated_at: String,
+}
+
+impl RateLimit {
+    /// Whether calls must wait for the window to reset at `now` (Unix time).
+    pub fn is_exhausted(&self, now: i64) -> bool {
+        self.remaining <= 0 && self.reset_at > now
+    }
+}
+
+/// Dashboard row: an account's tier with the quota left on one endpoint.
+/// `endpoint` is `None` for accounts that have not called the API yet.
+#[derive(Debug, Serialize)]
+pub struct AccountRateLimit {
+    pub account_id: i64,
+    pub account_name: String,
+    pub api_type: String,
+    pub endpoint: Option<String>,
+    pub limit: Option<i64>,
+    pub remaining: Option<i64>,
+    pub reset_at: Option<i64>,
+    pub updated_at: Option<String>,
+}
@@ -1,13 +1,27 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ReplySettings {
     pub id: Option<i64>,
+    /// JSON array of target bot ids, assembled from `reply_targets`.
     pub target_bot_ids: String,
     pub reply_bot_id: i64,
     pub reply_content: String,
     pub is_active: bool,
+    /// JSON array of `"<target_bot_id>:<tweet_id>"` entries, assembled from
+    /// `reply_targets`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_checked_tweet_ids: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
+
+/// One target of an active reply setting and how far it has been checked.
+#[derive(Debug, Clone)]
+pub struct ReplyTargetState {
+    pub reply_setting_id: i64,
+    pub reply_bot_id: i64,
+    pub target_bot_id: i64,
+    pub last_checked_tweet_id: Option<String>,
+}
@@ -17,6 +17,15 @@ pub struct DashboardStats {
     pub today_tweets: i32,
     pub total_tweets: i32,
     pub error_count: i32,
+    /// Today's errors grouped by `execution_logs.error_code`.
+    #[serde(default)]
+    pub errors_by_code: Vec<ErrorCodeCount>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErrorCodeCount {
+    pub error_code: String,
+    pub count: i32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -31,3 +40,28 @@ pub struct TwitterApiResponse {
     pub tweet_id: Option<String>,
     pub message: String,
 }
+
+/// How failed posts are retried, stored in `app_settings` as JSON. Only
+/// connection errors and 5xx responses are retried.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Total attempts including the first; 1 disables retrying.
+    pub max_attempts: u32,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    /// No retry starts once this much time has passed since the first
+    /// attempt.
+    pub deadline_secs: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff_ms: 1_000,
+            max_backoff_ms: 8_000,
+            deadline_secs: 30,
+        }
+    }
+}
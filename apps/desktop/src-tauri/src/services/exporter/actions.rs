@@ -0,0 +1,276 @@
+//! Splits the merged GitHub Actions config into the committed
+//! `user-config.json` and the workflow-owned `system-state.json`, in the
+//! shapes `config/actions/scripts/split-config.js` produces.
+
+use std::fs;
+use std::path::Path;
+
+use crate::error::AppError;
+use crate::i18n::Message;
+use crate::models::{ActionsConfig, BotState, ReplyState, SystemState};
+
+pub const USER_CONFIG_FILE: &str = "user-config.json";
+pub const SYSTEM_STATE_FILE: &str = "system-state.json";
+
+#[derive(Debug)]
+pub struct SplitConfig {
+    pub user_config: ActionsConfig,
+    pub state: SystemState,
+    /// Entries whose progress came from the previous state file.
+    pub preserved: usize,
+}
+
+/// The state file at `path`, or `None` when there is none yet. A file that
+/// cannot be parsed is an error rather than being overwritten, since it
+/// holds progress only the workflows know about.
+pub fn read_system_state(path: &Path) -> Result<Option<SystemState>, AppError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(AppError::Io(
+                Message::new("export.read_failed")
+                    .with("path", path.display())
+                    .with("detail", e),
+            ))
+        }
+    };
+    if content.trim().is_empty() {
+        return Ok(None);
+    }
+
+    serde_json::from_str(content.trim_start_matches('\u{feff}'))
+        .map(Some)
+        .map_err(|e| {
+            AppError::Validation(
+                Message::new("export.invalid_system_state")
+                    .with("path", path.display())
+                    .with("detail", e),
+            )
+        })
+}
+
+/// Moves `current_index` and `last_checked_tweet_ids` out of `config` into
+/// the state. Where `previous` already tracks a bot (by account id, then
+/// name) or a reply setting (by id, then reply bot), its values win over the
+/// database's, which lag behind whatever the workflows have advanced.
+pub fn split_config(mut config: ActionsConfig, previous: Option<&SystemState>) -> SplitConfig {
+    let mut state = SystemState {
+        bot_state: Vec::new(),
+        reply_state: Vec::new(),
+        version: Some(config.version.clone()),
+        updated_at: Some(config.updated_at.clone()),
+    };
+    let mut preserved = 0;
+
+    for bot in &mut config.bots {
+        let account_id = bot.account.id;
+        let account_name = Some(bot.account.account_name.clone());
+        let exported_index = bot.current_index.take().unwrap_or(0);
+
+        let current_index =
+            match previous.and_then(|state| find_bot_state(state, account_id, &account_name)) {
+                Some(entry) => {
+                    preserved += 1;
+                    entry.current_index
+                }
+                None => exported_index,
+            };
+
+        state.bot_state.push(BotState {
+            account_id,
+            account_name,
+            current_index,
+        });
+    }
+
+    for setting in &mut config.reply_settings {
+        let id = setting.id;
+        let reply_bot_id = Some(setting.reply_bot_id);
+        let exported_ids = setting
+            .last_checked_tweet_ids
+            .take()
+            .unwrap_or_else(|| "[]".to_string());
+
+        let last_checked_tweet_ids =
+            match previous.and_then(|state| find_reply_state(state, id, reply_bot_id)) {
+                Some(entry) => {
+                    preserved += 1;
+                    entry.last_checked_tweet_ids.clone()
+                }
+                None => exported_ids,
+            };
+
+        state.reply_state.push(ReplyState {
+            id,
+            reply_bot_id,
+            last_checked_tweet_ids,
+        });
+    }
+
+    SplitConfig {
+        user_config: config,
+        state,
+        preserved,
+    }
+}
+
+fn find_bot_state<'a>(
+    state: &'a SystemState,
+    account_id: Option<i64>,
+    account_name: &Option<String>,
+) -> Option<&'a BotState> {
+    account_id
+        .and_then(|id| {
+            state
+                .bot_state
+                .iter()
+                .find(|entry| entry.account_id == Some(id))
+        })
+        .or_else(|| {
+            account_name.as_ref().and_then(|name| {
+                state
+                    .bot_state
+                    .iter()
+                    .find(|entry| entry.account_name.as_ref() == Some(name))
+            })
+        })
+}
+
+fn find_reply_state(
+    state: &SystemState,
+    id: Option<i64>,
+    reply_bot_id: Option<i64>,
+) -> Option<&ReplyState> {
+    id.and_then(|id| state.reply_state.iter().find(|entry| entry.id == Some(id)))
+        .or_else(|| {
+            reply_bot_id.and_then(|reply_bot_id| {
+                state
+                    .reply_state
+                    .iter()
+                    .find(|entry| entry.reply_bot_id == Some(reply_bot_id))
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn account(id: i64, name: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "account_name": name,
+            "api_type": "Free",
+            "api_key": "${{ secrets.BOT_KEY }}",
+            "api_key_secret": "${{ secrets.BOT_KEY_SECRET }}",
+            "access_token": "${{ secrets.BOT_TOKEN }}",
+            "access_token_secret": "${{ secrets.BOT_TOKEN_SECRET }}",
+            "status": "active"
+        })
+    }
+
+    fn merged_config() -> ActionsConfig {
+        serde_json::from_value(json!({
+            "format": "tam-actions-config",
+            "version": "2",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "bots": [
+                {
+                    "account": account(1, "first"),
+                    "scheduled_content_list": "[\"a\",\"b\"]",
+                    "current_index": 0,
+                    "scheduled_times": "09:00"
+                },
+                {
+                    "account": account(2, "second"),
+                    "scheduled_content": "hello",
+                    "scheduled_times": "10:00"
+                }
+            ],
+            "reply_settings": [
+                {
+                    "id": 5,
+                    "reply_bot_id": 1,
+                    "target_bot_ids": "[2]",
+                    "reply_content": "hi",
+                    "is_active": true,
+                    "last_checked_tweet_ids": "[]",
+                    "created_at": "2024-01-01T00:00:00Z",
+                    "updated_at": "2024-01-01T00:00:00Z"
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn strips_dynamic_fields_from_the_user_config() {
+        let split = split_config(merged_config(), None);
+        let user_config = serde_json::to_value(&split.user_config).unwrap();
+
+        assert!(user_config["bots"][0].get("current_index").is_none());
+        assert!(user_config["reply_settings"][0]
+            .get("last_checked_tweet_ids")
+            .is_none());
+        assert_eq!(user_config["reply_settings"][0]["reply_content"], "hi");
+        assert_eq!(split.state.version.as_deref(), Some("2"));
+        assert_eq!(split.state.bot_state.len(), 2);
+        assert_eq!(split.state.bot_state[1].current_index, 0);
+        assert_eq!(split.state.reply_state[0].last_checked_tweet_ids, "[]");
+        assert_eq!(split.preserved, 0);
+    }
+
+    #[test]
+    fn keeps_progress_from_the_previous_state() {
+        let previous = SystemState {
+            bot_state: vec![
+                BotState {
+                    account_id: Some(1),
+                    account_name: Some("renamed".to_string()),
+                    current_index: 7,
+                },
+                BotState {
+                    account_id: None,
+                    account_name: Some("second".to_string()),
+                    current_index: 3,
+                },
+            ],
+            reply_state: vec![ReplyState {
+                id: Some(5),
+                reply_bot_id: Some(1),
+                last_checked_tweet_ids: "[\"2:99\"]".to_string(),
+            }],
+            ..Default::default()
+        };
+
+        let split = split_config(merged_config(), Some(&previous));
+
+        assert_eq!(split.state.bot_state[0].current_index, 7);
+        assert_eq!(
+            split.state.bot_state[0].account_name.as_deref(),
+            Some("first")
+        );
+        assert_eq!(split.state.bot_state[1].current_index, 3);
+        assert_eq!(
+            split.state.reply_state[0].last_checked_tweet_ids,
+            "[\"2:99\"]"
+        );
+        assert_eq!(split.preserved, 3);
+    }
+
+    #[test]
+    fn missing_state_file_is_not_an_error() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(SYSTEM_STATE_FILE);
+        assert_eq!(read_system_state(&path).unwrap(), None);
+
+        fs::write(&path, "{ not json").unwrap();
+        assert!(matches!(
+            read_system_state(&path),
+            Err(AppError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,863 @@
+//! Reads an export file back, whichever format and version it was written
+//! in, and restores it into the database.
+//!
+//! Ids in the file are the exporting database's, so every account is
+//! matched by name and given an id here, and schedules, reply settings and
+//! logs are rewritten to the new ids.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use chrono::Utc;
+use rusqlite::Connection;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::normalize_path;
+use super::state_import::parse_last_checked;
+use super::versioning::{current_version, upgrade, Upgraded, DATA_EXPORT_VERSION};
+use crate::db::queries::bots::{
+    delete_bot_account, fetch_account_names, insert_bot_account, restore_bot_identity,
+    update_bot_account,
+};
+use crate::db::queries::logs::insert_execution_log;
+use crate::db::queries::replies::{
+    fetch_reply_target_states, insert_reply_setting, set_reply_target_checked,
+};
+use crate::db::queries::schedules::{fetch_schedule_indices, restore_schedule};
+use crate::db::queries::users::update_user_settings;
+use crate::error::AppError;
+use crate::i18n::Message;
+use crate::models::{
+    AccountImport, ActionsConfig, BotAccount, BotAccountUpdate, CredentialHealth, DataExport,
+    DataImportOptions, ExecutionLog, ExportFormat, ImportMode, ImportOutcome, ImportReport,
+    NameConflict, ReplySettings, ScheduledPost, ScheduledTweet,
+};
+use crate::utils::vault::CredentialVault;
+
+const STATUS_INVALID_CREDENTIALS: &str = "invalid_credentials";
+const CREDENTIAL_FIELDS: [&str; 4] = [
+    "api_key",
+    "api_key_secret",
+    "access_token",
+    "access_token_secret",
+];
+
+/// An export file as the current version of its format.
+#[derive(Debug)]
+pub enum LoadedExport {
+    Data(DataExport),
+    ActionsConfig(ActionsConfig),
+}
+
+#[derive(Debug)]
+pub struct LoadedFile {
+    pub path: String,
+    pub source_version: u32,
+    pub upgrades: Vec<String>,
+    pub export: LoadedExport,
+}
+
+/// Reads the file at `raw_path` and upgrades it to the current version.
+pub fn load_export(raw_path: &str) -> Result<LoadedFile, AppError> {
+    let path = normalize_path(raw_path);
+    let content = fs::read_to_string(&path).map_err(|e| {
+        AppError::Io(
+            Message::new("export.read_failed")
+                .with("path", &path)
+                .with("detail", e),
+        )
+    })?;
+
+    let document: Value =
+        serde_json::from_str(content.trim_start_matches('\u{feff}')).map_err(|e| {
+            AppError::Validation(
+                Message::new("import.invalid_json")
+                    .with("path", &path)
+                    .with("detail", e),
+            )
+        })?;
+
+    let Upgraded {
+        format,
+        source_version,
+        steps,
+        document,
+    } = upgrade(document, &path)?;
+
+    let export = match format {
+        ExportFormat::Data => LoadedExport::Data(parse_current(document, format, &path)?),
+        ExportFormat::ActionsConfig => {
+            LoadedExport::ActionsConfig(parse_current(document, format, &path)?)
+        }
+    };
+
+    if !steps.is_empty() {
+        println!(
+            "Upgraded {} from {} v{}: {}",
+            path,
+            format.as_str(),
+            source_version,
+            steps.join(", ")
+        );
+    }
+
+    Ok(LoadedFile {
+        path,
+        source_version,
+        upgrades: steps,
+        export,
+    })
+}
+
+fn parse_current<T: DeserializeOwned>(
+    document: Value,
+    format: ExportFormat,
+    path: &str,
+) -> Result<T, AppError> {
+    serde_json::from_value(document).map_err(|e| {
+        AppError::Validation(
+            Message::new("import.invalid_content")
+                .with("path", path)
+                .with("format", format.as_str())
+                .with("version", current_version(format))
+                .with("detail", e),
+        )
+    })
+}
+
+/// Restores the file at `raw_path` into the database in one transaction.
+/// With `dry_run` the transaction is rolled back, so the report shows
+/// exactly what an import would do.
+pub fn import_data(
+    conn: &Connection,
+    vault: &CredentialVault,
+    raw_path: &str,
+    options: &DataImportOptions,
+) -> Result<ImportReport, AppError> {
+    let loaded = load_export(raw_path)?;
+    let (format, data) = match loaded.export {
+        LoadedExport::Data(data) => (ExportFormat::Data, data),
+        LoadedExport::ActionsConfig(config) => (
+            ExportFormat::ActionsConfig,
+            data_from_actions_config(config),
+        ),
+    };
+
+    let report = ImportReport {
+        path: loaded.path,
+        format,
+        source_version: loaded.source_version,
+        version: current_version(format),
+        upgrades: loaded.upgrades,
+        mode: options.mode,
+        applied: false,
+        deleted_accounts: 0,
+        accounts: Vec::new(),
+        schedules: Default::default(),
+        reply_settings: Default::default(),
+        logs: Default::default(),
+        user_settings: Default::default(),
+        safety_backup: None,
+        warnings: Vec::new(),
+    };
+
+    let now = Utc::now().to_rfc3339();
+    let tx = conn.unchecked_transaction()?;
+    let mut restore = Restore {
+        conn: &tx,
+        vault,
+        options,
+        now: &now,
+        restored: HashMap::new(),
+        known: HashMap::new(),
+        created: HashSet::new(),
+        redacted_paths: data.redacted_paths.iter().cloned().collect(),
+        report,
+    };
+    restore.accounts(&data.accounts)?;
+    restore.schedules(data.scheduled_tweets, data.scheduled_posts)?;
+    restore.reply_settings(&data.reply_settings)?;
+    restore.logs(data.logs)?;
+    if let Some(settings) = &data.user_settings {
+        let outcome = match options.mode {
+            ImportMode::Replace => {
+                update_user_settings(&tx, settings, &now)?;
+                ImportOutcome::Updated
+            }
+            ImportMode::Merge => ImportOutcome::Skipped,
+        };
+        restore.report.user_settings.add(outcome);
+    }
+    let mut report = restore.report;
+
+    if options.dry_run {
+        tx.rollback()?;
+    } else {
+        tx.commit()?;
+        report.applied = true;
+        println!(
+            "Imported {}: {} account(s), {} schedule(s), {} reply setting(s), {} log(s)",
+            report.path,
+            report.accounts.len(),
+            report.schedules.created + report.schedules.updated,
+            report.reply_settings.created + report.reply_settings.updated,
+            report.logs.created
+        );
+    }
+
+    Ok(report)
+}
+
+struct Restore<'a> {
+    conn: &'a Connection,
+    vault: &'a CredentialVault,
+    options: &'a DataImportOptions,
+    now: &'a str,
+    /// File account id to database id, for accounts whose schedules and
+    /// reply settings are restored.
+    restored: HashMap<i64, i64>,
+    /// Also includes skipped accounts, which can still be reply targets.
+    known: HashMap<i64, i64>,
+    /// File account ids of the accounts this import created.
+    created: HashSet<i64>,
+    redacted_paths: HashSet<String>,
+    report: ImportReport,
+}
+
+impl Restore<'_> {
+    fn accounts(&mut self, accounts: &[BotAccount]) -> Result<(), AppError> {
+        if self.options.mode == ImportMode::Replace {
+            let existing = fetch_account_names(self.conn)?;
+            for (id, _) in &existing {
+                delete_bot_account(self.conn, *id)?;
+            }
+            self.report.deleted_accounts = existing.len();
+        }
+
+        let mut taken: HashMap<String, i64> = fetch_account_names(self.conn)?
+            .into_iter()
+            .map(|(id, name)| (name, id))
+            .collect();
+
+        for (index, account) in accounts.iter().enumerate() {
+            let credentials = self.credentials(index, account);
+            let credentials_missing = credentials.iter().any(Option::is_none);
+            let name = account.account_name.clone();
+
+            let (imported_name, account_id, outcome) =
+                match (taken.get(&name).copied(), self.options.on_conflict) {
+                    (None, _) => {
+                        let id = self.create_account(account, &name, &credentials)?;
+                        (name, id, ImportOutcome::Created)
+                    }
+                    (Some(id), NameConflict::Skip) => (name, id, ImportOutcome::Skipped),
+                    (Some(id), NameConflict::Overwrite) => {
+                        self.overwrite_account(id, account, &credentials)?;
+                        (name, id, ImportOutcome::Updated)
+                    }
+                    (Some(_), NameConflict::Rename) => {
+                        let free = free_name(&name, &taken);
+                        let id = self.create_account(account, &free, &credentials)?;
+                        (free, id, ImportOutcome::Created)
+                    }
+                };
+
+            taken.insert(imported_name.clone(), account_id);
+            if let Some(source_id) = account.id {
+                self.known.insert(source_id, account_id);
+                if outcome != ImportOutcome::Skipped {
+                    self.restored.insert(source_id, account_id);
+                }
+                if outcome == ImportOutcome::Created {
+                    self.created.insert(source_id);
+                }
+            }
+
+            self.report.accounts.push(AccountImport {
+                source_id: account.id,
+                account_name: account.account_name.clone(),
+                imported_name,
+                account_id,
+                outcome,
+                credentials_missing: credentials_missing && outcome == ImportOutcome::Created,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The account's credentials in `CREDENTIAL_FIELDS` order, `None` for
+    /// values a credential cannot be: blanks, the Actions config's
+    /// `${{ secrets.* }}` references and whatever the export redacted.
+    fn credentials(&self, index: usize, account: &BotAccount) -> [Option<String>; 4] {
+        let values = [
+            &account.api_key,
+            &account.api_key_secret,
+            &account.access_token,
+            &account.access_token_secret,
+        ];
+        let mut credentials: [Option<String>; 4] = Default::default();
+        for ((credential, value), field) in
+            credentials.iter_mut().zip(values).zip(CREDENTIAL_FIELDS)
+        {
+            let value = value.trim();
+            let path = format!("$.accounts[{}].{}", index, field);
+            let missing =
+                value.is_empty() || value.starts_with("${{") || self.redacted_paths.contains(&path);
+            *credential = (!missing).then(|| value.to_string());
+        }
+        credentials
+    }
+
+    /// Accounts without usable credentials are stored with blanks and
+    /// marked `invalid_credentials` until they are entered again.
+    fn create_account(
+        &self,
+        account: &BotAccount,
+        name: &str,
+        credentials: &[Option<String>; 4],
+    ) -> Result<i64, AppError> {
+        let credentials_missing = credentials.iter().any(Option::is_none);
+        let [api_key, api_key_secret, access_token, access_token_secret] =
+            credentials.clone().map(Option::unwrap_or_default);
+        let stored = BotAccount {
+            id: None,
+            account_name: name.to_string(),
+            api_type: account.api_type.clone(),
+            api_key,
+            api_key_secret,
+            access_token,
+            access_token_secret,
+            status: match credentials_missing {
+                true => STATUS_INVALID_CREDENTIALS.to_string(),
+                false => account.status.clone(),
+            },
+            twitter_user_id: None,
+            twitter_username: None,
+            twitter_display_name: None,
+            last_verified_at: None,
+            credential_health: CredentialHealth::Unknown,
+            created_at: None,
+            updated_at: None,
+        };
+
+        let id = insert_bot_account(self.conn, self.vault, &stored, self.now)?;
+        // The Twitter identity goes with the credentials; without them it
+        // stays unset until the account is verified.
+        if !credentials_missing {
+            restore_bot_identity(self.conn, id, account, account.credential_health)?;
+        }
+        Ok(id)
+    }
+
+    /// Credentials the file does not have keep their stored values, and so
+    /// does the Twitter identity that goes with them.
+    fn overwrite_account(
+        &self,
+        id: i64,
+        account: &BotAccount,
+        credentials: &[Option<String>; 4],
+    ) -> Result<(), AppError> {
+        let credentials_missing = credentials.iter().any(Option::is_none);
+        let [api_key, api_key_secret, access_token, access_token_secret] = credentials.clone();
+        let update = BotAccountUpdate {
+            id,
+            account_name: account.account_name.clone(),
+            api_type: account.api_type.clone(),
+            api_key,
+            api_key_secret,
+            access_token,
+            access_token_secret,
+            status: account.status.clone(),
+        };
+        update_bot_account(self.conn, self.vault, &update, self.now)?;
+
+        if !credentials_missing {
+            restore_bot_identity(self.conn, id, account, account.credential_health)?;
+        }
+        Ok(())
+    }
+
+    fn schedules(
+        &mut self,
+        tweets: Vec<ScheduledTweet>,
+        posts: Vec<ScheduledPost>,
+    ) -> Result<(), AppError> {
+        let mut posts_by_schedule: HashMap<i64, Vec<ScheduledPost>> = HashMap::new();
+        for post in posts {
+            posts_by_schedule
+                .entry(post.schedule_id)
+                .or_default()
+                .push(post);
+        }
+
+        let mut scheduled_accounts: HashSet<i64> = fetch_schedule_indices(self.conn)?
+            .into_iter()
+            .map(|(account_id, _, _)| account_id)
+            .collect();
+
+        for tweet in tweets {
+            let account_id = self.restored.get(&tweet.account_id).copied();
+            let Some(account_id) = account_id.filter(|_| tweet.is_active) else {
+                self.report.schedules.add(ImportOutcome::Skipped);
+                continue;
+            };
+
+            let mut posts = tweet
+                .id
+                .and_then(|id| posts_by_schedule.remove(&id))
+                .unwrap_or_default();
+            posts.sort_by_key(|post| post.ordinal);
+
+            restore_schedule(self.conn, account_id, &tweet, &posts, self.now)?;
+            self.report
+                .schedules
+                .add(match scheduled_accounts.insert(account_id) {
+                    true => ImportOutcome::Created,
+                    false => ImportOutcome::Updated,
+                });
+        }
+
+        Ok(())
+    }
+
+    fn reply_settings(&mut self, settings: &[ReplySettings]) -> Result<(), AppError> {
+        let mut replying_bots: HashSet<i64> = fetch_reply_target_states(self.conn)?
+            .into_iter()
+            .map(|target| target.reply_bot_id)
+            .collect();
+
+        for setting in settings {
+            let Some(&reply_bot_id) = self.restored.get(&setting.reply_bot_id) else {
+                self.report.reply_settings.add(ImportOutcome::Skipped);
+                continue;
+            };
+
+            let mut targets = Vec::new();
+            for source_id in parse_ids(&setting.target_bot_ids) {
+                match self.known.get(&source_id) {
+                    Some(&target_id) => targets.push(target_id),
+                    None => self.report.warnings.push(format!(
+                        "Reply setting of bot {} watches bot {}, which is not in the file; the target was dropped",
+                        setting.reply_bot_id, source_id
+                    )),
+                }
+            }
+
+            if !setting.is_active || targets.is_empty() {
+                self.report.reply_settings.add(ImportOutcome::Skipped);
+                continue;
+            }
+
+            let setting_id = insert_reply_setting(
+                self.conn,
+                reply_bot_id,
+                &targets,
+                &setting.reply_content,
+                self.now,
+            )?;
+
+            let tokens: Vec<String> = setting
+                .last_checked_tweet_ids
+                .as_deref()
+                .and_then(|ids| serde_json::from_str(ids).ok())
+                .unwrap_or_default();
+            for (source_id, tweet_id) in parse_last_checked(&tokens) {
+                if let Some(&target_id) = self.known.get(&source_id) {
+                    set_reply_target_checked(
+                        self.conn, setting_id, target_id, &tweet_id, self.now,
+                    )?;
+                }
+            }
+
+            self.report
+                .reply_settings
+                .add(match replying_bots.insert(reply_bot_id) {
+                    true => ImportOutcome::Created,
+                    false => ImportOutcome::Updated,
+                });
+        }
+
+        Ok(())
+    }
+
+    /// Only accounts created by this import get their logs, so importing
+    /// the same file twice does not duplicate them.
+    fn logs(&mut self, logs: Vec<ExecutionLog>) -> Result<(), AppError> {
+        for mut log in logs {
+            let account_id = self
+                .restored
+                .get(&log.account_id)
+                .copied()
+                .filter(|_| self.created.contains(&log.account_id));
+            let Some(account_id) = account_id else {
+                self.report.logs.add(ImportOutcome::Skipped);
+                continue;
+            };
+
+            log.account_id = account_id;
+            insert_execution_log(self.conn, &log, &log.created_at)?;
+            self.report.logs.add(ImportOutcome::Created);
+        }
+
+        Ok(())
+    }
+}
+
+/// `name_2`, `name_3`, ... whichever is free first.
+fn free_name(name: &str, taken: &HashMap<String, i64>) -> String {
+    (2..)
+        .map(|suffix| format!("{}_{}", name, suffix))
+        .find(|candidate| !taken.contains_key(candidate))
+        .unwrap_or_default()
+}
+
+/// A JSON array of ids, as numbers or strings.
+fn parse_ids(ids: &str) -> Vec<i64> {
+    let values: Vec<Value> = serde_json::from_str(ids).unwrap_or_default();
+    values
+        .iter()
+        .filter_map(|value| match value {
+            Value::Number(number) => number.as_i64(),
+            Value::String(text) => text.trim().parse().ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The data export equivalent of an Actions config: one schedule per bot
+/// with a content list, keyed by the bot's id. The config carries no logs
+/// and, in place of credentials, secret references.
+fn data_from_actions_config(config: ActionsConfig) -> DataExport {
+    let mut data = DataExport {
+        format: ExportFormat::Data,
+        version: DATA_EXPORT_VERSION,
+        exported_at: config.updated_at.clone(),
+        accounts: Vec::new(),
+        scheduled_tweets: Vec::new(),
+        scheduled_posts: Vec::new(),
+        reply_settings: config.reply_settings,
+        logs: Vec::new(),
+        user_settings: None,
+        redacted_paths: Vec::new(),
+    };
+
+    for bot in config.bots {
+        if let (Some(account_id), Some(list)) = (bot.account.id, &bot.scheduled_content_list) {
+            let posts: Vec<String> = serde_json::from_str(list).unwrap_or_default();
+            data.scheduled_tweets.push(ScheduledTweet {
+                id: Some(account_id),
+                account_id,
+                content: posts.first().cloned().unwrap_or_default(),
+                content_list: Some(list.clone()),
+                current_index: bot.current_index.map(|index| index as i32),
+                scheduled_times: bot.scheduled_times.clone().unwrap_or_default(),
+                is_active: true,
+                created_at: config.updated_at.clone(),
+                updated_at: config.updated_at.clone(),
+            });
+            for (ordinal, content) in posts.into_iter().enumerate() {
+                data.scheduled_posts.push(ScheduledPost {
+                    id: None,
+                    schedule_id: account_id,
+                    ordinal: ordinal as i32,
+                    content,
+                    is_enabled: true,
+                    last_posted_at: None,
+                    post_count: 0,
+                    created_at: config.updated_at.clone(),
+                    updated_at: config.updated_at.clone(),
+                });
+            }
+        }
+        data.accounts.push(bot.account);
+    }
+
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::params;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::db::queries::bots::fetch_all_bots;
+    use crate::db::queries::logs::fetch_execution_logs;
+    use crate::db::queries::replies::fetch_reply_settings;
+    use crate::db::queries::users::fetch_user_settings;
+    use crate::services::exporter::export_data;
+    use crate::services::exporter::scanner::REDACTED;
+    use crate::test_support::{migrated_db, seeded_db, NOW};
+
+    fn exported_account(id: i64, name: &str, secret: &str) -> Value {
+        json!({
+            "id": id,
+            "account_name": name,
+            "api_type": "Free",
+            "api_key": secret,
+            "api_key_secret": secret,
+            "access_token": secret,
+            "access_token_secret": secret,
+            "status": "active",
+            "twitter_user_id": format!("{}00", id),
+            "credential_health": "valid"
+        })
+    }
+
+    fn schedule(id: i64, account_id: i64) -> Value {
+        json!({
+            "id": id,
+            "account_id": account_id,
+            "content": "x",
+            "content_list": "[\"x\",\"y\"]",
+            "current_index": 1,
+            "scheduled_times": "10:00",
+            "is_active": true,
+            "created_at": NOW,
+            "updated_at": NOW
+        })
+    }
+
+    fn post(schedule_id: i64, ordinal: i32, content: &str) -> Value {
+        json!({
+            "id": null,
+            "schedule_id": schedule_id,
+            "ordinal": ordinal,
+            "content": content,
+            "is_enabled": true,
+            "last_posted_at": null,
+            "post_count": 0,
+            "created_at": NOW,
+            "updated_at": NOW
+        })
+    }
+
+    fn log(account_id: i64) -> Value {
+        json!({
+            "id": null,
+            "account_id": account_id,
+            "log_type": "tweet",
+            "message": "posted",
+            "tweet_id": null,
+            "tweet_content": null,
+            "status": "success",
+            "created_at": "2023-12-31T00:00:00Z"
+        })
+    }
+
+    /// "first" (10) and "third" (11), each scheduled, with 11 replying to
+    /// 10 and to 99, which is not in the file.
+    fn write_export(dir: &TempDir, secret: &str, redacted_paths: &[String]) -> String {
+        let export = json!({
+            "format": "tam-data-export",
+            "version": DATA_EXPORT_VERSION,
+            "exported_at": NOW,
+            "accounts": [exported_account(10, "first", secret), exported_account(11, "third", secret)],
+            "scheduled_tweets": [schedule(20, 10), schedule(21, 11)],
+            "scheduled_posts": [post(21, 1, "y"), post(21, 0, "x"), post(20, 0, "z")],
+            "reply_settings": [
+                {
+                    "id": 30,
+                    "target_bot_ids": "[10,\"99\"]",
+                    "reply_bot_id": 11,
+                    "reply_content": "thanks",
+                    "is_active": true,
+                    "last_checked_tweet_ids": "[\"10:555\"]",
+                    "created_at": NOW,
+                    "updated_at": NOW
+                }
+            ],
+            "logs": [log(10), log(11)],
+            "user_settings": {
+                "id": 1,
+                "user_id": "default",
+                "plan_type": "pro",
+                "max_accounts": 50,
+                "created_at": NOW,
+                "updated_at": NOW
+            },
+            "redacted_paths": redacted_paths
+        });
+        let path = dir.path().join("backup.json");
+        fs::write(&path, export.to_string()).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    fn count(conn: &Connection, table: &str) -> i64 {
+        conn.query_row(
+            &format!("SELECT COUNT(*) FROM {}", table),
+            params![],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn merges_new_accounts_and_remaps_their_ids() {
+        let dir = TempDir::new().unwrap();
+        let (conn, vault) = seeded_db(&dir);
+        let path = write_export(&dir, "restored-secret", &[]);
+
+        let dry_run = DataImportOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+        let preview = import_data(&conn, &vault, &path, &dry_run).unwrap();
+        assert!(!preview.applied);
+        assert_eq!(preview.accounts.len(), 2);
+        assert_eq!(count(&conn, "bot_accounts"), 2);
+        assert_eq!(count(&conn, "execution_logs"), 0);
+
+        let report = import_data(&conn, &vault, &path, &Default::default()).unwrap();
+        assert!(report.applied);
+        assert_eq!(report.accounts[0].outcome, ImportOutcome::Skipped);
+        assert_eq!(report.accounts[0].account_id, 1);
+        assert_eq!(report.accounts[1].outcome, ImportOutcome::Created);
+        let third = report.accounts[1].account_id;
+        assert_eq!(report.schedules.created, 1);
+        assert_eq!(report.schedules.skipped, 1);
+        assert_eq!(report.reply_settings.created, 1);
+        assert_eq!(report.logs.created, 1);
+        assert_eq!(report.logs.skipped, 1);
+        assert_eq!(report.user_settings.skipped, 1);
+        assert_eq!(report.warnings.len(), 1, "{:?}", report.warnings);
+
+        let posts: Vec<String> = conn
+            .prepare(
+                "SELECT p.content FROM scheduled_posts p
+                 JOIN scheduled_tweets t ON t.id = p.schedule_id
+                 WHERE t.account_id = ? AND t.is_active = 1 ORDER BY p.ordinal",
+            )
+            .unwrap()
+            .query_map(params![third], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(posts, ["x", "y"]);
+
+        let reply = fetch_reply_settings(&conn)
+            .unwrap()
+            .into_iter()
+            .find(|setting| setting.reply_bot_id == third)
+            .unwrap();
+        assert_eq!(reply.target_bot_ids, "[1]");
+        assert_eq!(reply.last_checked_tweet_ids.as_deref(), Some("[\"1:555\"]"));
+
+        let logs = fetch_execution_logs(&conn, None, 10).unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].account_id, third);
+
+        let bots = fetch_all_bots(&conn, &vault).unwrap();
+        let restored = bots.iter().find(|bot| bot.id == Some(third)).unwrap();
+        assert_eq!(restored.api_key, "restored-secret");
+        assert_eq!(restored.twitter_user_id.as_deref(), Some("1100"));
+    }
+
+    #[test]
+    fn restores_its_own_export_into_a_new_database() {
+        let source_dir = TempDir::new().unwrap();
+        let (source, source_vault) = seeded_db(&source_dir);
+        let path = source_dir
+            .path()
+            .join("backup.json")
+            .to_string_lossy()
+            .to_string();
+        let exported = export_data(&source, &source_vault, &path, &Default::default()).unwrap();
+        assert!(exported.redacted_paths.is_empty());
+
+        let target_dir = TempDir::new().unwrap();
+        let (conn, vault) = migrated_db(&target_dir);
+        let report = import_data(&conn, &vault, &path, &Default::default()).unwrap();
+
+        assert!(report.accounts.iter().all(
+            |account| account.outcome == ImportOutcome::Created && !account.credentials_missing
+        ));
+        assert_eq!(report.schedules.created, 1);
+        assert_eq!(report.reply_settings.created, 1);
+
+        let bots = fetch_all_bots(&conn, &vault).unwrap();
+        assert_eq!(bots.len(), 2);
+        for bot in &bots {
+            assert_eq!(bot.status, "active");
+            assert_eq!(bot.api_key, "consumer-key");
+            assert_eq!(bot.api_key_secret, "consumer-secret");
+            assert_eq!(bot.access_token, "access-token");
+            assert_eq!(bot.access_token_secret, "access-token-secret");
+        }
+        let id_of = |name: &str| {
+            bots.iter()
+                .find(|bot| bot.account_name == name)
+                .and_then(|bot| bot.id)
+                .unwrap()
+        };
+        let settings = fetch_reply_settings(&conn).unwrap();
+        assert_eq!(settings.len(), 1);
+        assert_eq!(settings[0].reply_bot_id, id_of("first"));
+        assert_eq!(settings[0].target_bot_ids, format!("[{}]", id_of("second")));
+        assert_eq!(count(&conn, "scheduled_posts"), 3);
+    }
+
+    #[test]
+    fn replace_restores_redacted_accounts_as_invalid_credentials() {
+        let dir = TempDir::new().unwrap();
+        let (conn, vault) = seeded_db(&dir);
+        let redacted: Vec<String> = (0..2)
+            .flat_map(|index| {
+                CREDENTIAL_FIELDS.map(|field| format!("$.accounts[{}].{}", index, field))
+            })
+            .collect();
+        let path = write_export(&dir, REDACTED, &redacted);
+
+        let options = DataImportOptions {
+            mode: ImportMode::Replace,
+            ..Default::default()
+        };
+        let report = import_data(&conn, &vault, &path, &options).unwrap();
+
+        assert_eq!(report.deleted_accounts, 2);
+        assert!(report
+            .accounts
+            .iter()
+            .all(|account| account.credentials_missing));
+        assert_eq!(report.schedules.created, 2);
+        assert_eq!(report.logs.created, 2);
+        assert_eq!(report.user_settings.updated, 1);
+
+        let bots = fetch_all_bots(&conn, &vault).unwrap();
+        assert_eq!(bots.len(), 2);
+        for bot in &bots {
+            assert_eq!(bot.status, STATUS_INVALID_CREDENTIALS);
+            assert_eq!(bot.api_key, "");
+            assert_eq!(bot.credential_health, CredentialHealth::Unknown);
+            assert_eq!(bot.twitter_user_id, None);
+        }
+        assert_eq!(fetch_user_settings(&conn).unwrap().plan_type, "pro");
+    }
+
+    #[test]
+    fn renames_accounts_whose_names_are_taken() {
+        let dir = TempDir::new().unwrap();
+        let (conn, vault) = seeded_db(&dir);
+        let export = json!({
+            "format": "tam-data-export",
+            "version": DATA_EXPORT_VERSION,
+            "exported_at": NOW,
+            "accounts": [exported_account(5, "second", "secret")]
+        });
+        let path = dir.path().join("backup.json");
+        fs::write(&path, export.to_string()).unwrap();
+
+        let options = DataImportOptions {
+            on_conflict: NameConflict::Rename,
+            ..Default::default()
+        };
+        let report = import_data(&conn, &vault, &path.to_string_lossy(), &options).unwrap();
+
+        assert_eq!(report.accounts[0].imported_name, "second_2");
+        assert_eq!(report.accounts[0].outcome, ImportOutcome::Created);
+        assert_eq!(count(&conn, "bot_accounts"), 3);
+    }
+}
@@ -0,0 +1,561 @@
+pub mod actions;
+pub mod data_import;
+pub mod scanner;
+pub mod schema;
+pub mod state_import;
+pub mod versioning;
+pub mod workflows;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use rusqlite::{Connection, Result as SqliteResult};
+use serde_json::Value;
+
+use crate::db::queries::bots::{decrypt_bot_account, fetch_all_bots, row_to_bot_account};
+use crate::db::queries::logs::row_to_execution_log;
+use crate::db::queries::replies::fetch_reply_settings;
+use crate::db::queries::schedules::{
+    enabled_content_list, fetch_scheduled_posts, fetch_scheduled_tweets,
+};
+use crate::error::AppError;
+use crate::i18n::Message;
+use crate::models::{
+    ActionsBot, ActionsConfig, BotAccount, DataExport, DataExportOptions, DataExportReport,
+    ExecutionLog, ExportFormat, GithubExportOptions, GithubExportReport, RequiredSecret,
+    ScanPolicy, SecretMode, SecretsManifest, UserSettings,
+};
+use crate::utils::git::{remotes, tracking_status, GitTracking};
+use crate::utils::vault::CredentialVault;
+
+use self::actions::{read_system_state, split_config, SYSTEM_STATE_FILE, USER_CONFIG_FILE};
+use self::scanner::{scan_payload, SecretLeakError};
+use self::schema::validate_actions_config;
+use self::versioning::{ACTIONS_CONFIG_VERSION, DATA_EXPORT_VERSION};
+use self::workflows::{workflows_directory, write_workflow_secrets};
+
+pub use self::data_import::import_data;
+pub use self::state_import::import_system_state;
+
+fn project_root() -> PathBuf {
+    if let Ok(custom) = std::env::var("TAM_EXPORT_ROOT") {
+        let candidate = PathBuf::from(custom);
+        if candidate.exists() {
+            return candidate;
+        }
+    }
+
+    let start = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let mut current = start.clone();
+
+    let is_root = |dir: &Path| {
+        dir.join("config").join("actions").exists() && dir.join("package.json").exists()
+    };
+
+    if is_root(&current) {
+        return current;
+    }
+
+    for _ in 0..8 {
+        if !current.pop() {
+            break;
+        }
+        if is_root(&current) {
+            return current;
+        }
+    }
+
+    start
+}
+
+fn normalize_path(path: &str) -> String {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return project_root().to_string_lossy().to_string();
+    }
+
+    let relative = trimmed.strip_prefix("./").unwrap_or(trimmed);
+    let candidate = Path::new(relative);
+
+    if candidate.is_absolute() {
+        return candidate.to_path_buf().to_string_lossy().to_string();
+    }
+
+    project_root().join(candidate).to_string_lossy().to_string()
+}
+
+fn ensure_parent_directory(path: &str) -> Result<(), AppError> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            AppError::Io(
+                Message::new("export.create_directory_failed")
+                    .with("path", parent.display())
+                    .with("detail", e),
+            )
+        })?;
+    }
+    Ok(())
+}
+
+fn collect_known_secrets(
+    conn: &Connection,
+    vault: &CredentialVault,
+) -> Result<Vec<String>, AppError> {
+    let accounts = fetch_all_bots(conn, vault)?;
+    Ok(accounts
+        .into_iter()
+        .flat_map(|account| {
+            [
+                account.api_key,
+                account.api_key_secret,
+                account.access_token,
+                account.access_token_secret,
+            ]
+        })
+        .collect())
+}
+
+/// Where a data export keeps each account's credentials. A backup has to
+/// carry them, so the scanner only looks for them everywhere else.
+const DATA_EXPORT_CREDENTIAL_PATHS: &[&str] = &[
+    "$.accounts[*].api_key",
+    "$.accounts[*].api_key_secret",
+    "$.accounts[*].access_token",
+    "$.accounts[*].access_token_secret",
+];
+
+/// The bot credentials in `user-config.json`, which a plaintext Actions
+/// export writes on purpose.
+const ACTIONS_CREDENTIAL_PATHS: &[&str] = &[
+    "$.bots[*].account.api_key",
+    "$.bots[*].account.api_key_secret",
+    "$.bots[*].account.access_token",
+    "$.bots[*].account.access_token_secret",
+];
+
+/// Runs the secret scanner over `payload` before it is written, skipping the
+/// `exempt` paths. Returns the JSON paths that were redacted, or an error
+/// when the policy is to abort.
+fn guard_payload(
+    conn: &Connection,
+    vault: &CredentialVault,
+    payload: &mut Value,
+    exempt: &[&str],
+    policy: ScanPolicy,
+    force: bool,
+) -> Result<Vec<String>, AppError> {
+    let known_secrets = collect_known_secrets(conn, vault)?;
+    let findings = scan_payload(payload, &known_secrets, exempt, policy);
+
+    if findings.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    match policy {
+        ScanPolicy::Redact => {
+            println!(
+                "Redacted {} credential-like values before export",
+                findings.len()
+            );
+            Ok(findings.into_iter().map(|finding| finding.path).collect())
+        }
+        ScanPolicy::Abort if force => {
+            println!(
+                "Writing {} credential-like values because the export was forced",
+                findings.len()
+            );
+            Ok(Vec::new())
+        }
+        ScanPolicy::Abort => Err(SecretLeakError { findings }.into()),
+    }
+}
+
+fn git_remote_warnings(path: &str) -> Vec<String> {
+    let path = Path::new(path);
+    if tracking_status(path) == GitTracking::Outside {
+        return Vec::new();
+    }
+
+    let remotes = remotes(path);
+    if remotes.is_empty() {
+        return Vec::new();
+    }
+
+    let warning = format!(
+        "{} is inside a git working tree with remote(s) {}; make sure it is never pushed",
+        path.display(),
+        remotes.join(", ")
+    );
+    println!("Warning: {}", warning);
+    vec![warning]
+}
+
+pub fn export_data(
+    conn: &Connection,
+    vault: &CredentialVault,
+    raw_path: &str,
+    options: &DataExportOptions,
+) -> Result<DataExportReport, AppError> {
+    let adjusted_path = normalize_path(raw_path);
+    ensure_parent_directory(&adjusted_path)?;
+
+    let accounts: Vec<BotAccount> = fetch_all_bots(conn, vault)?;
+
+    let scheduled_tweets = fetch_scheduled_tweets(conn, None)?;
+
+    let mut scheduled_posts = Vec::new();
+    for schedule_id in scheduled_tweets.iter().filter_map(|tweet| tweet.id) {
+        scheduled_posts.extend(fetch_scheduled_posts(conn, schedule_id)?);
+    }
+
+    let reply_settings = fetch_reply_settings(conn)?;
+
+    let mut logs_stmt =
+        conn.prepare("SELECT * FROM execution_logs ORDER BY created_at DESC LIMIT 1000")?;
+
+    let logs_rows = logs_stmt.query_map([], row_to_execution_log)?;
+
+    let logs: Vec<ExecutionLog> = logs_rows.collect::<SqliteResult<Vec<_>>>()?;
+
+    let user_settings = conn.query_row(
+        "SELECT * FROM user_settings WHERE user_id = 'default'",
+        [],
+        |row| {
+            Ok(UserSettings {
+                id: row.get(0)?,
+                user_id: row.get(1)?,
+                plan_type: row.get(2)?,
+                max_accounts: row.get(3)?,
+                created_at: row.get(4)?,
+                updated_at: row.get(5)?,
+            })
+        },
+    )?;
+
+    let mut export_payload = serde_json::to_value(DataExport {
+        format: ExportFormat::Data,
+        version: DATA_EXPORT_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        accounts,
+        scheduled_tweets,
+        scheduled_posts,
+        reply_settings,
+        logs,
+        user_settings: Some(user_settings),
+        redacted_paths: Vec::new(),
+    })?;
+
+    let redacted_paths = guard_payload(
+        conn,
+        vault,
+        &mut export_payload,
+        DATA_EXPORT_CREDENTIAL_PATHS,
+        options.scan_policy,
+        options.force,
+    )?;
+    if !redacted_paths.is_empty() {
+        export_payload["redacted_paths"] = serde_json::to_value(&redacted_paths)?;
+    }
+    let warnings = git_remote_warnings(&adjusted_path);
+
+    fs::write(
+        &adjusted_path,
+        serde_json::to_string_pretty(&export_payload)?,
+    )
+    .map_err(|e| {
+        AppError::Io(
+            Message::new("export.write_failed")
+                .with("path", &adjusted_path)
+                .with("detail", e),
+        )
+    })?;
+
+    println!("Data export completed: {}", adjusted_path);
+    Ok(DataExportReport {
+        path: adjusted_path,
+        redacted_paths,
+        warnings,
+    })
+}
+
+fn secret_reference_name(account_id: i64, field: &str) -> String {
+    format!("BOT_{}_{}", account_id, field.to_uppercase())
+}
+
+/// The directory the Actions config files go to. A path to a `.json` file,
+/// like the `github-config.json` earlier versions wrote, means its folder.
+fn actions_directory(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    let is_file = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+    match path.parent() {
+        Some(parent) if is_file => parent.to_path_buf(),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Pretty JSON with a trailing newline, as the Node scripts and workers
+/// write it, so re-exports do not churn the committed files.
+fn write_json_file<T: serde::Serialize>(path: &str, value: &T) -> Result<(), AppError> {
+    let json = format!("{}\n", serde_json::to_string_pretty(value)?);
+    fs::write(path, json).map_err(|e| {
+        AppError::Io(
+            Message::new("export.write_failed")
+                .with("path", path)
+                .with("detail", e),
+        )
+    })
+}
+
+fn manifest_path_for(config_path: &str) -> String {
+    let path = Path::new(config_path);
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "github-config".to_string());
+    path.with_file_name(format!("{}.secrets-manifest.json", stem))
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Swaps the account's credentials for `${{ secrets.BOT_<ID>_* }}` references
+/// and records the repository secrets the workflow will need.
+fn replace_with_secret_references(account: &mut BotAccount, required: &mut Vec<RequiredSecret>) {
+    let account_id = account.id.unwrap_or_default();
+    let account_name = account.account_name.clone();
+
+    for (field, value) in [
+        ("api_key", &mut account.api_key),
+        ("api_key_secret", &mut account.api_key_secret),
+        ("access_token", &mut account.access_token),
+        ("access_token_secret", &mut account.access_token_secret),
+    ] {
+        let name = secret_reference_name(account_id, field);
+        *value = format!("${{{{ secrets.{} }}}}", name);
+        required.push(RequiredSecret {
+            name,
+            account_id,
+            account_name: account_name.clone(),
+            field: field.to_string(),
+        });
+    }
+}
+
+pub fn export_github_config(
+    conn: &Connection,
+    vault: &CredentialVault,
+    raw_path: &str,
+    options: &GithubExportOptions,
+) -> Result<GithubExportReport, AppError> {
+    let actions_dir = actions_directory(&normalize_path(raw_path));
+    let config_path = actions_dir
+        .join(USER_CONFIG_FILE)
+        .to_string_lossy()
+        .to_string();
+    let state_path = actions_dir
+        .join(SYSTEM_STATE_FILE)
+        .to_string_lossy()
+        .to_string();
+
+    if options.secret_mode == SecretMode::Plaintext
+        && !options.allow_tracked
+        && tracking_status(Path::new(&config_path)) == GitTracking::Tracked
+    {
+        return Err(AppError::Validation(
+            Message::new("export.plaintext_tracked").with("path", &config_path),
+        ));
+    }
+
+    ensure_parent_directory(&config_path)?;
+    let previous_state = read_system_state(Path::new(&state_path))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT ba.*, st.id AS schedule_id, st.content AS schedule_content,
+                    st.current_index AS schedule_current_index, st.scheduled_times AS schedule_times
+         FROM bot_accounts ba 
+         LEFT JOIN scheduled_tweets st ON ba.id = st.account_id AND st.is_active = 1
+         WHERE ba.status = 'active'
+         ORDER BY ba.created_at DESC",
+    )?;
+
+    let rows = stmt.query_map([], |row| {
+        let account = row_to_bot_account(row)?;
+        let schedule_id: Option<i64> = row.get("schedule_id")?;
+        let scheduled_content: Option<String> = row.get("schedule_content")?;
+        let current_index: Option<i64> = row.get("schedule_current_index")?;
+        let scheduled_times: Option<String> = row.get("schedule_times")?;
+
+        Ok((
+            account,
+            schedule_id,
+            scheduled_content,
+            current_index,
+            scheduled_times,
+        ))
+    })?;
+
+    let mut bots: Vec<ActionsBot> = Vec::new();
+    let mut required_secrets: Vec<RequiredSecret> = Vec::new();
+    for (account, schedule_id, scheduled_content, current_index, scheduled_times) in
+        rows.collect::<SqliteResult<Vec<_>>>()?
+    {
+        let content_list_json = match schedule_id {
+            Some(schedule_id) => Some(enabled_content_list(conn, schedule_id)?),
+            None => None,
+        };
+
+        let account = match options.secret_mode {
+            SecretMode::Plaintext => decrypt_bot_account(vault, account)?,
+            SecretMode::References => {
+                let mut account = account;
+                replace_with_secret_references(&mut account, &mut required_secrets);
+                account
+            }
+        };
+
+        let bot = match content_list_json {
+            Some(content_list) => ActionsBot {
+                account,
+                scheduled_content_list: Some(content_list),
+                scheduled_content: None,
+                current_index: Some(current_index.unwrap_or(0)),
+                scheduled_times,
+            },
+            None => ActionsBot {
+                account,
+                scheduled_content_list: None,
+                scheduled_content,
+                current_index: None,
+                scheduled_times,
+            },
+        };
+
+        bots.push(bot);
+    }
+
+    let github_config = ActionsConfig {
+        format: ExportFormat::ActionsConfig.as_str().to_string(),
+        version: ACTIONS_CONFIG_VERSION.to_string(),
+        bots,
+        reply_settings: fetch_reply_settings(conn)?,
+        updated_at: Utc::now().to_rfc3339(),
+    };
+
+    let split = split_config(github_config, previous_state.as_ref());
+    let mut user_config = serde_json::to_value(&split.user_config)?;
+    // References mode has no credentials to exempt: anything the scanner
+    // finds there is a leak.
+    let exempt = match options.secret_mode {
+        SecretMode::Plaintext => ACTIONS_CREDENTIAL_PATHS,
+        SecretMode::References => &[],
+    };
+    let redacted_paths = guard_payload(
+        conn,
+        vault,
+        &mut user_config,
+        exempt,
+        options.scan_policy,
+        options.ignore_scan,
+    )?;
+    validate_actions_config(&user_config, &config_path)?;
+    // user-config.json is meant to be committed, so only plaintext
+    // credentials warrant the remote warning.
+    let mut warnings = match options.secret_mode {
+        SecretMode::Plaintext => git_remote_warnings(&config_path),
+        SecretMode::References => Vec::new(),
+    };
+
+    write_json_file(&config_path, &user_config)?;
+    write_json_file(&state_path, &split.state)?;
+
+    println!(
+        "GitHub Actions config exported: {} (state: {}, {} entries kept)",
+        config_path, state_path, split.preserved
+    );
+
+    let required_count = required_secrets.len();
+    let mut workflow_paths = Vec::new();
+    let manifest_path = match options.secret_mode {
+        SecretMode::Plaintext => None,
+        SecretMode::References => {
+            match workflows_directory(&actions_dir) {
+                Some(dir) => {
+                    let (written, workflow_warnings) =
+                        write_workflow_secrets(&dir, &required_secrets)?;
+                    workflow_paths = written;
+                    warnings.extend(workflow_warnings);
+                }
+                None => {
+                    let warning = format!(
+                        "No .github/workflows folder found above {}; map the secrets in the manifest into the workflow env yourself",
+                        actions_dir.display()
+                    );
+                    println!("Warning: {}", warning);
+                    warnings.push(warning);
+                }
+            }
+
+            let manifest_path = manifest_path_for(&config_path);
+            let manifest = SecretsManifest {
+                generated_at: Utc::now().to_rfc3339(),
+                config_path: config_path.clone(),
+                secrets: required_secrets,
+            };
+
+            fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?).map_err(|e| {
+                AppError::Io(
+                    Message::new("export.write_failed")
+                        .with("path", &manifest_path)
+                        .with("detail", e),
+                )
+            })?;
+
+            println!("Secrets manifest exported: {}", manifest_path);
+            Some(manifest_path)
+        }
+    };
+
+    Ok(GithubExportReport {
+        config_path,
+        state_path,
+        preserved_state_entries: split.preserved,
+        secret_mode: options.secret_mode,
+        manifest_path,
+        required_secrets: required_count,
+        workflow_paths,
+        redacted_paths,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::test_support::seeded_db;
+
+    #[test]
+    fn plaintext_actions_export_writes_credentials_under_the_default_scan() {
+        let dir = TempDir::new().unwrap();
+        let (conn, vault) = seeded_db(&dir);
+        let options = GithubExportOptions {
+            secret_mode: SecretMode::Plaintext,
+            ..Default::default()
+        };
+
+        let report =
+            export_github_config(&conn, &vault, &dir.path().to_string_lossy(), &options).unwrap();
+
+        assert!(report.redacted_paths.is_empty());
+        let written: Value =
+            serde_json::from_str(&fs::read_to_string(&report.config_path).unwrap()).unwrap();
+        assert_eq!(written["bots"][0]["account"]["api_key"], "consumer-key");
+        assert_eq!(
+            written["bots"][1]["account"]["access_token_secret"],
+            "access-token-secret"
+        );
+    }
+}
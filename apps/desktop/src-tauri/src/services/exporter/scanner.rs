@@ -0,0 +1,249 @@
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::models::ScanPolicy;
+
+pub const REDACTED: &str = "[REDACTED]";
+const MIN_STORED_SECRET_LEN: usize = 8;
+/// The secret half of an OAuth 1.0a access token, after `<user id>-`.
+const MIN_ACCESS_TOKEN_LEN: usize = 20;
+const BEARER_PREFIX: &str = "AAAAAAAAAAAAAAAAAAAA";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretKind {
+    /// Matches a credential currently stored in `bot_accounts`.
+    StoredCredential,
+    /// Looks like a Twitter OAuth 1.0a access token or app bearer token.
+    OAuthToken,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SecretFinding {
+    pub path: String,
+    pub kind: SecretKind,
+}
+
+#[derive(Debug, Error, Serialize)]
+#[error("Export aborted: {} credential-like value(s) found at {}", .findings.len(), format_paths(.findings))]
+pub struct SecretLeakError {
+    pub findings: Vec<SecretFinding>,
+}
+
+fn format_paths(findings: &[SecretFinding]) -> String {
+    findings
+        .iter()
+        .map(|finding| finding.path.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Scans every string in `payload` for stored credentials and values shaped
+/// like Twitter tokens. Other long strings are left alone: tweets, hashtags
+/// and URLs routinely look random.
+///
+/// Strings at the `exempt` paths, written with `[*]` for any array index
+/// (`$.accounts[*].api_key`), are where credentials belong and are skipped.
+/// With [`ScanPolicy::Redact`] the payload is rewritten in place.
+pub fn scan_payload(
+    payload: &mut Value,
+    known_secrets: &[String],
+    exempt: &[&str],
+    policy: ScanPolicy,
+) -> Vec<SecretFinding> {
+    let known: Vec<&str> = known_secrets
+        .iter()
+        .map(|secret| secret.trim())
+        .filter(|secret| secret.len() >= MIN_STORED_SECRET_LEN)
+        .collect();
+
+    let mut scan = Scan {
+        known: &known,
+        exempt,
+        redact: policy == ScanPolicy::Redact,
+        findings: Vec::new(),
+    };
+    scan.walk(payload, "$".to_string(), "$".to_string());
+    scan.findings
+}
+
+struct Scan<'a> {
+    known: &'a [&'a str],
+    exempt: &'a [&'a str],
+    redact: bool,
+    findings: Vec<SecretFinding>,
+}
+
+impl Scan<'_> {
+    /// `pattern` is `path` with every array index written as `[*]`.
+    fn walk(&mut self, value: &mut Value, path: String, pattern: String) {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map.iter_mut() {
+                    self.walk(
+                        child,
+                        format!("{}.{}", path, key),
+                        format!("{}.{}", pattern, key),
+                    );
+                }
+            }
+            Value::Array(items) => {
+                for (index, child) in items.iter_mut().enumerate() {
+                    self.walk(
+                        child,
+                        format!("{}[{}]", path, index),
+                        format!("{}[*]", pattern),
+                    );
+                }
+            }
+            Value::String(text) if !self.exempt.contains(&pattern.as_str()) => {
+                if let Some(kind) = inspect_string(text, self.known, self.redact) {
+                    self.findings.push(SecretFinding { path, kind });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn inspect_string(text: &mut String, known: &[&str], redact: bool) -> Option<SecretKind> {
+    let mut detected = None;
+
+    for secret in known {
+        if text.contains(secret) {
+            detected = Some(SecretKind::StoredCredential);
+            if redact {
+                *text = text.replace(secret, REDACTED);
+            }
+        }
+    }
+
+    let mut tokens = Vec::new();
+    for word in text.split(|c: char| !is_token_char(c)) {
+        if let Some(kind) = classify_token(word) {
+            detected.get_or_insert(kind);
+            tokens.push(word.to_string());
+        }
+    }
+
+    if redact {
+        for token in tokens {
+            *text = text.replace(&token, REDACTED);
+        }
+    }
+
+    detected
+}
+
+/// Bearer tokens are percent-encoded, so `%` keeps one in a single word.
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '%'
+}
+
+fn classify_token(word: &str) -> Option<SecretKind> {
+    if word.starts_with(BEARER_PREFIX) {
+        return Some(SecretKind::OAuthToken);
+    }
+
+    if let Some((user_id, token)) = word.split_once('-') {
+        if user_id.len() >= 5
+            && user_id.chars().all(|c| c.is_ascii_digit())
+            && token.len() >= MIN_ACCESS_TOKEN_LEN
+            && token.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return Some(SecretKind::OAuthToken);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const ACCESS_TOKEN: &str = "1234567890-AbCdEfGhIjKlMnOpQrStUvWxYz0123456789";
+    const BEARER_TOKEN: &str = "AAAAAAAAAAAAAAAAAAAAAMLheAAAAAAA0%2BuSeid%2BULvsea4JtiGRiSDSJSI";
+
+    #[test]
+    fn leaves_ordinary_posts_alone() {
+        let mut payload = json!({
+            "scheduled_posts": [
+                { "content": "#SummerSale2024LimitedOffer starts now! https://example.com/Campaign_2024_SpringLaunchABC" },
+                { "content": "Thanks @OpenSourceMaintainer99 for the release v2024-05-01_rc1XYZabc" },
+                { "content": "週末の #TokyoMarathon2024Results はこちら https://t.co/AbC123xYz9" }
+            ],
+            "logs": [{ "tweet_id": "1789012345678901234", "message": "posted" }]
+        });
+        let original = payload.clone();
+
+        let findings = scan_payload(&mut payload, &[], &[], ScanPolicy::Redact);
+
+        assert!(findings.is_empty(), "{:?}", findings);
+        assert_eq!(payload, original);
+    }
+
+    #[test]
+    fn redacts_stored_credentials_and_twitter_tokens() {
+        let mut payload = json!({
+            "logs": [
+                { "message": "signed with consumer-secret-value" },
+                { "message": format!("token {}", ACCESS_TOKEN) },
+                { "message": format!("Bearer {}", BEARER_TOKEN) }
+            ]
+        });
+        let known = vec!["consumer-secret-value".to_string(), "short".to_string()];
+
+        let findings = scan_payload(&mut payload, &known, &[], ScanPolicy::Redact);
+
+        let kinds: Vec<(&str, SecretKind)> = findings
+            .iter()
+            .map(|finding| (finding.path.as_str(), finding.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("$.logs[0].message", SecretKind::StoredCredential),
+                ("$.logs[1].message", SecretKind::OAuthToken),
+                ("$.logs[2].message", SecretKind::OAuthToken),
+            ]
+        );
+        assert_eq!(payload["logs"][0]["message"], "signed with [REDACTED]");
+        assert_eq!(payload["logs"][1]["message"], "token [REDACTED]");
+        assert_eq!(payload["logs"][2]["message"], "Bearer [REDACTED]");
+    }
+
+    #[test]
+    fn skips_exempt_paths() {
+        let mut payload = json!({
+            "accounts": [
+                { "api_key": "consumer-secret-value", "account_name": "consumer-secret-value" }
+            ]
+        });
+        let known = vec!["consumer-secret-value".to_string()];
+
+        let findings = scan_payload(
+            &mut payload,
+            &known,
+            &["$.accounts[*].api_key"],
+            ScanPolicy::Redact,
+        );
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].path, "$.accounts[0].account_name");
+        assert_eq!(payload["accounts"][0]["api_key"], "consumer-secret-value");
+    }
+
+    #[test]
+    fn abort_reports_without_rewriting() {
+        let mut payload = json!({ "note": ACCESS_TOKEN });
+
+        let findings = scan_payload(&mut payload, &[], &[], ScanPolicy::Abort);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(payload["note"], ACCESS_TOKEN);
+    }
+}
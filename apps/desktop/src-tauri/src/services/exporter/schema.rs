@@ -0,0 +1,134 @@
+//! JSON Schema of the Actions config. It is generated from `ActionsConfig`
+//! and checked into `packages/shared/schema`; nothing on the TypeScript side
+//! reads it yet, so the zod `ActionsConfigSchema` has to be kept in step by
+//! hand.
+
+use jsonschema::JSONSchema;
+use serde_json::Value;
+
+use crate::error::AppError;
+use crate::i18n::Message;
+
+/// The checked-in schema. A test keeps it in sync with the Rust types.
+pub const ACTIONS_CONFIG_SCHEMA: &str =
+    include_str!("../../../../../../packages/shared/schema/actions-config.schema.json");
+
+/// At most this many violations are listed in the error.
+const MAX_REPORTED_ERRORS: usize = 5;
+
+/// Checks a config about to be written to `path` against the checked-in
+/// schema. A violation is a bug in the exporter, not in the user's data.
+pub fn validate_actions_config(config: &Value, path: &str) -> Result<(), AppError> {
+    let schema: Value = serde_json::from_str(ACTIONS_CONFIG_SCHEMA)?;
+    let compiled = JSONSchema::compile(&schema).map_err(|e| schema_violation(path, e))?;
+
+    let result = compiled.validate(config);
+    if let Err(errors) = result {
+        let details: Vec<String> = errors
+            .take(MAX_REPORTED_ERRORS)
+            .map(|error| format!("{}: {}", error.instance_path, error))
+            .collect();
+        return Err(schema_violation(path, details.join("; ")));
+    }
+    Ok(())
+}
+
+fn schema_violation(path: &str, detail: impl std::fmt::Display) -> AppError {
+    AppError::Internal(
+        Message::new("export.schema_violation")
+            .with("path", path)
+            .with("detail", detail),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::models::ActionsConfig;
+
+    /// Regenerate with `TAM_UPDATE_SCHEMA=1 cargo test checked_in_schema`.
+    #[test]
+    fn checked_in_schema_is_up_to_date() {
+        let schema = schemars::schema_for!(ActionsConfig);
+        let generated = format!("{}\n", serde_json::to_string_pretty(&schema).unwrap());
+
+        if std::env::var_os("TAM_UPDATE_SCHEMA").is_some() {
+            let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("../../../packages/shared/schema/actions-config.schema.json");
+            std::fs::write(path, &generated).unwrap();
+            return;
+        }
+
+        assert!(
+            generated == ACTIONS_CONFIG_SCHEMA,
+            "packages/shared/schema/actions-config.schema.json is stale; regenerate it with TAM_UPDATE_SCHEMA=1"
+        );
+    }
+
+    fn exported_config() -> Value {
+        json!({
+            "format": "tam-actions-config",
+            "version": "2",
+            "bots": [
+                {
+                    "account": {
+                        "id": 1,
+                        "account_name": "first",
+                        "api_type": "Free",
+                        "api_key": "${{ secrets.BOT_1_API_KEY }}",
+                        "api_key_secret": "${{ secrets.BOT_1_API_KEY_SECRET }}",
+                        "access_token": "${{ secrets.BOT_1_ACCESS_TOKEN }}",
+                        "access_token_secret": "${{ secrets.BOT_1_ACCESS_TOKEN_SECRET }}",
+                        "status": "active",
+                        "credential_health": "valid"
+                    },
+                    "scheduled_content_list": "[\"a\",\"b\"]",
+                    "scheduled_times": "09:00"
+                }
+            ],
+            "reply_settings": [
+                {
+                    "id": 5,
+                    "target_bot_ids": "[2]",
+                    "reply_bot_id": 1,
+                    "reply_content": "hi",
+                    "is_active": true,
+                    "created_at": "2024-01-01T00:00:00Z",
+                    "updated_at": "2024-01-01T00:00:00Z"
+                }
+            ],
+            "updated_at": "2024-01-01T00:00:00Z"
+        })
+    }
+
+    #[test]
+    fn accepts_an_exported_config() {
+        validate_actions_config(&exported_config(), "user-config.json").unwrap();
+    }
+
+    #[test]
+    fn rejects_configs_the_workers_cannot_read() {
+        let mut config = exported_config();
+        config["bots"][0]["scheduled_content_list"] = json!(["a", "b"]);
+        config["bots"][0]["account"]["credential_health"] = json!("great");
+
+        match validate_actions_config(&config, "user-config.json") {
+            Err(AppError::Internal(message)) => {
+                let detail = format!("{:?}", message.params);
+                assert!(
+                    detail.contains("/bots/0/scheduled_content_list"),
+                    "{}",
+                    detail
+                );
+                assert!(
+                    detail.contains("/bots/0/account/credential_health"),
+                    "{}",
+                    detail
+                );
+            }
+            other => panic!("expected a schema violation, got {:?}", other),
+        }
+    }
+}
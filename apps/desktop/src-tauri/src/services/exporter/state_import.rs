@@ -0,0 +1,352 @@
+//! Reads `system-state.json` back into the database, so the posting and
+//! reply progress the workflows committed is not rolled back by the next
+//! export.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use rusqlite::Connection;
+
+use super::actions::{read_system_state, SYSTEM_STATE_FILE};
+use super::normalize_path;
+use crate::db::queries::bots::fetch_account_names;
+use crate::db::queries::replies::{fetch_reply_target_states, set_reply_target_checked};
+use crate::db::queries::schedules::{fetch_schedule_indices, set_schedule_index};
+use crate::error::AppError;
+use crate::i18n::Message;
+use crate::models::{
+    AccountConflict, BotStateChange, ReplySettingConflict, ReplyState, ReplyStateChange,
+    StateImportReport, SystemState, UnknownStateEntry,
+};
+
+/// Compares the state file at `raw_path` (the file itself or the folder
+/// holding it) with the database. With `apply` the differences are written
+/// in one transaction; otherwise nothing is changed.
+pub fn import_system_state(
+    conn: &Connection,
+    raw_path: &str,
+    apply: bool,
+) -> Result<StateImportReport, AppError> {
+    let path = state_file_path(&normalize_path(raw_path));
+    let display_path = path.to_string_lossy().to_string();
+    let state = read_system_state(&path)?.ok_or_else(|| {
+        AppError::NotFound(
+            Message::new("export.system_state_not_found").with("path", &display_path),
+        )
+    })?;
+
+    let mut report = diff_state(conn, &state, &display_path)?;
+
+    if apply {
+        let now = Utc::now().to_rfc3339();
+        let tx = conn.unchecked_transaction()?;
+        for change in &report.bot_changes {
+            set_schedule_index(&tx, change.schedule_id, change.current_index_after, &now)?;
+        }
+        for change in &report.reply_changes {
+            set_reply_target_checked(
+                &tx,
+                change.reply_setting_id,
+                change.target_bot_id,
+                &change.last_checked_after,
+                &now,
+            )?;
+        }
+        tx.commit()?;
+        report.applied = true;
+
+        println!(
+            "System state imported from {}: {} schedule(s), {} reply target(s) updated",
+            display_path,
+            report.bot_changes.len(),
+            report.reply_changes.len()
+        );
+    }
+
+    Ok(report)
+}
+
+fn state_file_path(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+    {
+        path.to_path_buf()
+    } else {
+        path.join(SYSTEM_STATE_FILE)
+    }
+}
+
+fn diff_state(
+    conn: &Connection,
+    state: &SystemState,
+    path: &str,
+) -> Result<StateImportReport, AppError> {
+    let mut report = StateImportReport {
+        path: path.to_string(),
+        applied: false,
+        bot_changes: Vec::new(),
+        reply_changes: Vec::new(),
+        conflicts: Vec::new(),
+        reply_conflicts: Vec::new(),
+        unknown: Vec::new(),
+    };
+
+    let accounts = fetch_account_names(conn)?;
+    let schedules: HashMap<i64, (i64, i64)> = fetch_schedule_indices(conn)?
+        .into_iter()
+        .map(|(account_id, schedule_id, index)| (account_id, (schedule_id, index)))
+        .collect();
+
+    for entry in &state.bot_state {
+        let by_id = entry
+            .account_id
+            .and_then(|id| accounts.iter().find(|(account_id, _)| *account_id == id));
+        let by_name = entry.account_name.as_ref().and_then(|name| {
+            accounts
+                .iter()
+                .find(|(_, account_name)| account_name == name)
+        });
+
+        // An id and a name that disagree may mean the file was written for a
+        // different database; applying either match could move the wrong
+        // account's schedule, so the entry is only reported.
+        if let (Some(state_id), Some(state_name)) = (entry.account_id, &entry.account_name) {
+            let same_account = matches!((by_id, by_name), (Some((a, _)), Some((b, _))) if a == b);
+            if (by_id.is_some() || by_name.is_some()) && !same_account {
+                report.conflicts.push(AccountConflict {
+                    state_account_id: state_id,
+                    state_account_name: state_name.clone(),
+                    name_for_id: by_id.map(|(_, name)| name.clone()),
+                    id_for_name: by_name.map(|(id, _)| *id),
+                });
+                continue;
+            }
+        }
+
+        let Some((account_id, account_name)) = by_id.or(by_name) else {
+            report.unknown.push(UnknownStateEntry::Account {
+                account_id: entry.account_id,
+                account_name: entry.account_name.clone(),
+            });
+            continue;
+        };
+
+        match schedules.get(account_id) {
+            Some(&(schedule_id, current_index)) => {
+                let imported_index = entry.current_index.max(0);
+                if imported_index != current_index {
+                    report.bot_changes.push(BotStateChange {
+                        account_id: *account_id,
+                        account_name: account_name.clone(),
+                        schedule_id,
+                        current_index_before: current_index,
+                        current_index_after: imported_index,
+                    });
+                }
+            }
+            None => report.unknown.push(UnknownStateEntry::Schedule {
+                account_id: *account_id,
+                account_name: account_name.clone(),
+            }),
+        }
+    }
+
+    let targets = fetch_reply_target_states(conn)?;
+    for entry in &state.reply_state {
+        let by_id = entry
+            .id
+            .and_then(|id| targets.iter().find(|target| target.reply_setting_id == id));
+        let by_bot = entry
+            .reply_bot_id
+            .and_then(|bot_id| targets.iter().find(|target| target.reply_bot_id == bot_id));
+
+        // As with accounts above: a setting id and reply bot that disagree
+        // are reported rather than applied to either match.
+        if let (Some(state_id), Some(state_reply_bot_id)) = (entry.id, entry.reply_bot_id) {
+            let agrees = by_id.is_some_and(|target| target.reply_bot_id == state_reply_bot_id);
+            if (by_id.is_some() || by_bot.is_some()) && !agrees {
+                report.reply_conflicts.push(ReplySettingConflict {
+                    state_id,
+                    state_reply_bot_id,
+                    reply_bot_for_id: by_id.map(|target| target.reply_bot_id),
+                });
+                continue;
+            }
+        }
+
+        let setting = by_id
+            .or(by_bot)
+            .map(|target| (target.reply_setting_id, target.reply_bot_id));
+
+        let Some((reply_setting_id, reply_bot_id)) = setting else {
+            report.unknown.push(UnknownStateEntry::ReplySetting {
+                id: entry.id,
+                reply_bot_id: entry.reply_bot_id,
+            });
+            continue;
+        };
+
+        for (target_bot_id, tweet_id) in last_checked_entries(entry, path)? {
+            let target = targets.iter().find(|target| {
+                target.reply_setting_id == reply_setting_id && target.target_bot_id == target_bot_id
+            });
+
+            match target {
+                Some(target) if target.last_checked_tweet_id.as_deref() != Some(&tweet_id) => {
+                    report.reply_changes.push(ReplyStateChange {
+                        reply_setting_id,
+                        reply_bot_id,
+                        target_bot_id,
+                        last_checked_before: target.last_checked_tweet_id.clone(),
+                        last_checked_after: tweet_id,
+                    });
+                }
+                Some(_) => {}
+                None => report.unknown.push(UnknownStateEntry::ReplyTarget {
+                    reply_setting_id,
+                    target_bot_id,
+                }),
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+fn last_checked_entries(entry: &ReplyState, path: &str) -> Result<Vec<(i64, String)>, AppError> {
+    let tokens: Vec<String> = serde_json::from_str(&entry.last_checked_tweet_ids).map_err(|e| {
+        AppError::Validation(
+            Message::new("export.invalid_system_state")
+                .with("path", path)
+                .with("detail", e),
+        )
+    })?;
+
+    Ok(parse_last_checked(&tokens))
+}
+
+/// Parses `"<target_bot_id>:<tweet_id>"` entries, skipping malformed ones
+/// the same way the reply worker does.
+pub(super) fn parse_last_checked(tokens: &[String]) -> Vec<(i64, String)> {
+    tokens
+        .iter()
+        .filter_map(|token| {
+            let (target_bot_id, tweet_id) = token.split_once(':')?;
+            let target_bot_id = target_bot_id.trim().parse().ok()?;
+            let tweet_id = tweet_id.trim();
+            (!tweet_id.is_empty()).then(|| (target_bot_id, tweet_id.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use rusqlite::params;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::db::queries::replies::fetch_reply_settings;
+    use crate::test_support::seeded_db;
+
+    fn write_state(dir: &TempDir, state: serde_json::Value) -> String {
+        let path = dir.path().join(SYSTEM_STATE_FILE);
+        fs::write(&path, state.to_string()).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    fn schedule_index(conn: &Connection) -> i64 {
+        conn.query_row(
+            "SELECT current_index FROM scheduled_tweets WHERE account_id = ? AND is_active = 1",
+            params![1],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn previews_and_applies_worker_progress() {
+        let dir = TempDir::new().unwrap();
+        let (conn, _vault) = seeded_db(&dir);
+        let path = write_state(
+            &dir,
+            serde_json::json!({
+                "bot_state": [
+                    { "account_id": 1, "account_name": "first", "current_index": 2 },
+                    { "account_id": 2, "account_name": "second", "current_index": 0 },
+                    { "account_id": 9, "account_name": "gone", "current_index": 4 },
+                    { "account_id": 2, "account_name": "first", "current_index": 5 }
+                ],
+                "reply_state": [
+                    { "id": 1, "reply_bot_id": 1, "last_checked_tweet_ids": "[\"2:555\",\"7:1\"]" },
+                    { "id": 1, "reply_bot_id": 2, "last_checked_tweet_ids": "[\"2:999\"]" }
+                ]
+            }),
+        );
+
+        let preview = import_system_state(&conn, &path, false).unwrap();
+        assert!(!preview.applied);
+        assert_eq!(preview.bot_changes.len(), 1);
+        assert_eq!(preview.bot_changes[0].current_index_before, 0);
+        assert_eq!(preview.bot_changes[0].current_index_after, 2);
+        assert_eq!(preview.reply_changes.len(), 1);
+        assert_eq!(preview.reply_changes[0].last_checked_after, "555");
+        assert_eq!(preview.conflicts.len(), 1);
+        assert_eq!(preview.conflicts[0].name_for_id.as_deref(), Some("second"));
+        assert_eq!(preview.conflicts[0].id_for_name, Some(1));
+        assert_eq!(preview.reply_conflicts.len(), 1);
+        assert_eq!(preview.reply_conflicts[0].reply_bot_for_id, Some(1));
+        // account 2 has no schedule, 9 does not exist, 7 is not a target
+        assert_eq!(preview.unknown.len(), 3);
+        assert_eq!(schedule_index(&conn), 0);
+
+        let applied = import_system_state(&conn, &path, true).unwrap();
+        assert!(applied.applied);
+        assert_eq!(schedule_index(&conn), 2);
+        let settings = fetch_reply_settings(&conn).unwrap();
+        assert_eq!(
+            settings[0].last_checked_tweet_ids.as_deref(),
+            Some("[\"2:555\"]")
+        );
+
+        let again = import_system_state(&conn, &path, false).unwrap();
+        assert!(again.bot_changes.is_empty());
+        assert!(again.reply_changes.is_empty());
+    }
+
+    #[test]
+    fn id_match_with_another_name_is_not_applied() {
+        let dir = TempDir::new().unwrap();
+        let (conn, _vault) = seeded_db(&dir);
+        let path = write_state(
+            &dir,
+            serde_json::json!({
+                "bot_state": [
+                    { "account_id": 1, "account_name": "old_first", "current_index": 2 }
+                ],
+                "reply_state": []
+            }),
+        );
+
+        let report = import_system_state(&conn, &path, true).unwrap();
+        assert!(report.bot_changes.is_empty());
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].name_for_id.as_deref(), Some("first"));
+        assert_eq!(report.conflicts[0].id_for_name, None);
+        assert_eq!(schedule_index(&conn), 0);
+    }
+
+    #[test]
+    fn missing_state_file_is_not_found() {
+        let dir = TempDir::new().unwrap();
+        let (conn, _vault) = seeded_db(&dir);
+        let path = dir.path().to_string_lossy().to_string();
+
+        let result = import_system_state(&conn, &path, false);
+        assert!(matches!(result, Err(AppError::NotFound(_))), "{:?}", result);
+    }
+}
@@ -0,0 +1,321 @@
+//! The `format` and `version` every export carries, and the steps that bring
+//! a file written by an older version of the app up to the current one.
+//!
+//! Files from before versioning have neither field; their format and
+//! version are inferred from their shape.
+
+use serde_json::{json, Map, Value};
+
+use crate::error::AppError;
+use crate::i18n::Message;
+use crate::models::ExportFormat;
+
+/// 1: one `content` per schedule. 2: `content_list` per schedule.
+/// 3: `scheduled_posts` rows.
+pub const DATA_EXPORT_VERSION: u32 = 3;
+/// 1: `scheduled_content` per bot. 2: `scheduled_content_list` per bot.
+pub const ACTIONS_CONFIG_VERSION: u32 = 2;
+
+type Step = fn(&mut Map<String, Value>);
+
+/// Upgrades from version `n` are at index `n - 1`.
+const DATA_EXPORT_STEPS: &[(&str, Step)] = &[
+    ("content_to_content_list", content_to_content_list),
+    (
+        "content_list_to_scheduled_posts",
+        content_list_to_scheduled_posts,
+    ),
+];
+const ACTIONS_CONFIG_STEPS: &[(&str, Step)] =
+    &[("scheduled_content_to_list", scheduled_content_to_list)];
+
+/// An export document brought up to the current version of its format.
+#[derive(Debug)]
+pub struct Upgraded {
+    pub format: ExportFormat,
+    pub source_version: u32,
+    pub steps: Vec<String>,
+    pub document: Value,
+}
+
+pub fn current_version(format: ExportFormat) -> u32 {
+    match format {
+        ExportFormat::Data => DATA_EXPORT_VERSION,
+        ExportFormat::ActionsConfig => ACTIONS_CONFIG_VERSION,
+    }
+}
+
+fn steps(format: ExportFormat) -> &'static [(&'static str, Step)] {
+    match format {
+        ExportFormat::Data => DATA_EXPORT_STEPS,
+        ExportFormat::ActionsConfig => ACTIONS_CONFIG_STEPS,
+    }
+}
+
+/// Detects the format and version of `document` (read from `path`) and
+/// applies each upgrade step in turn. Files from a newer version are refused
+/// rather than read with fields silently dropped.
+pub fn upgrade(document: Value, path: &str) -> Result<Upgraded, AppError> {
+    let Value::Object(mut object) = document else {
+        return Err(unknown_format(path));
+    };
+
+    let format = detect_format(&object).ok_or_else(|| unknown_format(path))?;
+    let source_version = match object.get("version") {
+        Some(version) => parse_version(version).ok_or_else(|| {
+            AppError::Validation(
+                Message::new("import.invalid_version")
+                    .with("path", path)
+                    .with("version", version),
+            )
+        })?,
+        None => infer_version(format, &object),
+    };
+
+    let current = current_version(format);
+    if source_version > current {
+        return Err(AppError::Validation(
+            Message::new("import.newer_version")
+                .with("path", path)
+                .with("format", format.as_str())
+                .with("version", source_version)
+                .with("supported", current),
+        ));
+    }
+
+    let mut applied = Vec::new();
+    for (name, step) in &steps(format)[(source_version - 1) as usize..] {
+        step(&mut object);
+        applied.push(name.to_string());
+    }
+
+    object.insert("format".to_string(), json!(format.as_str()));
+    object.insert(
+        "version".to_string(),
+        match format {
+            ExportFormat::Data => json!(current),
+            ExportFormat::ActionsConfig => json!(current.to_string()),
+        },
+    );
+
+    Ok(Upgraded {
+        format,
+        source_version,
+        steps: applied,
+        document: Value::Object(object),
+    })
+}
+
+fn unknown_format(path: &str) -> AppError {
+    AppError::Validation(Message::new("import.unknown_format").with("path", path))
+}
+
+fn detect_format(object: &Map<String, Value>) -> Option<ExportFormat> {
+    match object.get("format") {
+        Some(format) => serde_json::from_value(format.clone()).ok(),
+        None if object.contains_key("accounts") => Some(ExportFormat::Data),
+        None if object.contains_key("bots") => Some(ExportFormat::ActionsConfig),
+        None => None,
+    }
+}
+
+/// Accepts `3`, `"3"` and the `"1.0"` the Actions config used to carry;
+/// only the major version counts.
+fn parse_version(version: &Value) -> Option<u32> {
+    let version = match version {
+        Value::Number(number) => number.as_u64()?,
+        Value::String(text) => text.trim().split('.').next()?.parse().ok()?,
+        _ => return None,
+    };
+    u32::try_from(version).ok().filter(|version| *version >= 1)
+}
+
+fn infer_version(format: ExportFormat, object: &Map<String, Value>) -> u32 {
+    match format {
+        ExportFormat::Data if object.contains_key("scheduled_posts") => 3,
+        ExportFormat::Data => {
+            let has_lists = entries(object, "scheduled_tweets").any(|tweet| {
+                tweet
+                    .get("content_list")
+                    .is_some_and(|list| !list.is_null())
+            });
+            if has_lists {
+                2
+            } else {
+                1
+            }
+        }
+        ExportFormat::ActionsConfig => 1,
+    }
+}
+
+fn entries<'a>(
+    object: &'a Map<String, Value>,
+    key: &str,
+) -> impl Iterator<Item = &'a Map<String, Value>> {
+    object
+        .get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object)
+}
+
+fn entries_mut<'a>(
+    object: &'a mut Map<String, Value>,
+    key: &str,
+) -> impl Iterator<Item = &'a mut Map<String, Value>> {
+    object
+        .get_mut(key)
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object_mut)
+}
+
+/// Data 1 → 2: a schedule's single `content` becomes a one-post list.
+fn content_to_content_list(object: &mut Map<String, Value>) {
+    for tweet in entries_mut(object, "scheduled_tweets") {
+        if tweet
+            .get("content_list")
+            .is_some_and(|list| !list.is_null())
+        {
+            continue;
+        }
+        let content = tweet.get("content").and_then(Value::as_str).unwrap_or("");
+        let list = json!([content]).to_string();
+        tweet.insert("content_list".to_string(), Value::String(list));
+    }
+}
+
+/// Data 2 → 3: each schedule's `content_list` becomes `scheduled_posts`
+/// rows, the way the `create_scheduled_posts` migration split them.
+fn content_list_to_scheduled_posts(object: &mut Map<String, Value>) {
+    let mut posts = Vec::new();
+    for tweet in entries(object, "scheduled_tweets") {
+        let list: Vec<String> = tweet
+            .get("content_list")
+            .and_then(Value::as_str)
+            .and_then(|list| serde_json::from_str(list).ok())
+            .unwrap_or_default();
+
+        for (ordinal, content) in list.into_iter().enumerate() {
+            posts.push(json!({
+                "id": null,
+                "schedule_id": tweet.get("id").cloned().unwrap_or(Value::Null),
+                "ordinal": ordinal,
+                "content": content,
+                "is_enabled": true,
+                "last_posted_at": null,
+                "post_count": 0,
+                "created_at": tweet.get("created_at").cloned().unwrap_or(Value::Null),
+                "updated_at": tweet.get("updated_at").cloned().unwrap_or(Value::Null),
+            }));
+        }
+    }
+    object.insert("scheduled_posts".to_string(), Value::Array(posts));
+}
+
+/// Actions 1 → 2: a bot's single `scheduled_content` becomes a one-post
+/// `scheduled_content_list`.
+fn scheduled_content_to_list(object: &mut Map<String, Value>) {
+    for bot in entries_mut(object, "bots") {
+        if bot
+            .get("scheduled_content_list")
+            .is_some_and(|list| !list.is_null())
+        {
+            continue;
+        }
+        let Some(content) = bot.remove("scheduled_content") else {
+            continue;
+        };
+        if let Some(content) = content.as_str() {
+            let list = json!([content]).to_string();
+            bot.insert("scheduled_content_list".to_string(), Value::String(list));
+            bot.entry("current_index").or_insert(json!(0));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upgrades_single_content_data_exports_step_by_step() {
+        let legacy = json!({
+            "exported_at": "2023-01-01T00:00:00Z",
+            "accounts": [],
+            "scheduled_tweets": [
+                {
+                    "id": 4,
+                    "account_id": 1,
+                    "content": "hello",
+                    "scheduled_times": "09:00",
+                    "is_active": true,
+                    "created_at": "2023-01-01T00:00:00Z",
+                    "updated_at": "2023-01-01T00:00:00Z"
+                }
+            ]
+        });
+
+        let upgraded = upgrade(legacy, "backup.json").unwrap();
+
+        assert_eq!(upgraded.format, ExportFormat::Data);
+        assert_eq!(upgraded.source_version, 1);
+        assert_eq!(
+            upgraded.steps,
+            ["content_to_content_list", "content_list_to_scheduled_posts"]
+        );
+        let document = upgraded.document;
+        assert_eq!(document["version"], DATA_EXPORT_VERSION);
+        assert_eq!(document["format"], "tam-data-export");
+        assert_eq!(
+            document["scheduled_tweets"][0]["content_list"],
+            "[\"hello\"]"
+        );
+        assert_eq!(document["scheduled_posts"][0]["schedule_id"], 4);
+        assert_eq!(document["scheduled_posts"][0]["content"], "hello");
+    }
+
+    #[test]
+    fn upgrades_actions_configs_from_scheduled_content() {
+        let legacy = json!({
+            "version": "1.0",
+            "bots": [
+                { "account": { "id": 1 }, "scheduled_content": "hi", "scheduled_times": "09:00" },
+                { "account": { "id": 2 }, "scheduled_content_list": "[\"a\"]", "current_index": 0 }
+            ]
+        });
+
+        let upgraded = upgrade(legacy, "user-config.json").unwrap();
+
+        assert_eq!(upgraded.format, ExportFormat::ActionsConfig);
+        assert_eq!(upgraded.source_version, 1);
+        let bots = &upgraded.document["bots"];
+        assert_eq!(bots[0]["scheduled_content_list"], "[\"hi\"]");
+        assert!(bots[0].get("scheduled_content").is_none());
+        assert_eq!(bots[1]["scheduled_content_list"], "[\"a\"]");
+        assert_eq!(upgraded.document["version"], "2");
+    }
+
+    #[test]
+    fn current_files_pass_through_and_newer_ones_are_refused() {
+        let current = json!({ "format": "tam-data-export", "version": 3, "accounts": [] });
+        let upgraded = upgrade(current, "backup.json").unwrap();
+        assert_eq!(upgraded.source_version, DATA_EXPORT_VERSION);
+        assert!(upgraded.steps.is_empty());
+
+        let newer = json!({ "format": "tam-actions-config", "version": "3", "bots": [] });
+        assert!(matches!(
+            upgrade(newer, "user-config.json"),
+            Err(AppError::Validation(message)) if message.id == "import.newer_version"
+        ));
+
+        let unknown = json!({ "format": "something-else", "version": 1 });
+        assert!(matches!(
+            upgrade(unknown, "other.json"),
+            Err(AppError::Validation(message)) if message.id == "import.unknown_format"
+        ));
+    }
+}
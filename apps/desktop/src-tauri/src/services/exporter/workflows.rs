@@ -0,0 +1,154 @@
+//! Keeps the `env:` block of the bot workflows in step with the secrets
+//! manifest. Each `BOT_<ID>_*` secret the exported config references is
+//! mapped into an env var of the same name, so the jobs never see any other
+//! repository secret.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::AppError;
+use crate::i18n::Message;
+use crate::models::RequiredSecret;
+
+/// Workflows that run the workers against the exported config.
+pub const WORKFLOW_FILES: &[&str] = &["auto-tweet.yml", "reply-monitor.yml"];
+
+const BEGIN_MARKER: &str = "# BEGIN BOT SECRETS";
+const END_MARKER: &str = "# END BOT SECRETS";
+
+/// The `.github/workflows` folder of the repository `actions_dir` sits in.
+pub fn workflows_directory(actions_dir: &Path) -> Option<PathBuf> {
+    actions_dir
+        .ancestors()
+        .map(|dir| dir.join(".github").join("workflows"))
+        .find(|dir| dir.is_dir())
+}
+
+/// Rewrites the marked secrets block of each workflow in `dir` to map
+/// exactly `secrets`. Returns the files that changed and a warning for each
+/// workflow that exists but has no block to fill in.
+pub fn write_workflow_secrets(
+    dir: &Path,
+    secrets: &[RequiredSecret],
+) -> Result<(Vec<String>, Vec<String>), AppError> {
+    let mut written = Vec::new();
+    let mut warnings = Vec::new();
+
+    for file in WORKFLOW_FILES {
+        let path = dir.join(file);
+        let display_path = path.to_string_lossy().to_string();
+        if !path.is_file() {
+            continue;
+        }
+
+        let workflow = fs::read_to_string(&path).map_err(|e| {
+            AppError::Io(
+                Message::new("export.read_failed")
+                    .with("path", &display_path)
+                    .with("detail", e),
+            )
+        })?;
+
+        let Some(updated) = replace_secret_block(&workflow, secrets) else {
+            let warning = format!(
+                "{} has no \"{}\" / \"{}\" block; add one to its env so the bot secrets are passed to the job",
+                display_path, BEGIN_MARKER, END_MARKER
+            );
+            println!("Warning: {}", warning);
+            warnings.push(warning);
+            continue;
+        };
+
+        if updated != workflow {
+            fs::write(&path, updated).map_err(|e| {
+                AppError::Io(
+                    Message::new("export.write_failed")
+                        .with("path", &display_path)
+                        .with("detail", e),
+                )
+            })?;
+            println!("Workflow secrets updated: {}", display_path);
+            written.push(display_path);
+        }
+    }
+
+    Ok((written, warnings))
+}
+
+/// Replaces the lines between the markers with one `NAME: ${{ secrets.NAME }}`
+/// entry per secret, indented like the begin marker. `None` if the markers
+/// are missing or out of order.
+fn replace_secret_block(workflow: &str, secrets: &[RequiredSecret]) -> Option<String> {
+    let lines: Vec<&str> = workflow.split_inclusive('\n').collect();
+    let begin = lines
+        .iter()
+        .position(|line| line.trim().starts_with(BEGIN_MARKER))?;
+    let end = begin
+        + 1
+        + lines[begin + 1..]
+            .iter()
+            .position(|line| line.trim().starts_with(END_MARKER))?;
+
+    let begin_line = lines[begin];
+    let indent = &begin_line[..begin_line.len() - begin_line.trim_start().len()];
+
+    let mut updated: String = lines[..=begin].concat();
+    for secret in secrets {
+        updated.push_str(&format!(
+            "{}{}: ${{{{ secrets.{} }}}}\n",
+            indent, secret.name, secret.name
+        ));
+    }
+    updated.push_str(&lines[end..].concat());
+    Some(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secret(name: &str) -> RequiredSecret {
+        RequiredSecret {
+            name: name.to_string(),
+            account_id: 1,
+            account_name: "first".to_string(),
+            field: "api_key".to_string(),
+        }
+    }
+
+    #[test]
+    fn fills_the_marked_block_and_drops_stale_entries() {
+        let workflow = "env:\n  LOG_LEVEL: info\n  # BEGIN BOT SECRETS\n  BOT_9_API_KEY: ${{ secrets.BOT_9_API_KEY }}\n  # END BOT SECRETS\nnext: 1\n";
+
+        let updated = replace_secret_block(
+            workflow,
+            &[secret("BOT_1_API_KEY"), secret("BOT_2_API_KEY")],
+        )
+        .unwrap();
+
+        assert_eq!(
+            updated,
+            "env:\n  LOG_LEVEL: info\n  # BEGIN BOT SECRETS\n  BOT_1_API_KEY: ${{ secrets.BOT_1_API_KEY }}\n  BOT_2_API_KEY: ${{ secrets.BOT_2_API_KEY }}\n  # END BOT SECRETS\nnext: 1\n"
+        );
+    }
+
+    #[test]
+    fn needs_both_markers() {
+        assert!(replace_secret_block("env:\n  # BEGIN BOT SECRETS\n", &[]).is_none());
+        assert!(replace_secret_block("env:\n  TAM: 1\n", &[]).is_none());
+    }
+
+    #[test]
+    fn checked_in_workflows_have_a_secrets_block() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../../.github/workflows");
+        for file in WORKFLOW_FILES {
+            let workflow = fs::read_to_string(dir.join(file)).unwrap();
+            assert!(
+                replace_secret_block(&workflow, &[]).is_some(),
+                "{} is missing its secrets block",
+                file
+            );
+            assert!(!workflow.contains("toJSON(secrets)"), "{}", file);
+        }
+    }
+}
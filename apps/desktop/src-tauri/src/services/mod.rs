@@ -1,5 +1,6 @@
 ﻿pub mod exporter;
+pub mod quota;
 pub mod twitter;
 
-pub use exporter::{export_data, export_github_config};
-pub use twitter::post_to_twitter;
+pub use exporter::{export_data, export_github_config, import_data, import_system_state};
+pub use twitter::{TwitterApi, TwitterClient};
@@ -0,0 +1,45 @@
+//! Monthly post budgets per API tier, enforced before each post.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+
+use crate::db::queries::quota::find_quota_usage;
+use crate::error::AppError;
+use crate::i18n::Message;
+use crate::models::QuotaStatus;
+
+/// The ledger month a timestamp falls in.
+pub fn quota_month(now: DateTime<Utc>) -> String {
+    now.format("%Y-%m").to_string()
+}
+
+/// Checks that `account_id` may post once more this month. Fails with
+/// [`AppError::QuotaExceeded`] when the post would go over the tier's budget,
+/// and returns a warning to log when it brings usage to 90% or more.
+pub fn check_post_quota(
+    conn: &Connection,
+    account_id: i64,
+    now: DateTime<Utc>,
+) -> Result<Option<Message>, AppError> {
+    let usage = find_quota_usage(conn, account_id, &quota_month(now))?;
+
+    if usage.posts_used >= usage.post_budget {
+        return Err(AppError::QuotaExceeded {
+            api_type: usage.api_type,
+            month: usage.month,
+            used: usage.posts_used,
+            budget: usage.post_budget,
+        });
+    }
+
+    let used = usage.posts_used + 1;
+    match QuotaStatus::of(used, usage.post_budget) {
+        QuotaStatus::Ok => Ok(None),
+        QuotaStatus::Warning | QuotaStatus::Exhausted => Ok(Some(
+            Message::new("quota.post_warning")
+                .with("api_type", usage.api_type)
+                .with("used", used)
+                .with("budget", usage.post_budget),
+        )),
+    }
+}
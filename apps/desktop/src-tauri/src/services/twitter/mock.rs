@@ -0,0 +1,251 @@
+//! A local stand-in for the Twitter API v2 that replays canned responses, so
+//! the client and the commands built on it can be tested offline.
+
+use std::collections::VecDeque;
+use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex};
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use serde_json::json;
+use tokio::sync::oneshot;
+
+/// A response the mock server sends back, in the shape the v2 API uses.
+#[derive(Debug, Clone)]
+pub struct CannedResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl CannedResponse {
+    pub fn new(status: u16, body: serde_json::Value) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.to_string(),
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// `201 Created` from `POST /2/tweets`.
+    pub fn created(tweet_id: &str, text: &str) -> Self {
+        Self::new(
+            201,
+            json!({ "data": { "id": tweet_id, "text": text, "edit_history_tweet_ids": [tweet_id] } }),
+        )
+    }
+
+    /// `200 OK` from `GET /2/users/me`.
+    pub fn user(id: &str, username: &str, name: &str) -> Self {
+        Self::new(
+            200,
+            json!({ "data": { "id": id, "name": name, "username": username } }),
+        )
+    }
+
+    /// `200 OK` from `GET /2/users/:id/tweets` listing `(id, text)` pairs.
+    pub fn user_tweets(tweets: &[(&str, &str)]) -> Self {
+        if tweets.is_empty() {
+            return Self::new(200, json!({ "meta": { "result_count": 0 } }));
+        }
+        let data: Vec<_> = tweets
+            .iter()
+            .map(|(id, text)| json!({ "id": id, "text": text, "edit_history_tweet_ids": [id] }))
+            .collect();
+        Self::new(
+            200,
+            json!({ "data": data, "meta": { "result_count": tweets.len() } }),
+        )
+    }
+
+    /// `401` for a bad or revoked token.
+    pub fn unauthorized() -> Self {
+        Self::new(
+            401,
+            json!({
+                "title": "Unauthorized",
+                "type": "about:blank",
+                "status": 401,
+                "detail": "Unauthorized"
+            }),
+        )
+    }
+
+    /// `403` for a tweet identical to one the account posted recently.
+    pub fn duplicate_content() -> Self {
+        Self::new(
+            403,
+            json!({
+                "detail": "You are not allowed to create a Tweet with duplicate content.",
+                "type": "about:blank",
+                "title": "Forbidden",
+                "status": 403
+            }),
+        )
+    }
+
+    /// `403` for an account Twitter has suspended, in the v1.1 `errors` shape
+    /// some endpoints still use.
+    pub fn suspended() -> Self {
+        Self::new(
+            403,
+            json!({
+                "errors": [{
+                    "code": 64,
+                    "message": "Your account is suspended and is not permitted to access this feature."
+                }]
+            }),
+        )
+    }
+
+    /// `429` with the rate-limit headers set; `reset` is a Unix timestamp.
+    pub fn rate_limited(reset: i64) -> Self {
+        Self::new(
+            429,
+            json!({
+                "title": "Too Many Requests",
+                "detail": "Too Many Requests",
+                "type": "about:blank",
+                "status": 429
+            }),
+        )
+        .with_header("x-rate-limit-limit", "200")
+        .with_header("x-rate-limit-remaining", "0")
+        .with_header("x-rate-limit-reset", &reset.to_string())
+    }
+
+    /// A `5xx` such as 503 while the API is over capacity.
+    pub fn server_error(status: u16) -> Self {
+        Self::new(
+            status,
+            json!({
+                "title": "Service Unavailable",
+                "detail": "Service Unavailable",
+                "type": "about:blank",
+                "status": status
+            }),
+        )
+    }
+}
+
+/// A request the mock server received.
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub authorization: Option<String>,
+    pub body: String,
+}
+
+#[derive(Clone, Default)]
+struct MockState {
+    responses: Arc<Mutex<VecDeque<CannedResponse>>>,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+/// Serves canned responses in order on `127.0.0.1`; the last one repeats once
+/// the queue runs out. Shuts down when dropped.
+pub struct MockTwitterServer {
+    addr: SocketAddr,
+    state: MockState,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockTwitterServer {
+    pub async fn start(responses: Vec<CannedResponse>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let addr = listener.local_addr().expect("mock server address");
+
+        let state = MockState::default();
+        state.responses.lock().unwrap().extend(responses);
+
+        let app = Router::new().fallback(replay).with_state(state.clone());
+        let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+        let server = axum::Server::from_tcp(listener)
+            .expect("start mock server")
+            .serve(app.into_make_service())
+            .with_graceful_shutdown(async {
+                shutdown_rx.await.ok();
+            });
+        tokio::spawn(server);
+
+        Self {
+            addr,
+            state,
+            shutdown: Some(shutdown),
+        }
+    }
+
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockTwitterServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
+
+async fn replay(
+    State(state): State<MockState>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    state.requests.lock().unwrap().push(RecordedRequest {
+        method: method.to_string(),
+        path: uri.path().to_string(),
+        authorization: headers
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        body: String::from_utf8_lossy(&body).to_string(),
+    });
+
+    let canned = {
+        let mut responses = state.responses.lock().unwrap();
+        if responses.len() > 1 {
+            responses.pop_front()
+        } else {
+            responses.front().cloned()
+        }
+    };
+    let Some(canned) = canned else {
+        return (StatusCode::NOT_FOUND, "no canned response").into_response();
+    };
+
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(
+        "content-type",
+        HeaderValue::from_static("application/json; charset=utf-8"),
+    );
+    for (name, value) in &canned.headers {
+        response_headers.insert(
+            HeaderName::from_bytes(name.as_bytes()).expect("header name"),
+            HeaderValue::from_str(value).expect("header value"),
+        );
+    }
+
+    (
+        StatusCode::from_u16(canned.status).expect("status code"),
+        response_headers,
+        canned.body,
+    )
+        .into_response()
+}
@@ -0,0 +1,288 @@
+#[cfg(test)]
+pub mod mock;
+pub mod problem;
+pub mod rate_limits;
+pub mod retry;
+#[cfg(test)]
+mod tests;
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, Method, StatusCode};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::AppError;
+use crate::i18n::Message;
+use crate::models::BotAccount;
+use crate::utils::oauth1::{percent_encode, Credentials, Signer};
+
+pub use self::problem::TwitterErrorCode;
+
+pub const DEFAULT_BASE_URL: &str = "https://api.twitter.com";
+const BASE_URL_ENV: &str = "TAM_TWITTER_API_BASE_URL";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Twitter's rate-limit window, assumed when a 429 carries no reset header.
+const DEFAULT_RATE_LIMIT_WINDOW_SECS: i64 = 15 * 60;
+
+/// Rate-limit keys, one per endpoint the app calls.
+pub const POST_TWEETS_ENDPOINT: &str = "POST /2/tweets";
+pub const USERS_ME_ENDPOINT: &str = "GET /2/users/me";
+pub const USER_TWEETS_ENDPOINT: &str = "GET /2/users/:id/tweets";
+
+/// Tweets asked for per `GET /2/users/:id/tweets` call; 5 is the API's minimum.
+const USER_TWEETS_PAGE_SIZE: u32 = 10;
+
+/// The `x-rate-limit-*` headers of a response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitHeaders {
+    pub limit: Option<i64>,
+    pub remaining: i64,
+    /// Unix time in seconds at which the window resets.
+    pub reset_at: i64,
+}
+
+impl RateLimitHeaders {
+    /// `None` unless both `remaining` and `reset` are present and numeric.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let number =
+            |name: &str| -> Option<i64> { headers.get(name)?.to_str().ok()?.trim().parse().ok() };
+
+        Some(Self {
+            limit: number("x-rate-limit-limit"),
+            remaining: number("x-rate-limit-remaining")?,
+            reset_at: number("x-rate-limit-reset")?,
+        })
+    }
+}
+
+/// A successful call together with the rate-limit state it reported.
+#[derive(Debug)]
+pub struct ApiResponse<T> {
+    pub data: T,
+    pub rate_limit: Option<RateLimitHeaders>,
+}
+
+/// The user an access token belongs to, from `GET /2/users/me`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TwitterUser {
+    pub id: String,
+    pub username: String,
+    pub name: String,
+}
+
+/// A tweet as listed by `GET /2/users/:id/tweets`. `text` is as Twitter
+/// stores it: `&`, `<` and `>` escaped and links shortened.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Tweet {
+    pub id: String,
+    pub text: String,
+}
+
+/// The Twitter API v2 calls the app makes. Commands take it as a trait
+/// object so tests can point them at a local mock server.
+#[async_trait]
+pub trait TwitterApi: Send + Sync {
+    /// Posts `text` as `account` and returns the new tweet's id. A 429 is
+    /// returned as [`AppError::RateLimited`].
+    async fn post_tweet(
+        &self,
+        account: &BotAccount,
+        text: &str,
+    ) -> Result<ApiResponse<String>, AppError>;
+
+    /// The user `account`'s access token belongs to.
+    async fn get_me(&self, account: &BotAccount) -> Result<ApiResponse<TwitterUser>, AppError>;
+
+    /// The latest tweets of `user_id` created at or after `start_time`
+    /// (RFC 3339 in whole seconds), newest first.
+    async fn user_tweets(
+        &self,
+        account: &BotAccount,
+        user_id: &str,
+        start_time: &str,
+    ) -> Result<ApiResponse<Vec<Tweet>>, AppError>;
+}
+
+/// [`TwitterApi`] over HTTP, sharing one connection pool between calls.
+pub struct TwitterClient {
+    http: Client,
+    base_url: String,
+}
+
+impl TwitterClient {
+    pub fn new(base_url: &str) -> Result<Self, AppError> {
+        let http = Client::builder().timeout(REQUEST_TIMEOUT).build()?;
+        Ok(Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Uses `TAM_TWITTER_API_BASE_URL` when set, otherwise the public API.
+    pub fn from_env() -> Result<Self, AppError> {
+        match std::env::var(BASE_URL_ENV) {
+            Ok(base_url) if !base_url.trim().is_empty() => Self::new(base_url.trim()),
+            _ => Self::new(DEFAULT_BASE_URL),
+        }
+    }
+
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Sends a signed request and returns the JSON body of a 2xx response.
+    /// A 429 becomes [`AppError::RateLimited`] for `endpoint`, any other
+    /// failure a classified [`AppError::TwitterApi`].
+    async fn send(
+        &self,
+        account: &BotAccount,
+        method: Method,
+        path: &str,
+        endpoint: &str,
+        json_body: Option<String>,
+    ) -> Result<ApiResponse<Value>, AppError> {
+        let url = self.endpoint(path);
+        let authorization_header = authorization_header(account, method.as_str(), &url, &[])?;
+
+        let mut request = self
+            .http
+            .request(method, &url)
+            .header("Authorization", authorization_header);
+        if let Some(body) = json_body {
+            request = request
+                .header("Content-Type", "application/json")
+                .body(body);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            AppError::Network(Message::new("twitter.request_failed").with("detail", e))
+        })?;
+
+        let status = response.status();
+        let rate_limit = RateLimitHeaders::from_headers(response.headers());
+        let response_text = response.text().await.map_err(|e| {
+            AppError::Network(Message::new("twitter.response_failed").with("detail", e))
+        })?;
+
+        if status.is_success() {
+            Ok(ApiResponse {
+                data: serde_json::from_str(&response_text)?,
+                rate_limit,
+            })
+        } else if status == StatusCode::TOO_MANY_REQUESTS {
+            Err(rate_limited(endpoint, rate_limit.as_ref()))
+        } else {
+            Err(problem::api_error(status.as_u16(), &response_text))
+        }
+    }
+}
+
+#[async_trait]
+impl TwitterApi for TwitterClient {
+    async fn post_tweet(
+        &self,
+        account: &BotAccount,
+        text: &str,
+    ) -> Result<ApiResponse<String>, AppError> {
+        let payload = json!({ "text": text }).to_string();
+        let response = self
+            .send(
+                account,
+                Method::POST,
+                "/2/tweets",
+                POST_TWEETS_ENDPOINT,
+                Some(payload),
+            )
+            .await?;
+
+        match response.data["data"]["id"].as_str() {
+            Some(tweet_id) => Ok(ApiResponse {
+                data: tweet_id.to_string(),
+                rate_limit: response.rate_limit,
+            }),
+            None => Err(AppError::Internal(Message::new("twitter.missing_tweet_id"))),
+        }
+    }
+
+    async fn get_me(&self, account: &BotAccount) -> Result<ApiResponse<TwitterUser>, AppError> {
+        let response = self
+            .send(account, Method::GET, "/2/users/me", USERS_ME_ENDPOINT, None)
+            .await?;
+
+        let user = serde_json::from_value(response.data["data"].clone())
+            .map_err(|_| AppError::Internal(Message::new("twitter.missing_user")))?;
+        Ok(ApiResponse {
+            data: user,
+            rate_limit: response.rate_limit,
+        })
+    }
+
+    async fn user_tweets(
+        &self,
+        account: &BotAccount,
+        user_id: &str,
+        start_time: &str,
+    ) -> Result<ApiResponse<Vec<Tweet>>, AppError> {
+        let path = format!(
+            "/2/users/{}/tweets?max_results={}&start_time={}",
+            percent_encode(user_id),
+            USER_TWEETS_PAGE_SIZE,
+            percent_encode(start_time)
+        );
+        let response = self
+            .send(account, Method::GET, &path, USER_TWEETS_ENDPOINT, None)
+            .await?;
+
+        // `data` is left out entirely when no tweet matches.
+        let tweets = match response.data.get("data") {
+            Some(data) => serde_json::from_value(data.clone())?,
+            None => Vec::new(),
+        };
+        Ok(ApiResponse {
+            data: tweets,
+            rate_limit: response.rate_limit,
+        })
+    }
+}
+
+/// The error for a 429, retrying once the window reported by `headers`
+/// resets.
+fn rate_limited(endpoint: &str, headers: Option<&RateLimitHeaders>) -> AppError {
+    let now = Utc::now().timestamp();
+    let reset_at = headers
+        .map(|headers| headers.reset_at)
+        .unwrap_or(now + DEFAULT_RATE_LIMIT_WINDOW_SECS);
+
+    AppError::RateLimited {
+        endpoint: endpoint.to_string(),
+        limit: headers.and_then(|headers| headers.limit),
+        reset_at,
+        retry_after: (reset_at - now).max(0),
+    }
+}
+
+/// Signs a request with the account's OAuth 1.0a user context. JSON bodies
+/// are not part of the signature, so only form parameters are passed in.
+fn authorization_header(
+    account: &BotAccount,
+    method: &str,
+    url: &str,
+    form_params: &[(&str, &str)],
+) -> Result<String, AppError> {
+    Signer::new(Credentials {
+        consumer_key: &account.api_key,
+        consumer_secret: &account.api_key_secret,
+        token: &account.access_token,
+        token_secret: &account.access_token_secret,
+    })
+    .authorization_header(method, url, form_params)
+    .map_err(|e| {
+        AppError::Internal(
+            Message::new("twitter.signing_failed").with("detail", format!("{:#}", e)),
+        )
+    })
+}
@@ -0,0 +1,160 @@
+//! Twitter API v2 error bodies ("problem" JSON) and their classification.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+/// What went wrong with a failed API call, as stored in
+/// `execution_logs.error_code`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TwitterErrorCode {
+    /// Bad, expired or revoked credentials.
+    Unauthorized,
+    /// The account may not perform this action (app permissions, project
+    /// setup and the like).
+    Forbidden,
+    /// A tweet identical to one the account posted recently.
+    DuplicateContent,
+    AccountSuspended,
+    RateLimited,
+    /// The app's monthly tier cap is used up on Twitter's side.
+    UsageCapped,
+    InvalidRequest,
+    /// A 5xx; the same request may succeed later.
+    ServerError,
+    Other,
+}
+
+impl TwitterErrorCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TwitterErrorCode::Unauthorized => "unauthorized",
+            TwitterErrorCode::Forbidden => "forbidden",
+            TwitterErrorCode::DuplicateContent => "duplicate_content",
+            TwitterErrorCode::AccountSuspended => "account_suspended",
+            TwitterErrorCode::RateLimited => "rate_limited",
+            TwitterErrorCode::UsageCapped => "usage_capped",
+            TwitterErrorCode::InvalidRequest => "invalid_request",
+            TwitterErrorCode::ServerError => "server_error",
+            TwitterErrorCode::Other => "other",
+        }
+    }
+}
+
+/// An RFC 7807 problem as returned by v2 endpoints. v1.1-style bodies only
+/// carry `errors`, so every field is optional.
+#[derive(Debug, Default, Deserialize)]
+pub struct Problem {
+    pub title: Option<String>,
+    pub detail: Option<String>,
+    #[serde(rename = "type")]
+    pub problem_type: Option<String>,
+    #[serde(default)]
+    pub errors: Vec<ProblemError>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ProblemError {
+    pub message: Option<String>,
+    pub detail: Option<String>,
+    pub title: Option<String>,
+    #[serde(rename = "type")]
+    pub problem_type: Option<String>,
+    /// Numeric v1.1 error code, e.g. 64 for a suspended account.
+    pub code: Option<i64>,
+}
+
+impl Problem {
+    /// Parses `body`, yielding an empty problem when it is not JSON.
+    pub fn parse(body: &str) -> Self {
+        serde_json::from_str(body).unwrap_or_default()
+    }
+
+    /// The most specific human-readable explanation in the body.
+    pub fn summary(&self) -> Option<String> {
+        self.detail
+            .clone()
+            .or_else(|| {
+                self.errors.iter().find_map(|error| {
+                    error
+                        .message
+                        .clone()
+                        .or_else(|| error.detail.clone())
+                        .or_else(|| error.title.clone())
+                })
+            })
+            .or_else(|| self.title.clone())
+    }
+
+    fn types(&self) -> impl Iterator<Item = &str> {
+        self.problem_type.iter().map(String::as_str).chain(
+            self.errors
+                .iter()
+                .filter_map(|error| error.problem_type.as_deref()),
+        )
+    }
+
+    fn texts(&self) -> impl Iterator<Item = &str> {
+        [&self.title, &self.detail]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .chain(self.errors.iter().flat_map(|error| {
+                [&error.message, &error.detail, &error.title]
+                    .into_iter()
+                    .flatten()
+                    .map(String::as_str)
+            }))
+    }
+
+    fn has_code(&self, codes: &[i64]) -> bool {
+        self.errors
+            .iter()
+            .any(|error| error.code.is_some_and(|code| codes.contains(&code)))
+    }
+
+    fn mentions(&self, needle: &str) -> bool {
+        self.texts()
+            .any(|text| text.to_ascii_lowercase().contains(needle))
+    }
+
+    fn has_type(&self, suffix: &str) -> bool {
+        self.types()
+            .any(|problem_type| problem_type.ends_with(suffix))
+    }
+
+    /// Classifies a failed response by its problem type, v1.1 error codes
+    /// and wording, falling back to the HTTP status.
+    pub fn classify(&self, status: u16) -> TwitterErrorCode {
+        if self.has_code(&[64]) || self.mentions("suspended") {
+            TwitterErrorCode::AccountSuspended
+        } else if self.has_code(&[187]) || self.mentions("duplicate content") {
+            TwitterErrorCode::DuplicateContent
+        } else if self.has_type("/usage-capped") {
+            TwitterErrorCode::UsageCapped
+        } else if status == 429 || self.has_code(&[88]) {
+            TwitterErrorCode::RateLimited
+        } else if status == 401 || self.has_code(&[32, 89, 135]) {
+            TwitterErrorCode::Unauthorized
+        } else if status == 403 {
+            TwitterErrorCode::Forbidden
+        } else if status == 400 || status == 422 || self.has_type("/invalid-request") {
+            TwitterErrorCode::InvalidRequest
+        } else if status >= 500 {
+            TwitterErrorCode::ServerError
+        } else {
+            TwitterErrorCode::Other
+        }
+    }
+}
+
+/// The typed error for a non-success response that is not a 429.
+pub fn api_error(status: u16, body: &str) -> AppError {
+    let problem = Problem::parse(body);
+    AppError::TwitterApi {
+        status,
+        code: problem.classify(status),
+        message: problem.summary().unwrap_or_else(|| body.trim().to_string()),
+    }
+}
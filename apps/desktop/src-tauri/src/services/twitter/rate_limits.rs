@@ -0,0 +1,147 @@
+//! Per-account, per-endpoint rate-limit tracking backed by the `rate_limits`
+//! table. Calls check the stored window first and record whatever the API
+//! reports back, including the reset time of a 429.
+
+use std::future::Future;
+
+use chrono::Utc;
+use rusqlite::Connection;
+
+use super::{
+    ApiResponse, RateLimitHeaders, Tweet, TwitterApi, TwitterUser, POST_TWEETS_ENDPOINT,
+    USERS_ME_ENDPOINT, USER_TWEETS_ENDPOINT,
+};
+use crate::db::pool::ConnectionPool;
+use crate::db::queries::rate_limits::{find_rate_limit, upsert_rate_limit};
+use crate::error::AppError;
+use crate::models::{BotAccount, RateLimit};
+
+/// Fails with [`AppError::RateLimited`] while the stored window for
+/// `endpoint` has no calls left.
+pub fn ensure_available(
+    conn: &Connection,
+    account_id: i64,
+    endpoint: &str,
+    now: i64,
+) -> Result<(), AppError> {
+    match find_rate_limit(conn, account_id, endpoint)? {
+        Some(rate_limit) if rate_limit.is_exhausted(now) => Err(AppError::RateLimited {
+            endpoint: endpoint.to_string(),
+            limit: rate_limit.limit,
+            reset_at: rate_limit.reset_at,
+            retry_after: rate_limit.reset_at - now,
+        }),
+        _ => Ok(()),
+    }
+}
+
+pub fn record_headers(
+    conn: &Connection,
+    account_id: i64,
+    endpoint: &str,
+    headers: &RateLimitHeaders,
+) -> Result<(), AppError> {
+    upsert_rate_limit(
+        conn,
+        &RateLimit {
+            account_id,
+            endpoint: endpoint.to_string(),
+            limit: headers.limit,
+            remaining: headers.remaining,
+            reset_at: headers.reset_at,
+            updated_at: Utc::now().to_rfc3339(),
+        },
+    )?;
+    Ok(())
+}
+
+/// Stores the exhausted window carried by a [`AppError::RateLimited`]; other
+/// errors leave the table alone.
+pub fn record_error(conn: &Connection, account_id: i64, error: &AppError) -> Result<(), AppError> {
+    if let AppError::RateLimited {
+        endpoint,
+        limit,
+        reset_at,
+        ..
+    } = error
+    {
+        let headers = RateLimitHeaders {
+            limit: *limit,
+            remaining: 0,
+            reset_at: *reset_at,
+        };
+        record_headers(conn, account_id, endpoint, &headers)?;
+    }
+    Ok(())
+}
+
+/// Runs `call` against `endpoint` gated on and feeding the account's stored
+/// rate limit. Accounts without an id are called untracked.
+pub async fn track<T, F, Fut>(
+    db: &ConnectionPool,
+    account: &BotAccount,
+    endpoint: &str,
+    call: F,
+) -> Result<T, AppError>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<ApiResponse<T>, AppError>>,
+{
+    let Some(account_id) = account.id else {
+        return call().await.map(|response| response.data);
+    };
+
+    {
+        let conn = db.get()?;
+        ensure_available(&conn, account_id, endpoint, Utc::now().timestamp())?;
+    }
+
+    let result = call().await;
+
+    let conn = db.get()?;
+    match result {
+        Ok(ApiResponse { data, rate_limit }) => {
+            if let Some(headers) = rate_limit {
+                record_headers(&conn, account_id, endpoint, &headers)?;
+            }
+            Ok(data)
+        }
+        Err(error) => {
+            record_error(&conn, account_id, &error)?;
+            Err(error)
+        }
+    }
+}
+
+pub async fn post_tweet(
+    db: &ConnectionPool,
+    api: &dyn TwitterApi,
+    account: &BotAccount,
+    text: &str,
+) -> Result<String, AppError> {
+    track(db, account, POST_TWEETS_ENDPOINT, || {
+        api.post_tweet(account, text)
+    })
+    .await
+}
+
+pub async fn get_me(
+    db: &ConnectionPool,
+    api: &dyn TwitterApi,
+    account: &BotAccount,
+) -> Result<TwitterUser, AppError> {
+    track(db, account, USERS_ME_ENDPOINT, || api.get_me(account)).await
+}
+
+pub async fn user_tweets(
+    db: &ConnectionPool,
+    api: &dyn TwitterApi,
+    account: &BotAccount,
+    user_id: &str,
+    start_time: &str,
+) -> Result<Vec<Tweet>, AppError> {
+    track(db, account, USER_TWEETS_ENDPOINT, || {
+        api.user_tweets(account, user_id, start_time)
+    })
+    .await
+}
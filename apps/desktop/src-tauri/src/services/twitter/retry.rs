@@ -0,0 +1,202 @@
+//! Retries for posts that failed for transient reasons, per the
+//! [`RetryPolicy`] in `app_settings`.
+
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+
+use super::{rate_limits, TwitterApi};
+use crate::db::pool::ConnectionPool;
+use crate::db::queries::logs::insert_execution_log;
+use crate::db::queries::settings::fetch_retry_policy;
+use crate::error::AppError;
+use crate::i18n::Message;
+use crate::models::{BotAccount, ExecutionLog, RetryPolicy};
+
+/// How far before the first attempt the timeline is searched, in case the
+/// local clock runs ahead of Twitter's.
+const CLOCK_SKEW_MARGIN_SECS: i64 = 120;
+
+/// Delay before attempt `attempt + 1`, doubling from the initial backoff up
+/// to the maximum. `attempt` counts from 1.
+pub fn backoff_delay(policy: &RetryPolicy, attempt: u32) -> Duration {
+    let factor = 2u64.saturating_pow(attempt.saturating_sub(1));
+    let delay = policy.initial_backoff_ms.saturating_mul(factor);
+    Duration::from_millis(delay.min(policy.max_backoff_ms))
+}
+
+/// [`rate_limits::post_tweet`] with retries on connection errors and 5xx.
+///
+/// An attempt can fail after Twitter has already accepted the tweet, so
+/// before each retry the account's tweets since the first attempt are looked
+/// up; if one has this text, its id is returned instead of posting again.
+/// Accounts that were never verified are looked up with `users/me` first.
+/// Both lookups are logged as `read` rows so they count against the
+/// account's read budget.
+pub async fn post_tweet(
+    db: &ConnectionPool,
+    api: &dyn TwitterApi,
+    account: &BotAccount,
+    text: &str,
+) -> Result<String, AppError> {
+    let policy = {
+        let conn = db.get()?;
+        fetch_retry_policy(&conn)?
+    };
+    let started = Instant::now();
+    let since = (Utc::now() - chrono::Duration::seconds(CLOCK_SKEW_MARGIN_SECS))
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string();
+    let mut user_id = account.twitter_user_id.clone();
+    let deadline = Duration::from_secs(policy.deadline_secs);
+    let max_attempts = policy.max_attempts.max(1);
+
+    let mut attempt = 1;
+    loop {
+        let error = match rate_limits::post_tweet(db, api, account, text).await {
+            Ok(tweet_id) => return Ok(tweet_id),
+            Err(error) => error,
+        };
+
+        if !error.is_transient() || attempt >= max_attempts {
+            return Err(error);
+        }
+        let delay = backoff_delay(&policy, attempt);
+        if started.elapsed() + delay > deadline {
+            return Err(error);
+        }
+
+        println!(
+            "Retrying post for account {} in {:?} (attempt {}/{}): {}",
+            account.account_name,
+            delay,
+            attempt + 1,
+            max_attempts,
+            error
+        );
+        tokio::time::sleep(delay).await;
+
+        if user_id.is_none() {
+            user_id = look_up_user_id(db, api, account).await;
+        }
+        let earlier = match &user_id {
+            Some(user_id) => find_earlier_attempt(db, api, account, user_id, text, &since).await,
+            None => None,
+        };
+        if let Some(tweet_id) = earlier {
+            println!(
+                "Skipping retry for account {}: tweet {} went through on an earlier attempt",
+                account.account_name, tweet_id
+            );
+            return Ok(tweet_id);
+        }
+
+        attempt += 1;
+    }
+}
+
+/// The Twitter user id of an account that has not been verified yet, so
+/// its timeline can be checked. `None` if the lookup fails.
+async fn look_up_user_id(
+    db: &ConnectionPool,
+    api: &dyn TwitterApi,
+    account: &BotAccount,
+) -> Option<String> {
+    match rate_limits::get_me(db, api, account).await {
+        Ok(user) => {
+            log_read(
+                db,
+                account,
+                Message::new("tweet.retry_user_found").with("username", &user.username),
+                None,
+            );
+            Some(user.id)
+        }
+        Err(error) => {
+            log_read(
+                db,
+                account,
+                Message::new("tweet.retry_user_lookup_failed")
+                    .with_message("error", error.message()),
+                Some(&error),
+            );
+            None
+        }
+    }
+}
+
+/// The id of a tweet of exactly `text` the account posted at or after
+/// `since`. When the lookup fails this is `None` and the retry goes ahead;
+/// Twitter refuses a duplicate of a tweet it did accept. Text with links
+/// never matches, since Twitter shortens them.
+async fn find_earlier_attempt(
+    db: &ConnectionPool,
+    api: &dyn TwitterApi,
+    account: &BotAccount,
+    user_id: &str,
+    text: &str,
+    since: &str,
+) -> Option<String> {
+    match rate_limits::user_tweets(db, api, account, user_id, since).await {
+        Ok(tweets) => {
+            log_read(
+                db,
+                account,
+                Message::new("tweet.retry_timeline_checked").with("count", tweets.len()),
+                None,
+            );
+            tweets
+                .into_iter()
+                .find(|tweet| unescape_text(&tweet.text) == text)
+                .map(|tweet| tweet.id)
+        }
+        Err(error) => {
+            log_read(
+                db,
+                account,
+                Message::new("tweet.retry_timeline_check_failed")
+                    .with_message("error", error.message()),
+                Some(&error),
+            );
+            None
+        }
+    }
+}
+
+/// Records a lookup as a `read` row, like `verify_credentials` does. A
+/// failure to log does not stop the retry.
+fn log_read(db: &ConnectionPool, account: &BotAccount, message: Message, error: Option<&AppError>) {
+    let now = Utc::now().to_rfc3339();
+    let mut log = ExecutionLog {
+        id: None,
+        account_id: account.id.unwrap_or_default(),
+        log_type: "read".to_string(),
+        message: String::new(),
+        message_id: None,
+        message_params: Default::default(),
+        error_code: error.map(|error| error.log_code().to_string()),
+        tweet_id: None,
+        tweet_content: None,
+        status: if error.is_some() { "error" } else { "success" }.to_string(),
+        created_at: now.clone(),
+    };
+    log.set_message(message);
+
+    let result = db
+        .get()
+        .map_err(AppError::from)
+        .and_then(|conn| insert_execution_log(&conn, &log, &now).map_err(AppError::from));
+    if let Err(e) = result {
+        println!(
+            "Could not log a read for account {}: {}",
+            account.account_name, e
+        );
+    }
+}
+
+/// Undoes the HTML escaping Twitter applies to tweet text.
+fn unescape_text(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
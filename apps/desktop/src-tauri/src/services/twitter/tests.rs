@@ -0,0 +1,680 @@
+use tempfile::TempDir;
+
+use super::mock::{CannedResponse, MockTwitterServer};
+use super::retry::backoff_delay;
+use super::{
+    ApiResponse, RateLimitHeaders, TwitterApi, TwitterClient, TwitterErrorCode,
+    POST_TWEETS_ENDPOINT,
+};
+use crate::commands::bots::verify_credentials;
+use crate::commands::tests::send_test_tweet;
+use crate::db::pool::ConnectionPool;
+use crate::db::queries::bots::{find_bot_account_by_id, insert_bot_account, restore_bot_identity};
+use crate::db::queries::logs::{fetch_execution_logs, insert_execution_log};
+use crate::db::queries::rate_limits::find_rate_limit;
+use crate::db::queries::settings::save_retry_policy;
+use crate::error::AppError;
+use crate::models::{
+    ApiTier, BotAccount, CredentialHealth, ExecutionLog, RetryPolicy, TestTweetRequest,
+};
+use crate::test_support;
+use crate::utils::vault::CredentialVault;
+
+fn account() -> BotAccount {
+    BotAccount {
+        id: Some(1),
+        ..test_support::account("mock_bot")
+    }
+}
+
+struct TestDb {
+    _dir: TempDir,
+    pool: ConnectionPool,
+    vault: CredentialVault,
+}
+
+/// A migrated database in a temp directory holding `account()` as id 1.
+fn test_db() -> TestDb {
+    let dir = TempDir::new().unwrap();
+    let (conn, vault) = test_support::migrated_db(&dir);
+    insert_bot_account(&conn, &vault, &account(), test_support::NOW).unwrap();
+
+    let pool = ConnectionPool::new(&test_support::database_path(dir.path()), conn).unwrap();
+    TestDb {
+        _dir: dir,
+        pool,
+        vault,
+    }
+}
+
+fn success_log(tweet_id: &str, content: Option<&str>, created_at: &str) -> ExecutionLog {
+    ExecutionLog {
+        id: None,
+        account_id: 1,
+        log_type: "tweet".to_string(),
+        message: String::new(),
+        message_id: None,
+        message_params: Default::default(),
+        error_code: None,
+        tweet_id: Some(tweet_id.to_string()),
+        tweet_content: content.map(str::to_string),
+        status: "success".to_string(),
+        created_at: created_at.to_string(),
+    }
+}
+
+/// Records `count` successful posts for account 1 this month.
+fn seed_posts(db: &TestDb, count: i64) {
+    let conn = db.pool.get().unwrap();
+    let now = chrono::Utc::now().to_rfc3339();
+    for n in 0..count {
+        let log = success_log(&n.to_string(), None, &now);
+        insert_execution_log(&conn, &log, &now).unwrap();
+    }
+}
+
+/// Stores a retry policy with millisecond backoffs so tests stay fast.
+fn fast_retries(db: &TestDb, max_attempts: u32) {
+    let policy = RetryPolicy {
+        max_attempts,
+        initial_backoff_ms: 1,
+        max_backoff_ms: 4,
+        deadline_secs: 5,
+    };
+    save_retry_policy(&db.pool.get().unwrap(), &policy, "2024-01-01T00:00:00Z").unwrap();
+}
+
+fn hello_request() -> TestTweetRequest {
+    TestTweetRequest {
+        account_id: 1,
+        content: "hi".to_string(),
+    }
+}
+
+async fn post_once(
+    response: CannedResponse,
+) -> (Result<ApiResponse<String>, AppError>, MockTwitterServer) {
+    let server = MockTwitterServer::start(vec![response]).await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+    let result = client.post_tweet(&account(), "こんにちは").await;
+    (result, server)
+}
+
+fn assert_api_error(
+    result: Result<ApiResponse<String>, AppError>,
+    expected_status: u16,
+    expected_code: TwitterErrorCode,
+) -> String {
+    match result {
+        Err(AppError::TwitterApi {
+            status,
+            code,
+            message,
+        }) => {
+            assert_eq!(status, expected_status);
+            assert_eq!(code, expected_code);
+            message
+        }
+        other => panic!(
+            "expected a {} TwitterApi error, got {:?}",
+            expected_status, other
+        ),
+    }
+}
+
+#[tokio::test]
+async fn created_returns_the_tweet_id() {
+    let (result, server) = post_once(CannedResponse::created("1445880548472328192", "hi")).await;
+
+    let response = result.unwrap();
+    assert_eq!(response.data, "1445880548472328192");
+    assert!(response.rate_limit.is_none());
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2/tweets");
+    assert!(requests[0]
+        .authorization
+        .as_deref()
+        .unwrap()
+        .starts_with("OAuth "));
+    let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+    assert_eq!(body["text"], "こんにちは");
+}
+
+#[tokio::test]
+async fn unauthorized_is_classified() {
+    let (result, _server) = post_once(CannedResponse::unauthorized()).await;
+    assert_api_error(result, 401, TwitterErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+async fn duplicate_content_is_classified() {
+    let (result, _server) = post_once(CannedResponse::duplicate_content()).await;
+    let message = assert_api_error(result, 403, TwitterErrorCode::DuplicateContent);
+    assert_eq!(
+        message,
+        "You are not allowed to create a Tweet with duplicate content."
+    );
+}
+
+#[tokio::test]
+async fn suspended_account_is_classified() {
+    let (result, _server) = post_once(CannedResponse::suspended()).await;
+    let message = assert_api_error(result, 403, TwitterErrorCode::AccountSuspended);
+    assert!(message.starts_with("Your account is suspended"));
+}
+
+#[tokio::test]
+async fn forbidden_without_a_known_cause_is_classified() {
+    let (result, _server) = post_once(CannedResponse::new(
+        403,
+        serde_json::json!({
+            "title": "Forbidden",
+            "detail": "You are not permitted to perform this action.",
+            "type": "about:blank",
+            "status": 403
+        }),
+    ))
+    .await;
+    assert_api_error(result, 403, TwitterErrorCode::Forbidden);
+}
+
+#[tokio::test]
+async fn created_reports_rate_limit_headers() {
+    let (result, _server) = post_once(
+        CannedResponse::created("1", "hi")
+            .with_header("x-rate-limit-limit", "17")
+            .with_header("x-rate-limit-remaining", "16")
+            .with_header("x-rate-limit-reset", "1700000000"),
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap().rate_limit,
+        Some(RateLimitHeaders {
+            limit: Some(17),
+            remaining: 16,
+            reset_at: 1_700_000_000,
+        })
+    );
+}
+
+#[tokio::test]
+async fn rate_limited_carries_the_reset_time() {
+    let (result, _server) = post_once(CannedResponse::rate_limited(1_700_000_000)).await;
+    match result {
+        Err(AppError::RateLimited {
+            endpoint,
+            limit,
+            reset_at,
+            ..
+        }) => {
+            assert_eq!(endpoint, POST_TWEETS_ENDPOINT);
+            assert_eq!(limit, Some(200));
+            assert_eq!(reset_at, 1_700_000_000);
+        }
+        other => panic!("expected RateLimited, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn server_error_is_classified_as_transient() {
+    let (result, _server) = post_once(CannedResponse::server_error(503)).await;
+    assert_api_error(result, 503, TwitterErrorCode::ServerError);
+}
+
+#[tokio::test]
+async fn non_json_error_bodies_keep_the_raw_text() {
+    let mut response = CannedResponse::server_error(502);
+    response.body = "Bad Gateway".to_string();
+    let (result, _server) = post_once(response).await;
+    let message = assert_api_error(result, 502, TwitterErrorCode::ServerError);
+    assert_eq!(message, "Bad Gateway");
+}
+
+#[tokio::test]
+async fn unreachable_server_is_a_network_error() {
+    let server = MockTwitterServer::start(Vec::new()).await;
+    let base_url = server.base_url();
+    drop(server);
+    tokio::task::yield_now().await;
+
+    let client = TwitterClient::new(&base_url).unwrap();
+    let result = client.post_tweet(&account(), "hi").await;
+    assert!(matches!(result, Err(AppError::Network(_))), "{:?}", result);
+}
+
+#[tokio::test]
+async fn test_tweet_logs_success() {
+    let db = test_db();
+    let server = MockTwitterServer::start(vec![CannedResponse::created("42", "hi")]).await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let request = TestTweetRequest {
+        account_id: 1,
+        content: "hi".to_string(),
+    };
+    let response = send_test_tweet(&db.pool, &db.vault, &client, &request)
+        .await
+        .unwrap();
+
+    assert!(response.success);
+    assert_eq!(response.tweet_id.as_deref(), Some("42"));
+
+    let logs = fetch_execution_logs(&db.pool.get().unwrap(), Some(1), 10).unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].status, "success");
+    assert_eq!(logs[0].tweet_id.as_deref(), Some("42"));
+    assert_eq!(logs[0].message_id.as_deref(), Some("tweet.posted"));
+}
+
+#[tokio::test]
+async fn test_tweet_logs_api_failures() {
+    let db = test_db();
+    let server = MockTwitterServer::start(vec![CannedResponse::duplicate_content()]).await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let request = TestTweetRequest {
+        account_id: 1,
+        content: "hi".to_string(),
+    };
+    let response = send_test_tweet(&db.pool, &db.vault, &client, &request)
+        .await
+        .unwrap();
+
+    assert!(!response.success);
+    assert!(response.tweet_id.is_none());
+
+    let logs = fetch_execution_logs(&db.pool.get().unwrap(), Some(1), 10).unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].status, "error");
+    assert_eq!(logs[0].message_id.as_deref(), Some("tweet.post_failed"));
+    assert_eq!(logs[0].error_code.as_deref(), Some("duplicate_content"));
+}
+
+#[tokio::test]
+async fn test_tweet_rejects_unknown_accounts() {
+    let db = test_db();
+    let server = MockTwitterServer::start(vec![CannedResponse::created("42", "hi")]).await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let request = TestTweetRequest {
+        account_id: 99,
+        content: "hi".to_string(),
+    };
+    let result = send_test_tweet(&db.pool, &db.vault, &client, &request).await;
+
+    assert!(matches!(result, Err(AppError::NotFound(_))), "{:?}", result);
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_tweet_records_rate_limit_headers() {
+    let db = test_db();
+    let server = MockTwitterServer::start(vec![CannedResponse::created("42", "hi")
+        .with_header("x-rate-limit-limit", "17")
+        .with_header("x-rate-limit-remaining", "3")
+        .with_header("x-rate-limit-reset", "4102444800")])
+    .await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let request = TestTweetRequest {
+        account_id: 1,
+        content: "hi".to_string(),
+    };
+    send_test_tweet(&db.pool, &db.vault, &client, &request)
+        .await
+        .unwrap();
+
+    let stored = find_rate_limit(&db.pool.get().unwrap(), 1, POST_TWEETS_ENDPOINT)
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.limit, Some(17));
+    assert_eq!(stored.remaining, 3);
+    assert_eq!(stored.reset_at, 4_102_444_800);
+}
+
+#[tokio::test]
+async fn test_tweet_waits_out_an_exhausted_window() {
+    let db = test_db();
+    let reset = chrono::Utc::now().timestamp() + 600;
+    let server = MockTwitterServer::start(vec![
+        CannedResponse::rate_limited(reset),
+        CannedResponse::created("42", "hi"),
+    ])
+    .await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let request = TestTweetRequest {
+        account_id: 1,
+        content: "hi".to_string(),
+    };
+    let first = send_test_tweet(&db.pool, &db.vault, &client, &request).await;
+    assert!(
+        matches!(first, Err(AppError::RateLimited { reset_at, .. }) if reset_at == reset),
+        "{:?}",
+        first
+    );
+
+    let second = send_test_tweet(&db.pool, &db.vault, &client, &request).await;
+    match second {
+        Err(AppError::RateLimited { retry_after, .. }) => {
+            assert!(retry_after > 0 && retry_after <= 600)
+        }
+        other => panic!("expected RateLimited, got {:?}", other),
+    }
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_tweet_is_blocked_once_the_monthly_budget_is_spent() {
+    let db = test_db();
+    seed_posts(&db, ApiTier::Free.monthly_post_budget());
+    let server = MockTwitterServer::start(vec![CannedResponse::created("42", "hi")]).await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let request = TestTweetRequest {
+        account_id: 1,
+        content: "hi".to_string(),
+    };
+    let result = send_test_tweet(&db.pool, &db.vault, &client, &request).await;
+
+    match result {
+        Err(AppError::QuotaExceeded { used, budget, .. }) => {
+            assert_eq!(used, budget);
+            assert_eq!(budget, 500);
+        }
+        other => panic!("expected QuotaExceeded, got {:?}", other),
+    }
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_tweet_warns_near_the_monthly_budget() {
+    let db = test_db();
+    seed_posts(&db, ApiTier::Free.monthly_post_budget() - 10);
+    let server = MockTwitterServer::start(vec![CannedResponse::created("42", "hi")]).await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let request = TestTweetRequest {
+        account_id: 1,
+        content: "hi".to_string(),
+    };
+    let response = send_test_tweet(&db.pool, &db.vault, &client, &request)
+        .await
+        .unwrap();
+    assert!(response.success);
+
+    let logs = fetch_execution_logs(&db.pool.get().unwrap(), Some(1), 1000).unwrap();
+    let warning = logs
+        .iter()
+        .find(|log| log.log_type == "warning")
+        .expect("a quota warning log");
+    assert_eq!(warning.message_id.as_deref(), Some("quota.post_warning"));
+    assert_eq!(
+        warning.message_params.get("used"),
+        Some(&crate::i18n::MessageParam::Text("491".to_string()))
+    );
+}
+
+#[test]
+fn backoff_doubles_up_to_the_maximum() {
+    let policy = RetryPolicy::default();
+    let delays: Vec<u64> = (1..=5)
+        .map(|attempt| backoff_delay(&policy, attempt).as_millis() as u64)
+        .collect();
+    assert_eq!(delays, vec![1_000, 2_000, 4_000, 8_000, 8_000]);
+}
+
+#[tokio::test]
+async fn test_tweet_retries_server_errors() {
+    let db = test_db();
+    fast_retries(&db, 3);
+    store_user_id(&db);
+    let server = MockTwitterServer::start(vec![
+        CannedResponse::server_error(503),
+        CannedResponse::user_tweets(&[]),
+        CannedResponse::server_error(502),
+        CannedResponse::user_tweets(&[]),
+        CannedResponse::created("42", "hi"),
+    ])
+    .await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let response = send_test_tweet(&db.pool, &db.vault, &client, &hello_request())
+        .await
+        .unwrap();
+
+    assert!(response.success);
+    assert_eq!(response.tweet_id.as_deref(), Some("42"));
+    assert_eq!(server.requests().len(), 5);
+}
+
+#[tokio::test]
+async fn test_tweet_gives_up_after_max_attempts() {
+    let db = test_db();
+    fast_retries(&db, 2);
+    let server = MockTwitterServer::start(vec![CannedResponse::server_error(503)]).await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let response = send_test_tweet(&db.pool, &db.vault, &client, &hello_request())
+        .await
+        .unwrap();
+
+    assert!(!response.success);
+    // post, users/me lookup, post
+    assert_eq!(server.requests().len(), 3);
+    let logs = fetch_execution_logs(&db.pool.get().unwrap(), Some(1), 10).unwrap();
+    let mut log_types: Vec<&str> = logs.iter().map(|log| log.log_type.as_str()).collect();
+    log_types.sort();
+    assert_eq!(log_types, vec!["error", "read"]);
+    assert!(logs
+        .iter()
+        .all(|log| log.error_code.as_deref() == Some("server_error")));
+}
+
+#[tokio::test]
+async fn test_tweet_does_not_retry_client_errors() {
+    let db = test_db();
+    fast_retries(&db, 3);
+    let server = MockTwitterServer::start(vec![
+        CannedResponse::unauthorized(),
+        CannedResponse::created("42", "hi"),
+    ])
+    .await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let response = send_test_tweet(&db.pool, &db.vault, &client, &hello_request())
+        .await
+        .unwrap();
+
+    assert!(!response.success);
+    assert_eq!(server.requests().len(), 1);
+}
+
+/// Gives account 1 the Twitter user id 1100.
+fn store_user_id(db: &TestDb) {
+    let identified = BotAccount {
+        twitter_user_id: Some("1100".to_string()),
+        ..account()
+    };
+    let conn = db.pool.get().unwrap();
+    restore_bot_identity(&conn, 1, &identified, CredentialHealth::Valid).unwrap();
+}
+
+#[tokio::test]
+async fn test_tweet_returns_an_earlier_attempt_found_on_the_timeline() {
+    let db = test_db();
+    fast_retries(&db, 3);
+    store_user_id(&db);
+    let server = MockTwitterServer::start(vec![
+        CannedResponse::server_error(503),
+        CannedResponse::user_tweets(&[("77", "hi")]),
+        CannedResponse::created("42", "hi"),
+    ])
+    .await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let response = send_test_tweet(&db.pool, &db.vault, &client, &hello_request())
+        .await
+        .unwrap();
+
+    assert_eq!(response.tweet_id.as_deref(), Some("77"));
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].method, "GET");
+    assert_eq!(requests[1].path, "/2/users/1100/tweets");
+    let logs = fetch_execution_logs(&db.pool.get().unwrap(), Some(1), 10).unwrap();
+    assert!(logs.iter().any(|log| log.log_type == "read"
+        && log.message_id.as_deref() == Some("tweet.retry_timeline_checked")));
+}
+
+#[tokio::test]
+async fn test_tweet_looks_up_unverified_accounts_before_retrying() {
+    let db = test_db();
+    fast_retries(&db, 3);
+    let server = MockTwitterServer::start(vec![
+        CannedResponse::server_error(503),
+        CannedResponse::user("1100", "mock_bot", "Mock"),
+        CannedResponse::user_tweets(&[("77", "hi")]),
+    ])
+    .await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let response = send_test_tweet(&db.pool, &db.vault, &client, &hello_request())
+        .await
+        .unwrap();
+
+    assert_eq!(response.tweet_id.as_deref(), Some("77"));
+    let paths: Vec<String> = server.requests().into_iter().map(|r| r.path).collect();
+    assert_eq!(
+        paths,
+        vec!["/2/tweets", "/2/users/me", "/2/users/1100/tweets"]
+    );
+    let conn = db.pool.get().unwrap();
+    let reads = fetch_execution_logs(&conn, Some(1), 10)
+        .unwrap()
+        .into_iter()
+        .filter(|log| log.log_type == "read")
+        .count();
+    assert_eq!(reads, 2);
+}
+
+#[tokio::test]
+async fn test_tweet_retries_when_the_timeline_lacks_the_post() {
+    let db = test_db();
+    fast_retries(&db, 3);
+    store_user_id(&db);
+    let server = MockTwitterServer::start(vec![
+        CannedResponse::server_error(503),
+        CannedResponse::user_tweets(&[("5", "hi &amp; bye")]),
+        CannedResponse::created("42", "hi"),
+    ])
+    .await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let response = send_test_tweet(&db.pool, &db.vault, &client, &hello_request())
+        .await
+        .unwrap();
+
+    assert_eq!(response.tweet_id.as_deref(), Some("42"));
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn verify_credentials_stores_the_twitter_identity() {
+    let db = test_db();
+    let server =
+        MockTwitterServer::start(vec![CannedResponse::user("1234", "Mock_Bot", "Mock")]).await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let verification = verify_credentials(&db.pool, &db.vault, &client, 1)
+        .await
+        .unwrap();
+
+    assert_eq!(verification.status, "active");
+    assert_eq!(verification.twitter_user_id.as_deref(), Some("1234"));
+    assert!(!verification.name_mismatch);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "/2/users/me");
+
+    let conn = db.pool.get().unwrap();
+    let stored = find_bot_account_by_id(&conn, &db.vault, 1).unwrap();
+    assert_eq!(stored.twitter_user_id.as_deref(), Some("1234"));
+    assert_eq!(stored.twitter_username.as_deref(), Some("Mock_Bot"));
+    assert_eq!(stored.twitter_display_name.as_deref(), Some("Mock"));
+    assert_eq!(stored.credential_health, CredentialHealth::Valid);
+    assert!(stored.last_verified_at.is_some());
+
+    let logs = fetch_execution_logs(&conn, Some(1), 10).unwrap();
+    assert_eq!(logs[0].log_type, "read");
+    assert_eq!(
+        logs[0].message_id.as_deref(),
+        Some("bot.credentials_verified")
+    );
+}
+
+#[tokio::test]
+async fn verify_credentials_flags_a_different_username() {
+    let db = test_db();
+    let server =
+        MockTwitterServer::start(vec![CannedResponse::user("1234", "someone_else", "X")]).await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let verification = verify_credentials(&db.pool, &db.vault, &client, 1)
+        .await
+        .unwrap();
+
+    assert_eq!(verification.status, "active");
+    assert!(verification.name_mismatch);
+}
+
+#[tokio::test]
+async fn verify_credentials_marks_rejected_tokens_invalid_until_they_pass() {
+    let db = test_db();
+    let server = MockTwitterServer::start(vec![CannedResponse::unauthorized()]).await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let verification = verify_credentials(&db.pool, &db.vault, &client, 1)
+        .await
+        .unwrap();
+
+    assert_eq!(verification.status, "invalid_credentials");
+
+    let conn = db.pool.get().unwrap();
+    let stored = find_bot_account_by_id(&conn, &db.vault, 1).unwrap();
+    assert_eq!(stored.status, "invalid_credentials");
+    assert_eq!(stored.credential_health, CredentialHealth::Invalid);
+
+    let logs = fetch_execution_logs(&conn, Some(1), 10).unwrap();
+    assert_eq!(logs[0].status, "error");
+    assert_eq!(logs[0].error_code.as_deref(), Some("unauthorized"));
+    drop(conn);
+
+    let server =
+        MockTwitterServer::start(vec![CannedResponse::user("1234", "mock_bot", "Mock")]).await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+    let verification = verify_credentials(&db.pool, &db.vault, &client, 1)
+        .await
+        .unwrap();
+    assert_eq!(verification.status, "active");
+}
+
+#[tokio::test]
+async fn verify_credentials_returns_other_failures() {
+    let db = test_db();
+    let server = MockTwitterServer::start(vec![CannedResponse::server_error(503)]).await;
+    let client = TwitterClient::new(&server.base_url()).unwrap();
+
+    let result = verify_credentials(&db.pool, &db.vault, &client, 1).await;
+
+    assert!(result.is_err());
+    let stored = find_bot_account_by_id(&db.pool.get().unwrap(), &db.vault, 1).unwrap();
+    assert_eq!(stored.status, "active");
+    assert_eq!(stored.credential_health, CredentialHealth::CheckFailed);
+}
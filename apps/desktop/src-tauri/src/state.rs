@@ -1,14 +1,21 @@
-use rusqlite::Connection;
-use std::sync::Mutex;
+use std::sync::Arc;
+
+use crate::db::pool::ConnectionPool;
+use crate::services::TwitterApi;
+use crate::utils::vault::CredentialVault;
 
 pub struct AppState {
-    pub db: Mutex<Connection>,
+    pub db: ConnectionPool,
+    pub vault: CredentialVault,
+    pub twitter: Arc<dyn TwitterApi>,
 }
 
 impl AppState {
-    pub fn new(connection: Connection) -> Self {
+    pub fn new(pool: ConnectionPool, vault: CredentialVault, twitter: Arc<dyn TwitterApi>) -> Self {
         Self {
-            db: Mutex::new(connection),
+            db: pool,
+            vault,
+            twitter,
         }
     }
 }
@@ -0,0 +1,64 @@
+//! Fixtures shared by the unit tests: a bot account and a migrated
+//! database in a temp directory.
+
+use std::path::{Path, PathBuf};
+
+use rusqlite::Connection;
+use tempfile::TempDir;
+
+use crate::db::migrations::{run_database_migrations, MigrationContext};
+use crate::db::pool::configure_connection;
+use crate::db::queries::bots::insert_bot_account;
+use crate::db::queries::replies::save_reply_settings;
+use crate::db::queries::schedules::save_scheduled_tweet_list;
+use crate::models::BotAccount;
+use crate::utils::vault::CredentialVault;
+
+pub const NOW: &str = "2024-01-01T00:00:00Z";
+
+/// An active Free account with placeholder credentials and no id.
+pub fn account(name: &str) -> BotAccount {
+    BotAccount {
+        id: None,
+        account_name: name.to_string(),
+        api_type: "Free".to_string(),
+        api_key: "consumer-key".to_string(),
+        api_key_secret: "consumer-secret".to_string(),
+        access_token: "access-token".to_string(),
+        access_token_secret: "access-token-secret".to_string(),
+        status: "active".to_string(),
+        twitter_user_id: None,
+        twitter_username: None,
+        twitter_display_name: None,
+        last_verified_at: None,
+        credential_health: Default::default(),
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+pub fn database_path(dir: &Path) -> PathBuf {
+    dir.join("test.sqlite")
+}
+
+/// An empty database in `dir`, migrated to the current schema, and the
+/// vault its credentials are encrypted with.
+pub fn migrated_db(dir: &TempDir) -> (Connection, CredentialVault) {
+    let vault = CredentialVault::open(dir.path()).unwrap();
+    let conn = Connection::open(database_path(dir.path())).unwrap();
+    configure_connection(&conn).unwrap();
+    run_database_migrations(&conn, &MigrationContext { vault: &vault }).unwrap();
+    (conn, vault)
+}
+
+/// Accounts 1 ("first", scheduled with posts a, b, c) and 2 ("second"),
+/// with 1 replying to 2.
+pub fn seeded_db(dir: &TempDir) -> (Connection, CredentialVault) {
+    let (conn, vault) = migrated_db(dir);
+    insert_bot_account(&conn, &vault, &account("first"), NOW).unwrap();
+    insert_bot_account(&conn, &vault, &account("second"), NOW).unwrap();
+    let posts = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+    save_scheduled_tweet_list(&conn, 1, "09:00", &posts, NOW).unwrap();
+    save_reply_settings(&conn, 1, &[2], "hi", NOW).unwrap();
+    (conn, vault)
+}
@@ -0,0 +1,83 @@
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitTracking {
+    /// The path is not inside any git working tree.
+    Outside,
+    /// The path is inside a working tree but matched by `.gitignore`.
+    Ignored,
+    /// The path is (or would be) tracked by the enclosing working tree.
+    Tracked,
+}
+
+/// Reports how git would treat `path`. When the `git` binary is unavailable
+/// we fall back to looking for a `.git` directory and assume the worst.
+pub fn tracking_status(path: &Path) -> GitTracking {
+    let Some(dir) = existing_ancestor(path) else {
+        return GitTracking::Outside;
+    };
+
+    let inside = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .output();
+
+    match inside {
+        Ok(output) => {
+            if !output.status.success() || String::from_utf8_lossy(&output.stdout).trim() != "true"
+            {
+                return GitTracking::Outside;
+            }
+        }
+        Err(_) => {
+            return if dir
+                .ancestors()
+                .any(|ancestor| ancestor.join(".git").exists())
+            {
+                GitTracking::Tracked
+            } else {
+                GitTracking::Outside
+            };
+        }
+    }
+
+    let ignored = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["check-ignore", "-q", "--"])
+        .arg(path)
+        .status();
+
+    match ignored {
+        Ok(status) if status.success() => GitTracking::Ignored,
+        _ => GitTracking::Tracked,
+    }
+}
+
+/// Lists the remotes configured for the working tree containing `path`.
+pub fn remotes(path: &Path) -> Vec<String> {
+    let Some(dir) = existing_ancestor(path) else {
+        return Vec::new();
+    };
+
+    match Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .arg("remote")
+        .output()
+    {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|remote| !remote.is_empty())
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn existing_ancestor(path: &Path) -> Option<&Path> {
+    path.ancestors().skip(1).find(|ancestor| ancestor.is_dir())
+}
@@ -1,2 +1,4 @@
-﻿pub mod crypto;
+﻿pub mod git;
+pub mod oauth1;
 pub mod time;
+pub mod vault;
@@ -0,0 +1,373 @@
+//! OAuth 1.0a request signing (RFC 5849, HMAC-SHA1) for the Twitter API.
+//!
+//! Query parameters and `application/x-www-form-urlencoded` body parameters
+//! are part of the signature; JSON bodies are not, as the spec requires.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use hmac::{Hmac, Mac};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use reqwest::Url;
+use sha1::Sha1;
+
+const NONCE_LEN: usize = 32;
+const SIGNATURE_METHOD: &str = "HMAC-SHA1";
+const VERSION: &str = "1.0";
+
+pub struct Credentials<'a> {
+    pub consumer_key: &'a str,
+    pub consumer_secret: &'a str,
+    pub token: &'a str,
+    pub token_secret: &'a str,
+}
+
+/// Signs requests for one set of credentials with a single nonce and
+/// timestamp; create a new signer per request.
+pub struct Signer<'a> {
+    credentials: Credentials<'a>,
+    nonce: String,
+    timestamp: u64,
+}
+
+impl<'a> Signer<'a> {
+    pub fn new(credentials: Credentials<'a>) -> Self {
+        let nonce = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(NONCE_LEN)
+            .map(char::from)
+            .collect();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+
+        Self::with_nonce_and_timestamp(credentials, nonce, timestamp)
+    }
+
+    /// A signer with a fixed nonce and timestamp, for reproducible
+    /// signatures.
+    pub fn with_nonce_and_timestamp(
+        credentials: Credentials<'a>,
+        nonce: String,
+        timestamp: u64,
+    ) -> Self {
+        Self {
+            credentials,
+            nonce,
+            timestamp,
+        }
+    }
+
+    fn oauth_params(&self) -> Vec<(String, String)> {
+        vec![
+            (
+                "oauth_consumer_key".to_string(),
+                self.credentials.consumer_key.to_string(),
+            ),
+            ("oauth_nonce".to_string(), self.nonce.clone()),
+            (
+                "oauth_signature_method".to_string(),
+                SIGNATURE_METHOD.to_string(),
+            ),
+            ("oauth_timestamp".to_string(), self.timestamp.to_string()),
+            (
+                "oauth_token".to_string(),
+                self.credentials.token.to_string(),
+            ),
+            ("oauth_version".to_string(), VERSION.to_string()),
+        ]
+    }
+
+    /// The `oauth_signature` for a request to `url` (query included) with
+    /// the given form-encoded body parameters.
+    pub fn signature(
+        &self,
+        method: &str,
+        url: &str,
+        form_params: &[(&str, &str)],
+    ) -> Result<String> {
+        let mut params = self.oauth_params();
+        params.extend(
+            form_params
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string())),
+        );
+
+        let base_string = signature_base_string(method, url, &params)?;
+        Ok(sign(
+            &base_string,
+            self.credentials.consumer_secret,
+            self.credentials.token_secret,
+        ))
+    }
+
+    /// The complete `Authorization` header value.
+    pub fn authorization_header(
+        &self,
+        method: &str,
+        url: &str,
+        form_params: &[(&str, &str)],
+    ) -> Result<String> {
+        let signature = self.signature(method, url, form_params)?;
+
+        let mut params = self.oauth_params();
+        params.push(("oauth_signature".to_string(), signature));
+        params.sort();
+
+        let fields = params
+            .iter()
+            .map(|(name, value)| format!("{}=\"{}\"", percent_encode(name), percent_encode(value)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Ok(format!("OAuth {}", fields))
+    }
+}
+
+/// RFC 3986 percent-encoding over the UTF-8 bytes of `input`: everything but
+/// ALPHA, DIGIT and `-._~` is escaped with uppercase hex.
+pub fn percent_encode(input: &str) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Builds the signature base string (RFC 5849 §3.4.1). `params` are the
+/// `oauth_*` and body parameters; query parameters are read from `url`.
+pub fn signature_base_string(
+    method: &str,
+    url: &str,
+    params: &[(String, String)],
+) -> Result<String> {
+    let parsed = Url::parse(url).with_context(|| format!("Invalid request URL: {}", url))?;
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| anyhow!("Request URL has no host: {}", url))?;
+
+    // `Url` lowercases the scheme and host and drops default ports already.
+    let base_uri = match parsed.port() {
+        Some(port) => format!("{}://{}:{}{}", parsed.scheme(), host, port, parsed.path()),
+        None => format!("{}://{}{}", parsed.scheme(), host, parsed.path()),
+    };
+
+    let mut encoded: Vec<(String, String)> = parsed
+        .query_pairs()
+        .map(|(name, value)| (percent_encode(&name), percent_encode(&value)))
+        .chain(
+            params
+                .iter()
+                .map(|(name, value)| (percent_encode(name), percent_encode(value))),
+        )
+        .collect();
+    encoded.sort();
+
+    let normalized = encoded
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    Ok(format!(
+        "{}&{}&{}",
+        method.to_uppercase(),
+        percent_encode(&base_uri),
+        percent_encode(&normalized)
+    ))
+}
+
+/// HMAC-SHA1 of `base_string`, keyed by the encoded consumer and token
+/// secrets, in base64.
+pub fn sign(base_string: &str, consumer_secret: &str, token_secret: &str) -> String {
+    let key = format!(
+        "{}&{}",
+        percent_encode(consumer_secret),
+        percent_encode(token_secret)
+    );
+    let mut mac =
+        Hmac::<Sha1>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(base_string.as_bytes());
+    STANDARD.encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn percent_encodes_reserved_characters() {
+        assert_eq!(
+            percent_encode("Ladies + Gentlemen"),
+            "Ladies%20%2B%20Gentlemen"
+        );
+        assert_eq!(
+            percent_encode("An encoded string!"),
+            "An%20encoded%20string%21"
+        );
+        assert_eq!(
+            percent_encode("Dogs, Cats & Mice"),
+            "Dogs%2C%20Cats%20%26%20Mice"
+        );
+        assert_eq!(percent_encode("-._~"), "-._~");
+        assert_eq!(percent_encode("☃"), "%E2%98%83");
+    }
+
+    #[test]
+    fn percent_encodes_japanese_byte_by_byte() {
+        assert_eq!(
+            percent_encode("こんにちは"),
+            "%E3%81%93%E3%82%93%E3%81%AB%E3%81%A1%E3%81%AF"
+        );
+    }
+
+    /// The worked example from Twitter's "Creating a signature" guide.
+    #[test]
+    fn matches_twitter_documentation_example() {
+        let signer = Signer::with_nonce_and_timestamp(
+            Credentials {
+                consumer_key: "xvz1evFS4wEEPTGEFPHBog",
+                consumer_secret: "kAcSOqF21Fu85e7zjz7ZN2U4ZRhfV3WpwPAoE3Z7kBw",
+                token: "370773112-GmHxMAgYyLbNEtIKZeRNFsMKPR9EyMZeS9weJAEb",
+                token_secret: "LswwdoUaIvS8ltyTt5jkRh4J50vUPVVHtR2YPi5kE",
+            },
+            "kYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg".to_string(),
+            1318622958,
+        );
+
+        let url = "https://api.twitter.com/1.1/statuses/update.json?include_entities=true";
+        let body = [(
+            "status",
+            "Hello Ladies + Gentlemen, a signed OAuth request!",
+        )];
+
+        let mut params = signer.oauth_params();
+        params.extend(pairs(&body));
+        assert_eq!(
+            signature_base_string("POST", url, &params).unwrap(),
+            "POST&https%3A%2F%2Fapi.twitter.com%2F1.1%2Fstatuses%2Fupdate.json&\
+             include_entities%3Dtrue%26oauth_consumer_key%3Dxvz1evFS4wEEPTGEFPHBog%26\
+             oauth_nonce%3DkYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg%26\
+             oauth_signature_method%3DHMAC-SHA1%26oauth_timestamp%3D1318622958%26\
+             oauth_token%3D370773112-GmHxMAgYyLbNEtIKZeRNFsMKPR9EyMZeS9weJAEb%26\
+             oauth_version%3D1.0%26status%3DHello%2520Ladies%2520%252B%2520Gentlemen%252C%2520\
+             a%2520signed%2520OAuth%2520request%2521"
+        );
+
+        assert_eq!(
+            signer.signature("POST", url, &body).unwrap(),
+            "hCtSmYh+iHYCEqBWrE7C7hYmtUk="
+        );
+
+        let header = signer.authorization_header("POST", url, &body).unwrap();
+        assert!(header.starts_with("OAuth oauth_consumer_key=\"xvz1evFS4wEEPTGEFPHBog\""));
+        assert!(header.contains("oauth_signature=\"hCtSmYh%2BiHYCEqBWrE7C7hYmtUk%3D\""));
+        assert!(!header.contains("status"));
+        assert!(!header.contains("include_entities"));
+    }
+
+    /// RFC 5849 §3.4.1.1: repeated names, encoded query values, empty values
+    /// and form parameters all end up in the normalized parameter string.
+    #[test]
+    fn matches_rfc5849_base_string_example() {
+        let params = pairs(&[
+            ("oauth_consumer_key", "9djdj82h48djs9d2"),
+            ("oauth_token", "kkk9d7dh3k39sjv7"),
+            ("oauth_signature_method", "HMAC-SHA1"),
+            ("oauth_timestamp", "137131201"),
+            ("oauth_nonce", "7d8f3e4a"),
+            ("c2", ""),
+            ("a3", "2 q"),
+        ]);
+
+        assert_eq!(
+            signature_base_string(
+                "POST",
+                "http://example.com/request?b5=%3D%253D&a3=a&c%40=&a2=r%20b",
+                &params
+            )
+            .unwrap(),
+            "POST&http%3A%2F%2Fexample.com%2Frequest&a2%3Dr%2520b%26a3%3D2%2520q\
+             %26a3%3Da%26b5%3D%253D%25253D%26c%2540%3D%26c2%3D%26oauth_consumer_\
+             key%3D9djdj82h48djs9d2%26oauth_nonce%3D7d8f3e4a%26oauth_signature_m\
+             ethod%3DHMAC-SHA1%26oauth_timestamp%3D137131201%26oauth_token%3Dkkk\
+             9d7dh3k39sjv7"
+        );
+    }
+
+    /// RFC 5849 §1.2: the signed request for the protected photo resource.
+    #[test]
+    fn matches_rfc5849_photos_example() {
+        let params = pairs(&[
+            ("oauth_consumer_key", "dpf43f3p2l4k3l03"),
+            ("oauth_token", "nnch734d00sl2jdk"),
+            ("oauth_signature_method", "HMAC-SHA1"),
+            ("oauth_timestamp", "137131202"),
+            ("oauth_nonce", "chapoH"),
+        ]);
+
+        let base_string = signature_base_string(
+            "GET",
+            "http://photos.example.net/photos?file=vacation.jpg&size=original",
+            &params,
+        )
+        .unwrap();
+        assert_eq!(
+            sign(&base_string, "kd94hf93k423kf44", "pfkkdhi9sl3r4s00"),
+            "MdpQcU8iPSUjWoN/UDMsK2sui9I="
+        );
+    }
+
+    #[test]
+    fn normalizes_scheme_host_and_default_port() {
+        let base =
+            signature_base_string("get", "HTTPS://API.Twitter.com:443/2/users/me", &[]).unwrap();
+        assert_eq!(base, "GET&https%3A%2F%2Fapi.twitter.com%2F2%2Fusers%2Fme&");
+
+        let base = signature_base_string("GET", "http://127.0.0.1:8080/2/tweets", &[]).unwrap();
+        assert_eq!(base, "GET&http%3A%2F%2F127.0.0.1%3A8080%2F2%2Ftweets&");
+    }
+
+    #[test]
+    fn signs_query_parameters() {
+        let signer = || {
+            Signer::with_nonce_and_timestamp(
+                Credentials {
+                    consumer_key: "key",
+                    consumer_secret: "secret",
+                    token: "token",
+                    token_secret: "token-secret",
+                },
+                "nonce".to_string(),
+                1_700_000_000,
+            )
+        };
+
+        let plain = signer()
+            .signature("GET", "https://api.twitter.com/2/users/me", &[])
+            .unwrap();
+        let with_query = signer()
+            .signature(
+                "GET",
+                "https://api.twitter.com/2/users/me?user.fields=public_metrics",
+                &[],
+            )
+            .unwrap();
+        assert_ne!(plain, with_query);
+    }
+}
@@ -0,0 +1,385 @@
+use std::fs;
+use std::path::Path;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{anyhow, Context, Result};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+
+const CIPHERTEXT_PREFIX: &str = "enc:v1:";
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+
+const PASSPHRASE_ENV: &str = "TAM_VAULT_PASSPHRASE";
+const KEY_FILE: &str = "vault.key";
+const SALT_FILE: &str = "vault.salt";
+const CHECK_FILE: &str = "vault.check";
+/// What the key check fingerprints, so it reveals nothing about the key.
+const CHECK_MESSAGE: &[u8] = b"tam-credential-vault";
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const KEYRING_SERVICE: &str = "com.twilia.bot-manager";
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const KEYRING_USER: &str = "credential-vault";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    Passphrase,
+    Keyring,
+    File,
+}
+
+/// `vault.check`: which key the stored credentials were encrypted with.
+#[derive(Debug, Serialize, Deserialize)]
+struct KeyCheck {
+    source: KeySource,
+    fingerprint: String,
+}
+
+/// Encrypts bot credentials before they are written to SQLite.
+///
+/// Stored values look like `enc:v1:<base64(nonce || ciphertext)>`; anything
+/// without that prefix is treated as legacy plaintext and returned unchanged.
+pub struct CredentialVault {
+    cipher: Aes256Gcm,
+    source: KeySource,
+    fingerprint: String,
+}
+
+impl CredentialVault {
+    /// Resolves the vault key in order: `TAM_VAULT_PASSPHRASE`, an existing
+    /// key file, the OS keyring, and finally a newly generated key, which is
+    /// kept in the keyring where there is one.
+    ///
+    /// Fails rather than continuing with a key that cannot read the stored
+    /// credentials: when the key does not match `vault.check`, or when no
+    /// key is found but `vault.check` shows one was in use.
+    pub fn open(data_dir: &Path) -> Result<Self> {
+        let passphrase = std::env::var(PASSPHRASE_ENV).ok();
+        Self::open_with(data_dir, passphrase.as_deref().filter(|p| !p.is_empty()))
+    }
+
+    fn open_with(data_dir: &Path, passphrase: Option<&str>) -> Result<Self> {
+        let check = read_key_check(data_dir)?;
+        let vault = Self::resolve_key(data_dir, passphrase, check.as_ref())?;
+
+        if let Some(check) = check {
+            if check.fingerprint != vault.fingerprint {
+                return Err(anyhow!(
+                    "The vault key from {:?} does not match the {:?} key the stored credentials were encrypted with{}",
+                    vault.source,
+                    check.source,
+                    passphrase_hint(check.source)
+                ));
+            }
+        }
+        Ok(vault)
+    }
+
+    fn resolve_key(
+        data_dir: &Path,
+        passphrase: Option<&str>,
+        check: Option<&KeyCheck>,
+    ) -> Result<Self> {
+        if let Some(passphrase) = passphrase {
+            return Self::from_passphrase(passphrase, data_dir);
+        }
+
+        let key_path = data_dir.join(KEY_FILE);
+        if key_path.exists() {
+            let key = read_key_file(&key_path)?;
+            return Ok(Self::with_key(key, KeySource::File));
+        }
+
+        if let Some(key) = read_keyring_key()? {
+            return Ok(Self::with_key(key, KeySource::Keyring));
+        }
+
+        if let Some(check) = check {
+            return Err(anyhow!(
+                "No vault key found, but the stored credentials were encrypted with a {:?} key{}",
+                check.source,
+                passphrase_hint(check.source)
+            ));
+        }
+
+        let key = random_bytes::<KEY_LEN>();
+        if write_keyring_key(&key)? {
+            println!("Stored a new credential vault key in the OS keyring");
+            return Ok(Self::with_key(key, KeySource::Keyring));
+        }
+        write_private_file(&key_path, STANDARD.encode(key).as_bytes())?;
+        println!("Created credential vault key file: {}", key_path.display());
+        Ok(Self::with_key(key, KeySource::File))
+    }
+
+    fn from_passphrase(passphrase: &str, data_dir: &Path) -> Result<Self> {
+        let salt_path = data_dir.join(SALT_FILE);
+        let salt = if salt_path.exists() {
+            STANDARD
+                .decode(fs::read_to_string(&salt_path)?.trim())
+                .context("Failed to decode vault salt")?
+        } else {
+            let salt = random_bytes::<SALT_LEN>();
+            write_private_file(&salt_path, STANDARD.encode(salt).as_bytes())?;
+            salt.to_vec()
+        };
+
+        let mut key = [0u8; KEY_LEN];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+            .map_err(|e| anyhow!("Failed to derive vault key: {}", e))?;
+
+        Ok(Self::with_key(key, KeySource::Passphrase))
+    }
+
+    pub fn with_key(key: [u8; KEY_LEN], source: KeySource) -> Self {
+        Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)),
+            source,
+            fingerprint: fingerprint(&key),
+        }
+    }
+
+    pub fn source(&self) -> KeySource {
+        self.source
+    }
+
+    /// Confirms the key can read `sample`, a credential already in the
+    /// database, and records it in `vault.check` if that is not there yet.
+    /// Databases from before the check file are caught by the sample.
+    pub fn confirm_key(&self, data_dir: &Path, sample: Option<&str>) -> Result<()> {
+        if let Some(sample) = sample {
+            self.decrypt(sample).map_err(|_| {
+                anyhow!(
+                    "The vault key from {:?} cannot decrypt the stored credentials{}",
+                    self.source,
+                    passphrase_hint(self.source)
+                )
+            })?;
+        }
+
+        let check_path = data_dir.join(CHECK_FILE);
+        if !check_path.exists() {
+            let check = KeyCheck {
+                source: self.source,
+                fingerprint: self.fingerprint.clone(),
+            };
+            write_private_file(&check_path, serde_json::to_string(&check)?.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub fn is_encrypted(value: &str) -> bool {
+        value.starts_with(CIPHERTEXT_PREFIX)
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| anyhow!("Failed to encrypt credential"))?;
+
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&ciphertext);
+
+        Ok(format!("{}{}", CIPHERTEXT_PREFIX, STANDARD.encode(payload)))
+    }
+
+    pub fn decrypt(&self, stored: &str) -> Result<String> {
+        let encoded = match stored.strip_prefix(CIPHERTEXT_PREFIX) {
+            Some(encoded) => encoded,
+            None => return Ok(stored.to_string()),
+        };
+
+        let payload = STANDARD
+            .decode(encoded)
+            .context("Failed to decode encrypted credential")?;
+        if payload.len() < NONCE_LEN {
+            return Err(anyhow!("Encrypted credential is truncated"));
+        }
+
+        let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow!("Failed to decrypt credential (vault key mismatch?)"))?;
+
+        String::from_utf8(plaintext).context("Decrypted credential is not valid UTF-8")
+    }
+}
+
+fn fingerprint(key: &[u8; KEY_LEN]) -> String {
+    let mut mac = <Hmac<Sha1> as Mac>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(CHECK_MESSAGE);
+    STANDARD.encode(mac.finalize().into_bytes())
+}
+
+fn read_key_check(data_dir: &Path) -> Result<Option<KeyCheck>> {
+    let path = data_dir.join(CHECK_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let check = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(Some(check))
+}
+
+fn passphrase_hint(source: KeySource) -> String {
+    match source {
+        KeySource::Passphrase => format!("; set {} to the passphrase used before", PASSPHRASE_ENV),
+        KeySource::Keyring | KeySource::File => String::new(),
+    }
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes
+}
+
+fn decode_key(encoded: &str) -> Result<[u8; KEY_LEN]> {
+    let bytes = STANDARD
+        .decode(encoded.trim())
+        .context("Failed to decode vault key")?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow!("Vault key must be {} bytes", KEY_LEN))
+}
+
+fn read_key_file(path: &Path) -> Result<[u8; KEY_LEN]> {
+    let encoded = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read vault key file {}", path.display()))?;
+    decode_key(&encoded)
+}
+
+fn write_private_file(path: &Path, contents: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    file.write_all(contents)?;
+    Ok(())
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn keyring_entry() -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER).context("Failed to open the OS keyring")
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn read_keyring_key() -> Result<Option<[u8; KEY_LEN]>> {
+    match keyring_entry()?.get_password() {
+        Ok(encoded) => decode_key(&encoded).map(Some),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(anyhow!(e).context("Failed to read the vault key from the OS keyring")),
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn write_keyring_key(key: &[u8; KEY_LEN]) -> Result<bool> {
+    keyring_entry()?
+        .set_password(&STANDARD.encode(key))
+        .context("Failed to store the vault key in the OS keyring")?;
+    Ok(true)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn read_keyring_key() -> Result<Option<[u8; KEY_LEN]>> {
+    Ok(None)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn write_keyring_key(_key: &[u8; KEY_LEN]) -> Result<bool> {
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn encrypts_and_decrypts() {
+        let dir = TempDir::new().unwrap();
+        let vault = CredentialVault::open_with(dir.path(), None).unwrap();
+        assert_eq!(vault.source(), KeySource::File);
+
+        let stored = vault.encrypt("consumer-secret").unwrap();
+        assert!(CredentialVault::is_encrypted(&stored));
+        assert!(!stored.contains("consumer-secret"));
+        assert_eq!(vault.decrypt(&stored).unwrap(), "consumer-secret");
+        assert_eq!(
+            vault.decrypt("legacy-plaintext").unwrap(),
+            "legacy-plaintext"
+        );
+
+        let reopened = CredentialVault::open_with(dir.path(), None).unwrap();
+        assert_eq!(reopened.decrypt(&stored).unwrap(), "consumer-secret");
+    }
+
+    #[test]
+    fn derives_the_same_key_from_the_passphrase() {
+        let dir = TempDir::new().unwrap();
+        let vault = CredentialVault::open_with(dir.path(), Some("correct horse")).unwrap();
+        assert_eq!(vault.source(), KeySource::Passphrase);
+        let stored = vault.encrypt("access-token").unwrap();
+        vault.confirm_key(dir.path(), Some(&stored)).unwrap();
+
+        let reopened = CredentialVault::open_with(dir.path(), Some("correct horse")).unwrap();
+        assert_eq!(reopened.decrypt(&stored).unwrap(), "access-token");
+        assert!(!dir.path().join(KEY_FILE).exists());
+    }
+
+    #[test]
+    fn refuses_a_key_that_does_not_match() {
+        let dir = TempDir::new().unwrap();
+        let vault = CredentialVault::open_with(dir.path(), Some("correct horse")).unwrap();
+        let stored = vault.encrypt("access-token").unwrap();
+        vault.confirm_key(dir.path(), Some(&stored)).unwrap();
+
+        let wrong = CredentialVault::open_with(dir.path(), Some("wrong horse"));
+        assert!(wrong.is_err());
+
+        // Without the passphrase no key is found, and none is generated.
+        let missing = CredentialVault::open_with(dir.path(), None);
+        assert!(missing.is_err());
+        assert!(!dir.path().join(KEY_FILE).exists());
+
+        let other = CredentialVault::with_key([7; KEY_LEN], KeySource::File);
+        assert!(other.decrypt(&stored).is_err());
+    }
+
+    #[test]
+    fn refuses_to_record_a_key_that_cannot_read_stored_credentials() {
+        let dir = TempDir::new().unwrap();
+        let stored = CredentialVault::with_key([1; KEY_LEN], KeySource::File)
+            .encrypt("access-token")
+            .unwrap();
+
+        let vault = CredentialVault::open_with(dir.path(), None).unwrap();
+        assert!(vault.confirm_key(dir.path(), Some(&stored)).is_err());
+        assert!(!dir.path().join(CHECK_FILE).exists());
+    }
+}
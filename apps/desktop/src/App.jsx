@@ -2,6 +2,7 @@ import React, { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { HashRouter as Router, Routes, Route, NavLink } from 'react-router-dom';
 import { FaUser, FaRobot, FaList, FaCog, FaTwitter } from 'react-icons/fa';
+import { formatError } from './utils/errors.js';
 import './App.css';
 
 // 外部コンポーネントをインポート
@@ -35,7 +36,7 @@ function App() {
       setUserSettings(settings);
     } catch (error) {
       console.error('Failed to fetch dashboard data:', error);
-      setError(error.toString());
+      setError(formatError(error));
       setUserSettings(prev => prev ?? {
         plan_type: 'starter',
         max_accounts: 1
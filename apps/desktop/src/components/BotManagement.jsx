@@ -1,8 +1,32 @@
 import React, { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { FaPlus, FaReply, FaTrash, FaPlay, FaPause, FaCog, FaTwitter, FaKey, FaRobot, FaPaperPlane, FaClock, FaFileAlt, FaTimes, FaArrowUp, FaArrowDown, FaList } from 'react-icons/fa';
+import { errorKind, formatError } from '../utils/errors.js';
 import './BotManagement.css';
 
+const STATUS_LABELS = {
+  active: '稼働中',
+  inactive: '停止中',
+  invalid_credentials: '認証エラー',
+};
+
+const CREDENTIAL_HEALTH_LABELS = {
+  unknown: '未確認',
+  valid: '有効',
+  invalid: '無効',
+  check_failed: '確認失敗',
+};
+
+const formatVerifiedAt = (dateString) => {
+  if (!dateString) return '-';
+  return new Date(dateString).toLocaleString('ja-JP', {
+    month: '2-digit',
+    day: '2-digit',
+    hour: '2-digit',
+    minute: '2-digit'
+  });
+};
+
 function BotManagement({ onUpdate, userSettings }) {
   
   const [botAccounts, setBotAccounts] = useState([]);
@@ -22,6 +46,8 @@ function BotManagement({ onUpdate, userSettings }) {
   const [isLoading, setIsLoading] = useState(true);
   const [error, setError] = useState(null);
   const [testingBotId, setTestingBotId] = useState(null);
+  const [verifyingBotId, setVerifyingBotId] = useState(null);
+  const [isRefreshingProfiles, setIsRefreshingProfiles] = useState(false);
   const [selectedBotForTweet, setSelectedBotForTweet] = useState(null);
   const [tweetContent, setTweetContent] = useState('');
   
@@ -63,7 +89,7 @@ function BotManagement({ onUpdate, userSettings }) {
       setBotAccounts(accounts || []);
     } catch (error) {
       console.error('API call failed:', error);
-      setError(`APIエラー: ${error.toString()}`);
+      setError(`APIエラー: ${formatError(error)}`);
     } finally {
       if (showLoader) {
         setIsLoading(false);
@@ -273,7 +299,7 @@ function BotManagement({ onUpdate, userSettings }) {
       closeModal();
     } catch (error) {
       console.error('Failed to save bot account:', error);
-      alert(`Bot アカウントの保存に失敗しました。\n\nエラー詳細: ${error}`);
+      alert(`Bot アカウントの保存に失敗しました。\n\nエラー詳細: ${formatError(error)}`);
     }
   };
 
@@ -307,7 +333,7 @@ function BotManagement({ onUpdate, userSettings }) {
       closeModal();
     } catch (error) {
       console.error('Failed to save bot config:', error);
-      alert(`Bot 設定の保存に失敗しました。\n\nエラー詳細: ${error}`);
+      alert(`Bot 設定の保存に失敗しました。\n\nエラー詳細: ${formatError(error)}`);
     }
   };
 
@@ -339,6 +365,43 @@ function BotManagement({ onUpdate, userSettings }) {
     }
   };
 
+  const handleVerifyCredentials = async (bot) => {
+    setVerifyingBotId(bot.id);
+    try {
+      const result = await invoke('verify_bot_credentials', { accountId: bot.id });
+      const icon = result.credential_health !== 'valid' ? '❌' : result.name_mismatch ? '⚠️' : '✅';
+      alert(`${icon} ${result.message}`);
+      fetchBotAccounts();
+      if (onUpdate) onUpdate();
+    } catch (error) {
+      console.error('Failed to verify credentials:', error);
+      alert(`❌ 認証情報の確認中にエラーが発生しました。\n\nエラー詳細: ${formatError(error)}`);
+    } finally {
+      setVerifyingBotId(null);
+    }
+  };
+
+  const handleRefreshProfiles = async () => {
+    setIsRefreshingProfiles(true);
+    try {
+      const report = await invoke('refresh_bot_profiles');
+      const failures = report.failed
+        .map((failure) => `${failure.account_name}: ${failure.message}`)
+        .join('\n');
+      alert(
+        `プロフィールを更新しました。\n\n確認済み: ${report.verified.length}件\n失敗: ${report.failed.length}件` +
+        (failures ? `\n\n${failures}` : '')
+      );
+      fetchBotAccounts();
+      if (onUpdate) onUpdate();
+    } catch (error) {
+      console.error('Failed to refresh bot profiles:', error);
+      alert(`❌ プロフィールの更新に失敗しました。\n\nエラー詳細: ${formatError(error)}`);
+    } finally {
+      setIsRefreshingProfiles(false);
+    }
+  };
+
   const handleTestTweet = async (botId, botName) => {
     setSelectedBotForTweet({ id: botId, name: botName });
     setTweetContent('');
@@ -378,7 +441,11 @@ function BotManagement({ onUpdate, userSettings }) {
       }
     } catch (error) {
       console.error('Failed to send tweet:', error);
-      alert(`❌ 投稿中にエラーが発生しました。\n\nエラー詳細: ${error}\n\nAPI Keyの設定を確認してください。`);
+      if (['rate_limited', 'quota_exceeded'].includes(errorKind(error))) {
+        alert(`⏳ ${formatError(error)}`);
+        return;
+      }
+      alert(`❌ 投稿中にエラーが発生しました。\n\nエラー詳細: ${formatError(error)}\n\nAPI Keyの設定を確認してください。`);
     } finally {
       setTestingBotId(null);
     }
@@ -423,7 +490,7 @@ function BotManagement({ onUpdate, userSettings }) {
       closeModal();
     } catch (error) {
       console.error('Failed to save reply settings:', error);
-      alert(`❌ 返信設定の保存に失敗しました。\n\nエラー詳細: ${error}`);
+      alert(`❌ 返信設定の保存に失敗しました。\n\nエラー詳細: ${formatError(error)}`);
     }
   };
 
@@ -526,12 +593,21 @@ function BotManagement({ onUpdate, userSettings }) {
       <div className="card">
         <div className="card-header">
           <h2 className="card-title">Bot一覧</h2>
-          <button 
-            className="btn btn-primary"
-            onClick={openAddModal}
-          >
-            <FaPlus /> 新規追加
-          </button>
+          <div className="card-header-actions">
+            <button
+              className="btn btn-secondary"
+              onClick={handleRefreshProfiles}
+              disabled={isRefreshingProfiles || botAccounts.length === 0}
+            >
+              {isRefreshingProfiles ? '⏳ 更新中...' : <><FaKey /> プロフィール更新</>}
+            </button>
+            <button 
+              className="btn btn-primary"
+              onClick={openAddModal}
+            >
+              <FaPlus /> 新規追加
+            </button>
+          </div>
         </div>
 
         {botAccounts.length === 0 ? (
@@ -555,9 +631,12 @@ function BotManagement({ onUpdate, userSettings }) {
                   <div className="bot-header">
                     <div className="bot-info">
                       <h3 className="bot-name">{bot.account_name || 'Unknown'}</h3>
+                      {bot.twitter_display_name && (
+                        <div className="bot-display-name">{bot.twitter_display_name}</div>
+                      )}
                       <div className="bot-username">
                         <FaTwitter className="twitter-icon" />
-                        {bot.account_name || 'unknown'}
+                        {bot.twitter_username ? `@${bot.twitter_username}` : (bot.account_name || 'unknown')}
                       </div>
                     </div>
                     <div className="bot-status">
@@ -566,7 +645,7 @@ function BotManagement({ onUpdate, userSettings }) {
                       </div>
                       <div className={`status-badge ${bot.status || 'inactive'}`}>
                         <div className="status-indicator"></div>
-                        {(bot.status === 'active') ? '稼働中' : '停止中'}
+                        {STATUS_LABELS[bot.status] || STATUS_LABELS.inactive}
                       </div>
                     </div>
                   </div>
@@ -579,7 +658,14 @@ function BotManagement({ onUpdate, userSettings }) {
                     <div className="stat">
                       <span className="stat-label">ステータス:</span>
                       <span className="stat-value">
-                        {bot.status === 'active' ? '稼働中' : '停止中'}
+                        {STATUS_LABELS[bot.status] || STATUS_LABELS.inactive}
+                      </span>
+                    </div>
+                    <div className="stat">
+                      <span className="stat-label">認証:</span>
+                      <span className={`stat-value credential-health ${bot.credential_health || 'unknown'}`}>
+                        {CREDENTIAL_HEALTH_LABELS[bot.credential_health] || CREDENTIAL_HEALTH_LABELS.unknown}
+                        {bot.last_verified_at && ` (${formatVerifiedAt(bot.last_verified_at)})`}
                       </span>
                     </div>
                   </div>
@@ -610,6 +696,15 @@ function BotManagement({ onUpdate, userSettings }) {
                       )}
                     </button>
                     
+                    <button
+                      className="btn btn-secondary"
+                      onClick={() => handleVerifyCredentials(bot)}
+                      disabled={verifyingBotId === bot.id}
+                      title="認証確認"
+                    >
+                      {verifyingBotId === bot.id ? '⏳' : <FaKey />}
+                    </button>
+                    
                     <button
                       className="btn btn-secondary"
                       onClick={() => openConfigModal(bot)}
@@ -10,12 +10,14 @@ import {
   FaSearch,
   FaList,
 } from 'react-icons/fa';
+import { errorCodeLabel } from '../utils/errors.js';
 import './ExecutionLogs.css';
 
 const LOGS_PER_PAGE = 50;
 const LOG_REFRESH_INTERVAL_MS = 30000;
 const LOG_TYPE_LABELS = {
   tweet: 'ツイート',
+  read: '読み取り',
   error: 'エラー',
   info: '情報',
   warning: '警告',
@@ -332,6 +334,9 @@ function ExecutionLogs() {
                       <span className={`log-type ${log.log_type}`}>
                         {getLogTypeText(log.log_type)}
                       </span>
+                      {log.error_code && (
+                        <span className="log-error-code">{errorCodeLabel(log.error_code)}</span>
+                      )}
                     </div>
                     <div className="log-status">{getLogIcon(log.log_type, log.status)}</div>
                   </div>
@@ -1,11 +1,23 @@
 // components/MyPage.jsx
-import React, { useState } from 'react';
+import React, { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { FaUser, FaRobot, FaChartLine, FaExclamationTriangle, FaCrown, FaCalendarCheck } from 'react-icons/fa';
+import { errorCodeLabel } from '../utils/errors.js';
 import './MyPage.css';
 
 function MyPage({ stats, userSettings, onStatsUpdate }) {
   const [isUpdating, setIsUpdating] = useState(false);
+  const [rateLimits, setRateLimits] = useState([]);
+  const [quotaUsage, setQuotaUsage] = useState([]);
+
+  useEffect(() => {
+    invoke('get_rate_limits')
+      .then(setRateLimits)
+      .catch(error => console.error('Failed to fetch rate limits:', error));
+    invoke('get_quota_usage')
+      .then(setQuotaUsage)
+      .catch(error => console.error('Failed to fetch quota usage:', error));
+  }, [stats]);
 
   const handleUpdateSettings = async (newPlan) => {
     setIsUpdating(true);
@@ -33,6 +45,13 @@ function MyPage({ stats, userSettings, onStatsUpdate }) {
     });
   };
 
+  const formatReset = (resetAt) => {
+    return new Date(resetAt * 1000).toLocaleTimeString('ja-JP', {
+      hour: '2-digit',
+      minute: '2-digit'
+    });
+  };
+
   if (!userSettings) {
     return (
       <div className="loading">
@@ -175,10 +194,89 @@ function MyPage({ stats, userSettings, onStatsUpdate }) {
             <div className="stat-number">{stats.error_count}</div>
             <div className="stat-label">エラー件数</div>
             <div className="stat-sub">今日発生分</div>
+            {stats.errors_by_code?.length > 0 && (
+              <div className="stat-sub error-breakdown">
+                {stats.errors_by_code
+                  .map(entry => `${errorCodeLabel(entry.error_code)} ${entry.count}`)
+                  .join(' / ')}
+              </div>
+            )}
           </div>
         </div>
       </div>
 
+      {/* 月間API利用量 */}
+      {quotaUsage.length > 0 && (
+        <div className="card">
+          <div className="card-header">
+            <h2 className="card-title">月間API利用量 ({quotaUsage[0].month})</h2>
+          </div>
+
+          <table className="rate-limit-table">
+            <thead>
+              <tr>
+                <th>アカウント</th>
+                <th>API種別</th>
+                <th>投稿</th>
+                <th>読み取り</th>
+              </tr>
+            </thead>
+            <tbody>
+              {quotaUsage.map(usage => (
+                <tr key={usage.account_id}>
+                  <td>{usage.account_name}</td>
+                  <td>{usage.tier}</td>
+                  <td className={`quota-${usage.post_status}`}>
+                    {usage.posts_used} / {usage.post_budget}
+                  </td>
+                  <td className={`quota-${usage.read_status}`}>
+                    {usage.reads_used} / {usage.read_budget}
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+
+      {/* API残量 */}
+      {rateLimits.length > 0 && (
+        <div className="card">
+          <div className="card-header">
+            <h2 className="card-title">API残量</h2>
+          </div>
+
+          <table className="rate-limit-table">
+            <thead>
+              <tr>
+                <th>アカウント</th>
+                <th>API種別</th>
+                <th>エンドポイント</th>
+                <th>残り</th>
+                <th>リセット</th>
+              </tr>
+            </thead>
+            <tbody>
+              {rateLimits.map(limit => (
+                <tr
+                  key={`${limit.account_id}-${limit.endpoint ?? ''}`}
+                  className={limit.remaining === 0 ? 'exhausted' : ''}
+                >
+                  <td>{limit.account_name}</td>
+                  <td>{limit.api_type}</td>
+                  <td>{limit.endpoint ?? '未使用'}</td>
+                  <td>
+                    {limit.remaining ?? '-'}
+                    {limit.limit != null && ` / ${limit.limit}`}
+                  </td>
+                  <td>{limit.reset_at ? formatReset(limit.reset_at) : '-'}</td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+
       {/* プラン管理 */}
       {userSettings.plan_type === 'starter' && (
         <div className="card">
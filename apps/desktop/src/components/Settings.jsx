@@ -3,10 +3,10 @@ import React, { useEffect, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { save, open } from '@tauri-apps/plugin-dialog';
 import { FaSave, FaUpload, FaInfoCircle, FaCog, FaDatabase, FaShieldAlt, FaGithub } from 'react-icons/fa';
+import { formatError } from '../utils/errors.js';
 import './Settings.css';
 
-const DEFAULT_GITHUB_EXPORT_PATH = 'config/actions/github-config.json';
-const CONFIG_SPLIT_COMMAND = 'npm run config:split';
+const DEFAULT_GITHUB_EXPORT_PATH = 'config/actions';
 
 const renderMessageContent = (text) => {
   if (!text) return null;
@@ -22,26 +22,30 @@ const renderMessageContent = (text) => {
 function Settings({ userSettings, onSettingsUpdate }) {
   const [exportPath, setExportPath] = useState('');
   const [importPath, setImportPath] = useState('');
+  const [importOptions, setImportOptions] = useState({ mode: 'merge', on_conflict: 'skip' });
   const [gitHubExportPath, setGitHubExportPath] = useState(DEFAULT_GITHUB_EXPORT_PATH);
   const [isExporting, setIsExporting] = useState(false);
   const [isImporting, setIsImporting] = useState(false);
   const [isGitHubExporting, setIsGitHubExporting] = useState(false);
+  const [isStateImporting, setIsStateImporting] = useState(false);
   const [message, setMessage] = useState(null);
-  const [hasCopiedSplit, setHasCopiedSplit] = useState(false);
   const [appSettings, setAppSettings] = useState({
     autoStart: false,
     notifications: true,
     darkMode: false,
-    logLevel: 'info'
+    logLevel: 'info',
+    locale: 'ja'
   });
+  const [retryPolicy, setRetryPolicy] = useState(null);
 
   useEffect(() => {
-    if (!hasCopiedSplit) return undefined;
-    const timer = window.setTimeout(() => setHasCopiedSplit(false), 2000);
-    return () => {
-      window.clearTimeout(timer);
-    };
-  }, [hasCopiedSplit]);
+    invoke('get_locale')
+      .then((locale) => setAppSettings(prev => ({ ...prev, locale })))
+      .catch((error) => console.error('Failed to load locale:', error));
+    invoke('get_retry_policy')
+      .then(setRetryPolicy)
+      .catch((error) => console.error('Failed to load retry policy:', error));
+  }, []);
 
 
   // ダイアログでエクスポート先を選択
@@ -96,12 +100,9 @@ function Settings({ userSettings, onSettingsUpdate }) {
   // ダイアログでGitHub設定ファイルの保存先を選択
   const selectGitHubExportPath = async () => {
     try {
-      const path = await save({
-        title: 'GitHub Actions用設定ファイルの保存先を選択',
-        filters: [{
-          name: 'JSON',
-          extensions: ['json']
-        }],
+      const path = await open({
+        title: 'GitHub Actions用設定の保存先フォルダを選択',
+        directory: true,
         defaultPath: DEFAULT_GITHUB_EXPORT_PATH
       });
       if (path) {
@@ -110,7 +111,7 @@ function Settings({ userSettings, onSettingsUpdate }) {
     } catch (error) {
       console.error('ファイル選択エラー:', error);
       // フォールバック：プロンプトを使用
-      const path = prompt('GitHub Actions用設定ファイルの保存先を入力してください：', 
+      const path = prompt('GitHub Actions用設定の保存先フォルダを入力してください：', 
         DEFAULT_GITHUB_EXPORT_PATH);
       if (path) {
         setGitHubExportPath(path);
@@ -128,15 +129,20 @@ function Settings({ userSettings, onSettingsUpdate }) {
     setMessage(null);
     
     try {
-      await invoke('export_data', { path: exportPath });
+      const report = await invoke('export_data', { path: exportPath });
+      const lines = [`データを正常にエクスポートしました: ${report?.path ?? exportPath}`];
+      if (report?.redacted_paths?.length) {
+        lines.push(`認証情報と思われる値 ${report.redacted_paths.length}件を [REDACTED] に置き換えました。`);
+      }
+      (report?.warnings ?? []).forEach((warning) => lines.push(`警告: ${warning}`));
       setMessage({ 
-        type: 'success', 
-        text: `データを正常にエクスポートしました: ${exportPath}` 
+        type: report?.warnings?.length ? 'warning' : 'success', 
+        text: lines.join('\n')
       });
       setExportPath('');
     } catch (error) {
       console.error('Export failed:', error);
-      setMessage({ type: 'error', text: `エクスポートに失敗しました: ${error}` });
+      setMessage({ type: 'error', text: `エクスポートに失敗しました: ${formatError(error)}` });
     } finally {
       setIsExporting(false);
     }
@@ -144,7 +150,7 @@ function Settings({ userSettings, onSettingsUpdate }) {
 
   const handleGitHubExport = async () => {
     if (!gitHubExportPath) {
-      setMessage({ type: 'error', text: 'GitHub Actions用設定ファイルの保存先を選択してください' });
+      setMessage({ type: 'error', text: 'GitHub Actions用設定の保存先フォルダを選択してください' });
       return;
     }
     
@@ -152,34 +158,133 @@ function Settings({ userSettings, onSettingsUpdate }) {
     setMessage(null);
     
     try {
-      await invoke('export_github_config', { path: gitHubExportPath });
+      const report = await invoke('export_github_config', { path: gitHubExportPath });
+      const lines = [
+        `GitHub Actions用設定を正常にエクスポートしました: ${report?.config_path ?? gitHubExportPath}`,
+        `運用状態ファイル: ${report?.state_path}`
+      ];
+      if (report?.preserved_state_entries) {
+        lines.push(`既存の運用状態 ${report.preserved_state_entries}件 (投稿位置・返信チェック位置) を引き継ぎました。`);
+      }
+      if (report?.manifest_path) {
+        lines.push(`認証情報は secrets 参照として出力しました。必要なリポジトリシークレット (${report.required_secrets}件) の一覧: ${report.manifest_path}`);
+      }
+      (report?.workflow_paths ?? []).forEach((path) => lines.push(`ワークフローのシークレット設定を更新しました: ${path}`));
+      (report?.warnings ?? []).forEach((warning) => lines.push(`警告: ${warning}`));
       setMessage({ 
-        type: 'success', 
-        text: [
-          `GitHub Actions用設定を正常にエクスポートしました: ${gitHubExportPath}`,
-          '続けて npm run config:split を実行し、設定ファイルを分割してください。'
-        ].join('\n')
+        type: report?.warnings?.length ? 'warning' : 'success', 
+        text: lines.join('\n')
       });
       setGitHubExportPath(DEFAULT_GITHUB_EXPORT_PATH);
     } catch (error) {
       console.error('GitHub export failed:', error);
-      setMessage({ type: 'error', text: `GitHub Actions用設定のエクスポートに失敗しました: ${error}` });
+      setMessage({ type: 'error', text: `GitHub Actions用設定のエクスポートに失敗しました: ${formatError(error)}` });
     } finally {
       setIsGitHubExporting(false);
     }
   };
 
-  const handleCopySplitCommand = async () => {
+  const describeStateImport = (report) => {
+    const lines = [];
+    report.bot_changes.forEach((change) => {
+      lines.push(`${change.account_name}: 投稿位置 ${change.current_index_before} → ${change.current_index_after}`);
+    });
+    report.reply_changes.forEach((change) => {
+      lines.push(`返信設定 #${change.reply_setting_id} (監視対象 #${change.target_bot_id}): 最終確認 ${change.last_checked_before ?? '-'} → ${change.last_checked_after}`);
+    });
+    report.conflicts.forEach((conflict) => {
+      const byId = conflict.name_for_id ?? 'なし';
+      const byName = conflict.id_for_name != null ? `#${conflict.id_for_name}` : 'なし';
+      lines.push(`ID/名前の不一致 (取り込みません): #${conflict.state_account_id} ${conflict.state_account_name} (ID #${conflict.state_account_id} のアカウント: ${byId} / 名前 ${conflict.state_account_name} のアカウント: ${byName})`);
+    });
+    report.reply_conflicts.forEach((conflict) => {
+      const byId = conflict.reply_bot_for_id != null ? `Bot #${conflict.reply_bot_for_id}` : 'なし';
+      lines.push(`返信設定ID/返信Botの不一致 (取り込みません): 返信設定 #${conflict.state_id} / 返信Bot #${conflict.state_reply_bot_id} (返信設定 #${conflict.state_id} の返信Bot: ${byId})`);
+    });
+    report.unknown.forEach((entry) => {
+      switch (entry.kind) {
+        case 'account':
+          lines.push(`不明なアカウント: ${entry.account_name ?? `#${entry.account_id}`}`);
+          break;
+        case 'schedule':
+          lines.push(`スケジュール未設定: ${entry.account_name}`);
+          break;
+        case 'reply_setting':
+          lines.push(`不明な返信設定: #${entry.id ?? '-'} (返信Bot #${entry.reply_bot_id ?? '-'})`);
+          break;
+        default:
+          lines.push(`監視対象外: 返信設定 #${entry.reply_setting_id} / Bot #${entry.target_bot_id}`);
+      }
+    });
+    return lines;
+  };
+
+  const handleImportSystemState = async () => {
+    if (!gitHubExportPath) {
+      setMessage({ type: 'error', text: 'GitHub Actions用設定の保存先フォルダを選択してください' });
+      return;
+    }
+
+    setIsStateImporting(true);
+    setMessage(null);
+
     try {
-      if (!navigator?.clipboard?.writeText) {
-        throw new Error('Clipboard API is not available');
+      const preview = await invoke('import_system_state', { path: gitHubExportPath, apply: false });
+      const lines = describeStateImport(preview);
+      const changeCount = preview.bot_changes.length + preview.reply_changes.length;
+
+      if (changeCount === 0) {
+        setMessage({
+          type: preview.unknown.length || preview.conflicts.length || preview.reply_conflicts.length ? 'warning' : 'success',
+          text: ['運用状態はデータベースと一致しています。', ...lines].join('\n')
+        });
+        return;
       }
-      await navigator.clipboard.writeText(CONFIG_SPLIT_COMMAND);
-      setHasCopiedSplit(true);
+
+      if (!window.confirm(`${preview.path} から以下の ${changeCount}件を取り込みます。続行しますか？\n\n${lines.join('\n')}`)) {
+        return;
+      }
+
+      const report = await invoke('import_system_state', { path: gitHubExportPath, apply: true });
+      setMessage({
+        type: report.unknown.length || report.conflicts.length || report.reply_conflicts.length ? 'warning' : 'success',
+        text: [`運用状態を取り込みました: ${report.path}`, ...describeStateImport(report)].join('\n')
+      });
+      if (onSettingsUpdate) onSettingsUpdate();
     } catch (error) {
-      console.error('Copy split command failed:', error);
-      setMessage({ type: 'error', text: 'コマンドのコピーに失敗しました。手動でコピーしてください。' });
+      console.error('System state import failed:', error);
+      setMessage({ type: 'error', text: `運用状態の取り込みに失敗しました: ${formatError(error)}` });
+    } finally {
+      setIsStateImporting(false);
+    }
+  };
+
+  const describeImport = (report) => {
+    const formatLabel = report.format === 'tam-actions-config' ? 'GitHub Actions用設定' : 'データエクスポート';
+    const outcomeLabel = { created: '追加', updated: '上書き', skipped: 'スキップ' };
+    const countLabel = (counts) => `追加 ${counts.created} / 更新 ${counts.updated} / スキップ ${counts.skipped}`;
+    const lines = [
+      report.upgrades.length
+        ? `${formatLabel} v${report.source_version} を v${report.version} に変換しました (${report.upgrades.join(' → ')})`
+        : `${formatLabel} v${report.version}`
+    ];
+    if (report.deleted_accounts) {
+      lines.push(`既存のアカウント ${report.deleted_accounts}件 を削除`);
+    }
+    report.accounts.forEach((account) => {
+      const name = account.imported_name === account.account_name
+        ? account.account_name
+        : `${account.account_name} → ${account.imported_name}`;
+      lines.push(`アカウント ${name}: ${outcomeLabel[account.outcome]}${account.credentials_missing ? ' (認証情報の再入力が必要)' : ''}`);
+    });
+    lines.push(`スケジュール: ${countLabel(report.schedules)}`);
+    lines.push(`返信設定: ${countLabel(report.reply_settings)}`);
+    lines.push(`実行ログ: ${countLabel(report.logs)}`);
+    if (report.user_settings.updated) {
+      lines.push('ユーザー設定を上書き');
     }
+    report.warnings.forEach((warning) => lines.push(`注意: ${warning}`));
+    return lines;
   };
 
   const handleImport = async () => {
@@ -187,26 +292,34 @@ function Settings({ userSettings, onSettingsUpdate }) {
       setMessage({ type: 'error', text: 'インポートするファイルを選択してください' });
       return;
     }
-    
-    if (!window.confirm('インポートを実行すると既存のデータが上書きされる可能性があります。続行しますか？')) {
-      return;
-    }
-    
+
     setIsImporting(true);
     setMessage(null);
-    
+
     try {
-      // TODO: インポート機能の実装が必要
-      // await invoke('import_data', { path: importPath });
-      setMessage({ 
-        type: 'warning', 
-        text: 'インポート機能は現在開発中です。' 
+      const preview = await invoke('import_data', {
+        path: importPath,
+        options: { ...importOptions, dry_run: true }
+      });
+      const modeLabel = importOptions.mode === 'replace' ? '既存のデータを置き換えて' : '既存のデータに追加して';
+      if (!window.confirm(`${preview.path} を${modeLabel}インポートします。続行しますか？\n\n${describeImport(preview).join('\n')}`)) {
+        return;
+      }
+
+      const report = await invoke('import_data', { path: importPath, options: importOptions });
+      const needsCredentials = report.accounts.some((account) => account.credentials_missing);
+      setMessage({
+        type: needsCredentials || report.warnings.length ? 'warning' : 'success',
+        text: [
+          `インポートが完了しました: ${report.path}`,
+          ...(report.safety_backup ? [`インポート前のデータは ${report.safety_backup} にバックアップしました`] : []),
+          ...describeImport(report)
+        ].join('\n')
       });
-      setImportPath('');
       if (onSettingsUpdate) onSettingsUpdate();
     } catch (error) {
       console.error('Import failed:', error);
-      setMessage({ type: 'error', text: `インポートに失敗しました: ${error}` });
+      setMessage({ type: 'error', text: `インポートに失敗しました: ${formatError(error)}` });
     } finally {
       setIsImporting(false);
     }
@@ -219,13 +332,24 @@ function Settings({ userSettings, onSettingsUpdate }) {
     }));
   };
 
+  const handleRetryPolicyChange = (key, value) => {
+    setRetryPolicy(prev => ({
+      ...prev,
+      [key]: Number(value)
+    }));
+  };
+
   const saveAppSettings = async () => {
     try {
-      // TODO: アプリケーション設定の保存機能
+      // TODO: 言語と再試行以外のアプリケーション設定の保存機能
+      await invoke('set_locale', { locale: appSettings.locale });
+      if (retryPolicy) {
+        setRetryPolicy(await invoke('set_retry_policy', { policy: retryPolicy }));
+      }
       setMessage({ type: 'success', text: '設定を保存しました' });
     } catch (error) {
       console.error('Save settings failed:', error);
-      setMessage({ type: 'error', text: '設定の保存に失敗しました' });
+      setMessage({ type: 'error', text: `設定の保存に失敗しました: ${formatError(error)}` });
     }
   };
 
@@ -331,6 +455,55 @@ function Settings({ userSettings, onSettingsUpdate }) {
               <option value="debug">すべて</option>
             </select>
           </div>
+
+          <div className="setting-item">
+            <div className="setting-info">
+              <h4>言語</h4>
+              <p>エラーメッセージと実行ログの表示言語を設定します</p>
+            </div>
+            <select
+              className="form-select"
+              value={appSettings.locale}
+              onChange={(e) => handleSettingsChange('locale', e.target.value)}
+            >
+              <option value="ja">日本語</option>
+              <option value="en">English</option>
+            </select>
+          </div>
+
+          {retryPolicy && (
+            <>
+              <div className="setting-item">
+                <div className="setting-info">
+                  <h4>投稿の再試行回数</h4>
+                  <p>通信エラーやサーバーエラー(5xx)の時だけ、初回を含めてこの回数まで投稿を試みます</p>
+                </div>
+                <input
+                  type="number"
+                  className="form-input"
+                  min="1"
+                  max="10"
+                  value={retryPolicy.max_attempts}
+                  onChange={(e) => handleRetryPolicyChange('max_attempts', e.target.value)}
+                />
+              </div>
+
+              <div className="setting-item">
+                <div className="setting-info">
+                  <h4>再試行の期限(秒)</h4>
+                  <p>最初の投稿からこの時間を過ぎたら再試行しません</p>
+                </div>
+                <input
+                  type="number"
+                  className="form-input"
+                  min="1"
+                  max="600"
+                  value={retryPolicy.deadline_secs}
+                  onChange={(e) => handleRetryPolicyChange('deadline_secs', e.target.value)}
+                />
+              </div>
+            </>
+          )}
         </div>
 
         <div className="card-actions">
@@ -371,7 +544,7 @@ function Settings({ userSettings, onSettingsUpdate }) {
                   className="form-input"
                   value={gitHubExportPath}
                   onChange={(e) => setGitHubExportPath(e.target.value)}
-                  placeholder="保存先: config/actions/github-config.json"
+                  placeholder="保存先フォルダ: config/actions"
                 />
                 <button className="btn btn-secondary" onClick={selectGitHubExportPath}>
                   ファイル選択
@@ -387,24 +560,22 @@ function Settings({ userSettings, onSettingsUpdate }) {
                 {isGitHubExporting ? 'エクスポート中...' : 'GitHub Actions用設定をエクスポート'}
               </button>
 
+              <button
+                className="btn btn-secondary"
+                onClick={handleImportSystemState}
+                disabled={isStateImporting || !gitHubExportPath}
+              >
+                <FaUpload />
+                {isStateImporting ? '取り込み中...' : '運用状態 (system-state.json) を取り込む'}
+              </button>
+
               <div className="github-instructions">
                 <p className="github-instructions-title">エクスポート後の手順</p>
                 <ol className="github-step-list">
                   <li>
-                    <div className="step-title">設定ファイルを分割する</div>
-                    <p className="step-note">プロジェクトルートで以下のコマンドを実行してください。</p>
-                    <div className="command-row">
-                      <code className="inline-code">{CONFIG_SPLIT_COMMAND}</code>
-                      <button
-                        type="button"
-                        className="btn btn-secondary btn-copy"
-                        onClick={handleCopySplitCommand}
-                      >
-                        コマンドをコピー
-                      </button>
-                      {hasCopiedSplit && <span className="copy-feedback">コピーしました</span>}
-                    </div>
-                    <p className="step-note">`config/actions/github-config.json` から `user-config.json` と `system-state.json` が生成されます。</p>
+                    <div className="step-title">出力されるファイル</div>
+                    <p className="step-note">保存先フォルダに <span className="inline-code">user-config.json</span> と <span className="inline-code">system-state.json</span> が出力されます。</p>
+                    <p className="step-note">既存の <span className="inline-code">system-state.json</span> にある投稿位置と返信チェック位置はそのまま引き継がれます。</p>
                   </li>
                   <li>
                     <div className="step-title">コミットするファイル</div>
@@ -413,9 +584,9 @@ function Settings({ userSettings, onSettingsUpdate }) {
                   <li>
                     <div className="step-title">自動管理されるファイル</div>
                     <p className="step-note"><span className="inline-code">config/actions/system-state.json</span> は GitHub Actions が自動更新します。手動でコミットする必要はありません。</p>
+                    <p className="step-note">ワークフローが進めた投稿位置を「運用状態を取り込む」でアプリに反映できます。</p>
                   </li>
                 </ol>
-                <p className="step-note step-note-muted">`config/actions/github-config.json` は .gitignore に登録されているため、リポジトリには追加されません。</p>
               </div>
             </div>
           </div>
@@ -478,7 +649,28 @@ function Settings({ userSettings, onSettingsUpdate }) {
             <div className="backup-content">
               <div className="backup-warning">
                 <FaShieldAlt className="warning-icon" />
-                <span>インポートを実行すると既存のデータが置き換えられます</span>
+                <span>「置き換え」では既存のアカウントと設定が削除されます。実行前に自動でバックアップを作成します</span>
+              </div>
+
+              <div className="import-options">
+                <select
+                  className="form-select"
+                  value={importOptions.mode}
+                  onChange={(e) => setImportOptions(prev => ({ ...prev, mode: e.target.value }))}
+                >
+                  <option value="merge">既存のデータに追加</option>
+                  <option value="replace">既存のデータを置き換え</option>
+                </select>
+                <select
+                  className="form-select"
+                  value={importOptions.on_conflict}
+                  onChange={(e) => setImportOptions(prev => ({ ...prev, on_conflict: e.target.value }))}
+                  disabled={importOptions.mode === 'replace'}
+                >
+                  <option value="skip">同名のアカウントはスキップ</option>
+                  <option value="overwrite">同名のアカウントを上書き</option>
+                  <option value="rename">同名のアカウントは別名で追加</option>
+                </select>
               </div>
               
               <div className="path-selector">
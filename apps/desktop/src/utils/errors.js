@@ -0,0 +1,30 @@
+// utils/errors.js
+// Tauri コマンドのエラーは { kind, message, details } で返ってくる。
+// message はバックエンドで設定言語に翻訳済み。分岐には kind を使う。
+
+export const errorKind = (error) =>
+  error && typeof error === 'object' && typeof error.kind === 'string' ? error.kind : null;
+
+export const formatError = (error) => {
+  if (errorKind(error)) {
+    return error.message;
+  }
+  return error instanceof Error ? error.message : String(error);
+};
+
+// execution_logs.error_code の表示名。未知のコードはそのまま表示する。
+const ERROR_CODE_LABELS = {
+  unauthorized: '認証エラー',
+  forbidden: '権限エラー',
+  duplicate_content: '重複投稿',
+  account_suspended: 'アカウント凍結',
+  rate_limited: 'レート制限',
+  usage_capped: '月間上限',
+  quota_exceeded: '投稿上限',
+  invalid_request: '不正なリクエスト',
+  server_error: 'サーバーエラー',
+  network: '通信エラー',
+  other: 'その他',
+};
+
+export const errorCodeLabel = (code) => ERROR_CODE_LABELS[code] || code;
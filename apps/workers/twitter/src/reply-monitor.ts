@@ -86,15 +86,32 @@ const postReply = async (client: any, content: string, tweetId: string, botName:
   }
 };
 
-const getLatestTweetId = async (client: any, username: string, sinceId?: string | null) => {
+/**
+ * Resolves the account's Twitter user id, preferring the one the desktop app
+ * stored at verification time so a renamed handle still resolves.
+ */
+const resolveUserId = async (client: any, account: Account) => {
+  if (account.twitter_user_id) {
+    return account.twitter_user_id;
+  }
+
+  const username = account.twitter_username ?? account.account_name;
+  const userResponse = await client.v2.userByUsername(username);
+  if (!userResponse?.data) {
+    log.warn(`User ${username} not found`);
+    return null;
+  }
+  return String(userResponse.data.id);
+};
+
+const getLatestTweetId = async (client: any, account: Account, sinceId?: string | null) => {
   if (config.dryRun) {
     return sinceId ? null : `dry_run_tweet_${Date.now()}`;
   }
 
   try {
-    const userResponse = await client.v2.userByUsername(username);
-    if (!userResponse?.data) {
-      log.warn(`User ${username} not found`);
+    const userId = await resolveUserId(client, account);
+    if (!userId) {
       return null;
     }
 
@@ -107,12 +124,12 @@ const getLatestTweetId = async (client: any, username: string, sinceId?: string
       options.since_id = sinceId;
     }
 
-    const timeline = await client.v2.userTimeline(userResponse.data.id, options);
+    const timeline = await client.v2.userTimeline(userId, options);
     const tweets: any[] = timeline?.data?.data ?? timeline?.data ?? [];
     const latest = tweets.find((item) => item?.id);
     return latest ? String(latest.id) : null;
   } catch (error: any) {
-    log.error(`Failed fetching tweets for ${username}: ${error?.message}`);
+    log.error(`Failed fetching tweets for ${account.account_name}: ${error?.message}`);
     return null;
   }
 };
@@ -156,7 +173,7 @@ const processReplies = async (configData: ActionsConfig): Promise<ReplyResult> =
       }
 
       const sinceId = lastChecked.get(targetId);
-      const latestId = await getLatestTweetId(client, targetAccount.account_name, sinceId);
+      const latestId = await getLatestTweetId(client, targetAccount, sinceId);
       if (!latestId || latestId === sinceId) {
         continue;
       }
@@ -1,13 +1,26 @@
 ﻿import { TwitterApi } from "twitter-api-v2";
 
+const SECRET_REFERENCE = /^\$\{\{\s*secrets\.([A-Za-z0-9_]+)\s*\}\}$/;
+
+/**
+ * Resolves `${{ secrets.NAME }}` references written by the desktop exporter.
+ * The workflow maps each referenced secret into an env var of the same name.
+ */
+export function resolveSecret(value: unknown): string | undefined {
+  if (typeof value !== "string") return undefined;
+  const match = SECRET_REFERENCE.exec(value.trim());
+  if (!match) return value;
+  return process.env[match[1]];
+}
+
 export function createTwitterClient(account: any) {
-  if (!account?.api_key || !account?.api_key_secret || !account?.access_token || !account?.access_token_secret) {
+  const appKey = resolveSecret(account?.api_key);
+  const appSecret = resolveSecret(account?.api_key_secret);
+  const accessToken = resolveSecret(account?.access_token);
+  const accessSecret = resolveSecret(account?.access_token_secret);
+
+  if (!appKey || !appSecret || !accessToken || !accessSecret) {
     throw new Error("Missing Twitter API credentials");
   }
-  return new TwitterApi({
-    appKey: account.api_key,
-    appSecret: account.api_key_secret,
-    accessToken: account.access_token,
-    accessSecret: account.access_token_secret,
-  });
+  return new TwitterApi({ appKey, appSecret, accessToken, accessSecret });
 }
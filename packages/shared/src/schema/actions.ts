@@ -2,6 +2,7 @@ import { z } from "zod";
 import { BotSchema, ReplySettingsSchema } from "./bots.js";
 
 export const ActionsConfigSchema = z.object({
+  format: z.literal("tam-actions-config").optional(),
   version: z.string().optional(),
   bots: z.array(BotSchema),
   reply_settings: z.array(ReplySettingsSchema).optional(),
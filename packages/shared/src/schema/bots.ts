@@ -12,7 +12,12 @@ export const AccountSchema = z.object({
   api_key_secret: z.string().optional(),
   access_token: z.string().optional(),
   access_token_secret: z.string().optional(),
-  status: z.enum(["active", "inactive", "error"]).optional(),
+  status: z.enum(["active", "inactive", "error", "invalid_credentials"]).optional(),
+  twitter_user_id: z.string().optional(),
+  twitter_username: z.string().optional(),
+  twitter_display_name: z.string().optional(),
+  last_verified_at: z.string().optional(),
+  credential_health: z.enum(["unknown", "valid", "invalid", "check_failed"]).optional(),
   created_at: z.string().optional(),
   updated_at: z.string().optional(),
 });